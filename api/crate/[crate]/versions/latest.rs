@@ -1,34 +1,8 @@
-use http::{Method, StatusCode};
-use mason_registry_api::{
-    crates::{api::CrateResponse, manager::CratesManager},
-    vercel::parse_url,
-    QueryParams,
-};
-use vercel_runtime::{run, Body, Error, Request, Response};
-
-async fn handler(request: Request) -> Result<Response<Body>, Error> {
-    if request.method() != Method::GET {
-        return Ok(Response::builder()
-            .status(StatusCode::METHOD_NOT_ALLOWED)
-            .body(Body::Empty)?);
-    }
-
-    let url = parse_url(&request)?;
-    let query_params: QueryParams = (&url).into();
-    let crate_pkg = (&query_params).into();
-    let manager = CratesManager::new();
-
-    match manager.get_crate(crate_pkg) {
-        Ok(crate_response) => mason_registry_api::vercel::ok_json::<CrateResponse>(
-            crate_response.into(),
-            mason_registry_api::CacheControl::PublicMedium,
-        ),
-        Err(err) => mason_registry_api::vercel::err_json(err),
-    }
-}
+use mason_registry_api::{handlers::crates::latest_version, vercel::conditional};
+use vercel_runtime::{run, Error};
 
 #[tokio::main]
 async fn main() -> Result<(), Error> {
     mason_registry_api::setup_tracing();
-    run(handler).await
+    run(|request| conditional(request, latest_version)).await
 }
@@ -0,0 +1,8 @@
+use mason_registry_api::{handlers::hackage::version, vercel::conditional};
+use vercel_runtime::{run, Error};
+
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    mason_registry_api::setup_tracing();
+    run(|request| conditional(request, version)).await
+}
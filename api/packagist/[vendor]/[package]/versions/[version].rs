@@ -1,35 +1,8 @@
-use http::{Method, StatusCode};
-use mason_registry_api::{
-    packagist::{api::PackagistResponse, client::PackagistClient, manager::PackagistManager},
-    vercel::parse_url,
-    QueryParams,
-};
-use vercel_runtime::{run, Body, Error, Request, Response};
-
-async fn handler(request: Request) -> Result<Response<Body>, Error> {
-    if request.method() != Method::GET {
-        return Ok(Response::builder()
-            .status(StatusCode::METHOD_NOT_ALLOWED)
-            .body(Body::Empty)?);
-    }
-
-    let url = parse_url(&request)?;
-    let query_params: QueryParams = (&url).into();
-    let packagist_package = (&query_params).into();
-    let version = query_params.get("version").unwrap();
-    let manager = PackagistManager::new(PackagistClient::new());
-
-    match manager.get_package_version(&packagist_package, version) {
-        Ok(package) => mason_registry_api::vercel::ok_json(
-            PackagistResponse::from_packagist_package_dto(packagist_package.name, package),
-            mason_registry_api::CacheControl::PublicMedium,
-        ),
-        Err(err) => mason_registry_api::vercel::err_json(err),
-    }
-}
+use mason_registry_api::{handlers::packagist::version, vercel::conditional};
+use vercel_runtime::{run, Error};
 
 #[tokio::main]
 async fn main() -> Result<(), Error> {
     mason_registry_api::setup_tracing();
-    run(handler).await
+    run(|request| conditional(request, version)).await
 }
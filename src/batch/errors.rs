@@ -0,0 +1,34 @@
+use http::StatusCode;
+use thiserror::Error;
+
+use crate::errors::ApiError;
+
+#[derive(Error, Debug)]
+pub enum BatchError {
+    #[error("Invalid batch request. {source}")]
+    InvalidRequest { source: serde_json::Error },
+    #[error("Batch requests are limited to {max} packages, got {actual}.")]
+    TooManyPackages { max: usize, actual: usize },
+    #[error("Invalid {ecosystem} package identifier {package:?}, expected {expected}.")]
+    InvalidPackage {
+        ecosystem: &'static str,
+        package: String,
+        expected: &'static str,
+    },
+}
+
+impl ApiError for BatchError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            BatchError::InvalidRequest { .. }
+            | BatchError::TooManyPackages { .. }
+            | BatchError::InvalidPackage { .. } => StatusCode::BAD_REQUEST,
+        }
+    }
+}
+
+impl From<serde_json::Error> for BatchError {
+    fn from(source: serde_json::Error) -> Self {
+        Self::InvalidRequest { source }
+    }
+}
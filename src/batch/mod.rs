@@ -0,0 +1,399 @@
+//! Looks up the latest versions of many packages, across ecosystems, in a single request.
+
+use std::collections::BTreeMap;
+
+use futures::{stream, StreamExt};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    errors::ApiError,
+    purl::{errors::PurlError, manager::PurlManager, Purl, PurlQuery},
+    registry::{PackageVersion, Registry},
+};
+
+use self::errors::BatchError;
+
+pub mod errors;
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Ecosystem {
+    Crate,
+    GitHub,
+    Golang,
+    Hackage,
+    LuaRocks,
+    Maven,
+    Npm,
+    NuGet,
+    Opam,
+    OpenVSX,
+    Packagist,
+    PyPi,
+    RubyGems,
+}
+
+impl Ecosystem {
+    /// The ecosystem's name in batch requests, the package URL type its packages are looked up
+    /// as, and the format of its package identifiers.
+    fn describe(self) -> (&'static str, &'static str, &'static str) {
+        match self {
+            Ecosystem::Crate => ("crate", "cargo", "<name>"),
+            Ecosystem::GitHub => ("github", "github", "<owner>/<name>"),
+            Ecosystem::Golang => ("golang", "golang", "<module path>"),
+            Ecosystem::Hackage => ("hackage", "hackage", "<name>"),
+            Ecosystem::LuaRocks => ("luarocks", "luarocks", "<name>"),
+            Ecosystem::Maven => ("maven", "maven", "<groupId>:<artifactId>"),
+            Ecosystem::Npm => ("npm", "npm", "<name> or @<scope>/<name>"),
+            Ecosystem::NuGet => ("nuget", "nuget", "<id>"),
+            Ecosystem::Opam => ("opam", "opam", "<name>"),
+            Ecosystem::OpenVSX => ("openvsx", "openvsx", "<namespace>/<extension>"),
+            Ecosystem::Packagist => ("packagist", "composer", "<vendor>/<package>"),
+            Ecosystem::PyPi => ("pypi", "pypi", "<name>"),
+            Ecosystem::RubyGems => ("rubygems", "gem", "<name>"),
+        }
+    }
+}
+
+/// A package to look up, identified the same way as in the ecosystem's routes, e.g.
+/// `@angular/cli` (npm), `sumneko/vscode-lua` (GitHub), `golang.org/x/tools/gopls` (Go) or
+/// `org.eclipse.jdt:org.eclipse.jdt.core` (Maven).
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct BatchPackage {
+    pub ecosystem: Ecosystem,
+    pub package: String,
+}
+
+impl BatchPackage {
+    fn invalid(&self) -> BatchError {
+        let (ecosystem, _, expected) = self.ecosystem.describe();
+        BatchError::InvalidPackage {
+            ecosystem,
+            package: self.package.clone(),
+            expected,
+        }
+    }
+
+    /// The package URL the package is looked up by, e.g. `pkg:npm/%40angular/cli` for
+    /// `@angular/cli` (npm).
+    fn to_purl(&self) -> Result<Purl, BatchError> {
+        let package = self.package.as_str();
+        let (namespace, name) = match self.ecosystem {
+            Ecosystem::GitHub | Ecosystem::OpenVSX | Ecosystem::Packagist => {
+                let (namespace, name) = split_pair(package).ok_or_else(|| self.invalid())?;
+                (Some(namespace.to_owned()), name)
+            }
+            Ecosystem::Golang => match package.rsplit_once('/') {
+                Some((namespace, name)) => (Some(namespace.to_owned()), name),
+                None => (None, package),
+            },
+            Ecosystem::Maven => {
+                let (group_id, artifact_id) =
+                    package.split_once(':').ok_or_else(|| self.invalid())?;
+                (Some(group_id.to_owned()), artifact_id)
+            }
+            Ecosystem::Npm => match package.strip_prefix('@') {
+                Some(scoped) => {
+                    let (scope, name) = split_pair(scoped).ok_or_else(|| self.invalid())?;
+                    (Some(format!("@{}", scope)), name)
+                }
+                None => (None, package),
+            },
+            Ecosystem::Crate
+            | Ecosystem::Hackage
+            | Ecosystem::LuaRocks
+            | Ecosystem::NuGet
+            | Ecosystem::Opam
+            | Ecosystem::PyPi
+            | Ecosystem::RubyGems => (None, package),
+        };
+        let (_, package_type, _) = self.ecosystem.describe();
+        Ok(Purl {
+            package_type: package_type.to_owned(),
+            namespace,
+            name: name.to_owned(),
+            version: None,
+            qualifiers: BTreeMap::new(),
+            subpath: None,
+        })
+    }
+}
+
+/// A package to look up, either by package URL or by ecosystem and identifier.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum BatchTarget {
+    /// A package URL, as in the purl routes, e.g. `pkg:npm/%40angular/cli`.
+    Purl {
+        purl: String,
+    },
+    Package(BatchPackage),
+}
+
+/// A package to look up, along with the same flags the individual routes accept.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct BatchLookup {
+    #[serde(flatten)]
+    pub target: BatchTarget,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub include_prerelease: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub include_yanked: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub include_draft: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub include_deprecated: Option<bool>,
+}
+
+impl From<BatchPackage> for BatchLookup {
+    fn from(package: BatchPackage) -> Self {
+        Self {
+            target: BatchTarget::Package(package),
+            include_prerelease: None,
+            include_yanked: None,
+            include_draft: None,
+            include_deprecated: None,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct BatchRequest {
+    pub packages: Vec<BatchLookup>,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct BatchItemError {
+    pub status: u16,
+    pub message: String,
+}
+
+impl<E: ApiError> From<E> for BatchItemError {
+    fn from(error: E) -> Self {
+        Self {
+            status: error.status_code().as_u16(),
+            message: error.to_string(),
+        }
+    }
+}
+
+/// The outcome of looking up a single package, with either `version` or `error` set.
+#[derive(Serialize, Debug)]
+pub struct BatchItem {
+    #[serde(flatten)]
+    pub package: BatchLookup,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<PackageVersion>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<BatchItemError>,
+}
+
+/// Splits identifiers of the form `<first>/<second>`.
+fn split_pair(package: &str) -> Option<(&str, &str)> {
+    package
+        .split_once('/')
+        .filter(|(first, second)| !first.is_empty() && !second.is_empty() && !second.contains('/'))
+}
+
+/// Looks up packages by resolving them to package URLs, see [`PurlManager`].
+pub struct BatchManager {
+    purls: PurlManager,
+}
+
+impl Default for BatchManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BatchManager {
+    pub const MAX_PACKAGES: usize = 100;
+    pub const MAX_CONCURRENT_LOOKUPS: usize = 16;
+
+    pub fn new() -> Self {
+        Self {
+            purls: PurlManager::new(),
+        }
+    }
+
+    async fn get_latest_version(
+        &self,
+        lookup: &BatchLookup,
+    ) -> Result<PackageVersion, BatchItemError> {
+        let query = PurlQuery {
+            purl: match &lookup.target {
+                BatchTarget::Purl { purl } => purl.parse()?,
+                BatchTarget::Package(package) => package.to_purl()?,
+            },
+            include_prerelease: lookup.include_prerelease,
+            include_yanked: lookup.include_yanked,
+            include_draft: lookup.include_draft,
+            include_deprecated: lookup.include_deprecated,
+            target: None,
+        };
+        self.purls
+            .get_latest_version(&query)
+            .await
+            .map_err(|err| match (err, &lookup.target) {
+                // Report invalid identifiers as such, rather than as invalid package URLs.
+                (PurlError::InvalidPackage { .. }, BatchTarget::Package(package)) => {
+                    package.invalid().into()
+                }
+                (err, _) => err.into(),
+            })
+    }
+
+    /// Looks up the latest version of all `packages` concurrently, returning the results in the
+    /// same order. Failed lookups are reported per package rather than failing the whole batch.
+    pub async fn get_latest_versions(
+        &self,
+        packages: Vec<BatchLookup>,
+    ) -> Result<Vec<BatchItem>, BatchError> {
+        if packages.len() > Self::MAX_PACKAGES {
+            return Err(BatchError::TooManyPackages {
+                max: Self::MAX_PACKAGES,
+                actual: packages.len(),
+            });
+        }
+        Ok(stream::iter(packages)
+            .map(|package| async move {
+                let (version, error) = match self.get_latest_version(&package).await {
+                    Ok(version) => (Some(version), None),
+                    Err(error) => (None, Some(error)),
+                };
+                BatchItem {
+                    package,
+                    version,
+                    error,
+                }
+            })
+            .buffered(Self::MAX_CONCURRENT_LOOKUPS)
+            .collect()
+            .await)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_batch_request() {
+        let request: BatchRequest = serde_json::from_str(
+            r#"{"packages": [
+                {"ecosystem": "npm", "package": "@angular/cli"},
+                {"ecosystem": "github", "package": "sumneko/vscode-lua", "include_draft": true},
+                {"purl": "pkg:cargo/crates_io_api", "include_prerelease": true}
+            ]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            vec![
+                BatchLookup::from(BatchPackage {
+                    ecosystem: Ecosystem::Npm,
+                    package: "@angular/cli".to_owned(),
+                }),
+                BatchLookup {
+                    include_draft: Some(true),
+                    ..BatchLookup::from(BatchPackage {
+                        ecosystem: Ecosystem::GitHub,
+                        package: "sumneko/vscode-lua".to_owned(),
+                    })
+                },
+                BatchLookup {
+                    target: BatchTarget::Purl {
+                        purl: "pkg:cargo/crates_io_api".to_owned()
+                    },
+                    include_prerelease: Some(true),
+                    include_yanked: None,
+                    include_draft: None,
+                    include_deprecated: None,
+                },
+            ],
+            request.packages
+        );
+        assert!(serde_json::from_str::<BatchRequest>(
+            r#"{"packages": [{"ecosystem": "cpan", "package": "Moose"}]}"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn should_split_pairs() {
+        assert_eq!(
+            Some(("sumneko", "vscode-lua")),
+            split_pair("sumneko/vscode-lua")
+        );
+        for invalid in [
+            "sumneko",
+            "sumneko/",
+            "/vscode-lua",
+            "sumneko/vscode-lua/extra",
+        ] {
+            assert_eq!(None, split_pair(invalid), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn should_resolve_package_urls() {
+        let purl = |ecosystem, package: &str| {
+            let purl = BatchPackage {
+                ecosystem,
+                package: package.to_owned(),
+            }
+            .to_purl()?;
+            Ok::<_, BatchError>((purl.package_type, purl.namespace, purl.name))
+        };
+        assert_eq!(
+            (
+                "npm".to_owned(),
+                Some("@angular".to_owned()),
+                "cli".to_owned()
+            ),
+            purl(Ecosystem::Npm, "@angular/cli").unwrap()
+        );
+        assert_eq!(
+            (
+                "golang".to_owned(),
+                Some("golang.org/x/tools".to_owned()),
+                "gopls".to_owned()
+            ),
+            purl(Ecosystem::Golang, "golang.org/x/tools/gopls").unwrap()
+        );
+        assert_eq!(
+            (
+                "maven".to_owned(),
+                Some("org.eclipse.jdt".to_owned()),
+                "org.eclipse.jdt.core".to_owned()
+            ),
+            purl(Ecosystem::Maven, "org.eclipse.jdt:org.eclipse.jdt.core").unwrap()
+        );
+        assert_eq!(
+            ("gem".to_owned(), None, "rubocop".to_owned()),
+            purl(Ecosystem::RubyGems, "rubocop").unwrap()
+        );
+        assert!(matches!(
+            purl(Ecosystem::Packagist, "psalm"),
+            Err(BatchError::InvalidPackage {
+                ecosystem: "packagist",
+                expected: "<vendor>/<package>",
+                ..
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn should_reject_oversized_batches() {
+        let packages = vec![
+            BatchLookup::from(BatchPackage {
+                ecosystem: Ecosystem::Npm,
+                package: "typescript".to_owned(),
+            });
+            BatchManager::MAX_PACKAGES + 1
+        ];
+        assert!(matches!(
+            BatchManager::new().get_latest_versions(packages).await,
+            Err(BatchError::TooManyPackages { .. })
+        ));
+    }
+}
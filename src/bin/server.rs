@@ -0,0 +1,13 @@
+use std::net::SocketAddr;
+
+use tokio::net::TcpListener;
+use vercel_runtime::Error;
+
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    mason_registry_api::setup_tracing();
+    let host = std::env::var("HOST").unwrap_or_else(|_| "127.0.0.1".to_owned());
+    let port = std::env::var("PORT").unwrap_or_else(|_| "3000".to_owned());
+    let addr: SocketAddr = format!("{host}:{port}").parse()?;
+    mason_registry_api::server::serve(TcpListener::bind(addr).await?).await
+}
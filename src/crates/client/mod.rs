@@ -0,0 +1,71 @@
+use std::{fmt::Display, time::Duration};
+
+use crate::{
+    http::client::{base_url_from_env, Client, HttpEndpoint},
+    CacheControl,
+};
+
+use super::Crate;
+
+pub struct CratesClient {
+    client: Client,
+}
+
+enum CratesEndpoint<'a> {
+    Crate(&'a Crate),
+}
+
+impl<'a> HttpEndpoint for CratesEndpoint<'a> {
+    fn as_full_url(&self, base_url: &str) -> String {
+        format!("{}/{}", base_url, self)
+    }
+
+    fn cache_control(&self) -> CacheControl {
+        CacheControl::PublicMedium
+    }
+}
+
+impl<'a> Display for CratesEndpoint<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CratesEndpoint::Crate(crate_pkg) => {
+                f.write_fmt(format_args!("api/v1/crates/{}", crate_pkg.name))
+            }
+        }
+    }
+}
+
+impl Default for CratesClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CratesClient {
+    pub const DEFAULT_BASE_URL: &'static str = "https://crates.io";
+    /// crates.io's crawler policy allows at most one request per second.
+    pub const MIN_REQUEST_INTERVAL: Duration = Duration::from_secs(1);
+
+    /// Creates a client for the registry configured via `CRATES_API_URL`, defaulting to crates.io.
+    pub fn new() -> Self {
+        Self::with_base_url(base_url_from_env("CRATES_API_URL", Self::DEFAULT_BASE_URL))
+    }
+
+    pub fn with_base_url(base_url: String) -> Self {
+        Self {
+            client: Client::new(base_url, None)
+                .with_min_request_interval(Self::MIN_REQUEST_INTERVAL),
+        }
+    }
+
+    pub async fn fetch_crate(
+        &self,
+        crate_pkg: &Crate,
+    ) -> Result<crates_io_api::CrateResponse, reqwest::Error> {
+        self.client
+            .get(CratesEndpoint::Crate(crate_pkg))
+            .await?
+            .json()
+            .await
+    }
+}
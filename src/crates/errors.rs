@@ -1,7 +1,9 @@
+use std::time::Duration;
+
 use http::StatusCode;
 use thiserror::Error;
 
-use crate::errors::ApiError;
+use crate::{errors::ApiError, http::client::DEFAULT_MAX_THROTTLE_WAIT};
 
 #[derive(Error, Debug)]
 pub enum CratesError {
@@ -13,6 +15,8 @@ pub enum CratesError {
     ServerError { source: Option<reqwest::Error> },
     #[error("Network error.")]
     NetworkError { source: Option<reqwest::Error> },
+    #[error("Crate registry API is unavailable, or too many requests to it are already queued.")]
+    Unavailable { source: Option<reqwest::Error> },
 }
 
 impl ApiError for CratesError {
@@ -23,6 +27,14 @@ impl ApiError for CratesError {
                 StatusCode::INTERNAL_SERVER_ERROR
             }
             CratesError::ServerError { .. } => StatusCode::BAD_GATEWAY,
+            CratesError::Unavailable { .. } => StatusCode::SERVICE_UNAVAILABLE,
+        }
+    }
+
+    fn retry_after(&self) -> Option<Duration> {
+        match self {
+            CratesError::Unavailable { .. } => Some(DEFAULT_MAX_THROTTLE_WAIT),
+            _ => None,
         }
     }
 }
@@ -33,6 +45,9 @@ impl From<reqwest::Error> for CratesError {
             Some(reqwest::StatusCode::NOT_FOUND) => Self::ResourceNotFound {
                 source: Some(req_error),
             },
+            Some(reqwest::StatusCode::SERVICE_UNAVAILABLE) => Self::Unavailable {
+                source: Some(req_error),
+            },
             Some(status_code) if status_code.is_server_error() => Self::ServerError {
                 source: Some(req_error),
             },
@@ -45,17 +60,3 @@ impl From<reqwest::Error> for CratesError {
         }
     }
 }
-
-impl From<crates_io_api::Error> for CratesError {
-    fn from(err: crates_io_api::Error) -> Self {
-        match err {
-            crates_io_api::Error::NotFound(_) => CratesError::ResourceNotFound { source: None },
-            crates_io_api::Error::Http(err) => CratesError::NetworkError { source: Some(err) },
-            crates_io_api::Error::Url(_) => CratesError::NetworkError { source: None },
-            crates_io_api::Error::PermissionDenied(_) => CratesError::ClientError { source: None },
-            crates_io_api::Error::JsonDecode(_) => CratesError::ServerError { source: None },
-            crates_io_api::Error::Api(_) => CratesError::ServerError { source: None },
-            _ => CratesError::ServerError { source: None },
-        }
-    }
-}
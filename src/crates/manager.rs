@@ -1,38 +1,52 @@
-use std::time::Duration;
+use crate::{
+    registry::{PackageVersion, Registry},
+    versioning::semver,
+};
 
-use super::{errors::CratesError, Crate};
+use super::{client::CratesClient, errors::CratesError, Crate};
 
 pub struct CratesManager {
-    client: crates_io_api::SyncClient,
+    client: CratesClient,
 }
 
 impl CratesManager {
-    pub fn new() -> Self {
-        Self {
-            client: crates_io_api::SyncClient::new(
-                "mason-registry-api (+https://github.com/mason-org/mason-registry-api)",
-                Duration::from_secs(1),
-            )
-            .expect("Failed to instantiate SyncClient."),
-        }
+    pub fn new(client: CratesClient) -> Self {
+        Self { client }
     }
 
-    pub fn get_crate(&self, crate_pkg: Crate) -> Result<crates_io_api::CrateResponse, CratesError> {
-        Ok(self.client.get_crate(&crate_pkg.name)?)
+    pub async fn get_crate(
+        &self,
+        crate_pkg: &Crate,
+    ) -> Result<crates_io_api::CrateResponse, CratesError> {
+        Ok(self.client.fetch_crate(crate_pkg).await?)
     }
 
-    /// Returns all crate versions in DESCENDING order.
-    pub fn get_all_crate_versions(&self, crate_pkg: Crate) -> Result<Vec<String>, CratesError> {
-        let crate_response = self.get_crate(crate_pkg)?;
-        return Ok(crate_response.versions.into_iter().map(|v| v.num).collect());
+    /// Returns all crate versions in DESCENDING order. Pre-releases and yanked versions are each
+    /// included unless opted out of.
+    pub async fn get_all_crate_versions(
+        &self,
+        crate_pkg: &Crate,
+    ) -> Result<Vec<String>, CratesError> {
+        let crate_response = self.get_crate(crate_pkg).await?;
+        let include_prerelease = crate_pkg.include_prerelease.unwrap_or(true);
+        let include_yanked = crate_pkg.include_yanked.unwrap_or(true);
+        let mut versions: Vec<String> = crate_response
+            .versions
+            .into_iter()
+            .filter(|v| include_prerelease || !semver::is_prerelease(&v.num))
+            .filter(|v| include_yanked || !v.yanked)
+            .map(|v| v.num)
+            .collect();
+        semver::sort_desc(&mut versions);
+        Ok(versions)
     }
 
-    pub fn get_crate_version(
+    pub async fn get_crate_version(
         &self,
-        crate_pkg: Crate,
+        crate_pkg: &Crate,
         version: &str,
     ) -> Result<crates_io_api::CrateResponse, CratesError> {
-        let crate_response = self.get_crate(crate_pkg)?;
+        let crate_response = self.get_crate(crate_pkg).await?;
         if crate_response.versions.iter().any(|v| v.num == version) {
             Ok(crate_response)
         } else {
@@ -40,3 +54,40 @@ impl CratesManager {
         }
     }
 }
+
+impl Registry for CratesManager {
+    type Package = Crate;
+    type Error = CratesError;
+
+    async fn get_all_versions(&self, crate_pkg: &Crate) -> Result<Vec<String>, CratesError> {
+        self.get_all_crate_versions(crate_pkg).await
+    }
+
+    async fn get_latest_version(&self, crate_pkg: &Crate) -> Result<PackageVersion, CratesError> {
+        let crate_response = self.get_crate(crate_pkg).await?;
+        let latest = semver::latest(
+            crate_response
+                .versions
+                .iter()
+                // Like Cargo, never resolve to a yanked version.
+                .filter(|v| !v.yanked)
+                .map(|v| v.num.as_str()),
+            crate_pkg.include_prerelease.unwrap_or(false),
+        )
+        .map(ToOwned::to_owned)
+        .unwrap_or(crate_response.crate_data.max_version);
+        Ok(PackageVersion::new(crate_response.crate_data.name, latest))
+    }
+
+    async fn get_version(
+        &self,
+        crate_pkg: &Crate,
+        version: &str,
+    ) -> Result<PackageVersion, CratesError> {
+        let crate_response = self.get_crate_version(crate_pkg, version).await?;
+        Ok(PackageVersion::new(
+            crate_response.crate_data.name,
+            version.to_owned(),
+        ))
+    }
+}
@@ -1,12 +1,29 @@
 use crate::QueryParams;
 
 pub mod api;
+pub mod client;
 pub mod errors;
 pub mod manager;
 
 #[derive(Debug)]
 pub struct Crate {
     pub name: String,
+    /// Whether to include pre-releases. Unless set, `versions/all` lists them but
+    /// `versions/latest` doesn't resolve to them.
+    pub include_prerelease: Option<bool>,
+    /// Whether to list yanked versions in `versions/all`, which they are unless opted out of.
+    /// Yanked versions are never resolved as the latest version.
+    pub include_yanked: Option<bool>,
+}
+
+impl Crate {
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            include_prerelease: None,
+            include_yanked: None,
+        }
+    }
 }
 
 impl From<&QueryParams> for Crate {
@@ -16,6 +33,8 @@ impl From<&QueryParams> for Crate {
                 .get("crate")
                 .expect("No [crate] query param")
                 .to_owned(),
+            include_prerelease: query.flag("include_prerelease"),
+            include_yanked: query.flag("include_yanked"),
         }
     }
 }
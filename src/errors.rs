@@ -1,4 +1,4 @@
-use std::fmt::Display;
+use std::{convert::Infallible, fmt::Display, time::Duration};
 
 use http::StatusCode;
 use url::ParseError;
@@ -24,6 +24,19 @@ impl From<ParseError> for CoreError {
 
 pub trait ApiError: Display {
     fn status_code(&self) -> StatusCode;
+
+    /// How long clients should wait before retrying, sent as the `Retry-After` header.
+    fn retry_after(&self) -> Option<Duration> {
+        None
+    }
+}
+
+/// Lets infallible conversions, e.g. of route params into packages, be used where fallible ones
+/// are expected.
+impl ApiError for Infallible {
+    fn status_code(&self) -> StatusCode {
+        match *self {}
+    }
 }
 
 impl ApiError for ParseError {
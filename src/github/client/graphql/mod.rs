@@ -1,5 +1,10 @@
+use std::time::SystemTime;
+
+use chrono::DateTime;
 use serde::Deserialize;
 
+use crate::github::rate_limit::RateLimit;
+
 pub mod sponsors;
 pub mod tags;
 
@@ -17,3 +22,23 @@ pub struct PageInfo {
     pub has_next_page: bool,
     pub has_previous_page: bool,
 }
+
+/// The `rateLimit` object, requested alongside every query to keep track of its cost.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphQLRateLimit {
+    pub cost: u64,
+    pub limit: u64,
+    pub remaining: u64,
+    pub reset_at: String,
+}
+
+impl GraphQLRateLimit {
+    pub fn to_rate_limit(&self) -> Option<RateLimit> {
+        Some(RateLimit {
+            limit: self.limit,
+            remaining: self.remaining,
+            reset: SystemTime::from(DateTime::parse_from_rfc3339(&self.reset_at).ok()?),
+        })
+    }
+}
@@ -3,7 +3,7 @@ use std::collections::HashMap;
 use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::Value;
 
-use super::PageInfo;
+use super::{GraphQLRateLimit, PageInfo};
 
 #[derive(Serialize)]
 pub struct Variables {
@@ -31,6 +31,12 @@ pub const QUERY: &str = r#"
           }
         }
       }
+      rateLimit {
+        cost
+        limit
+        remaining
+        resetAt
+      }
     }
 "#;
 
@@ -44,6 +50,7 @@ pub struct Sponsor {
 
 #[derive(Debug)]
 pub struct SponsorsQuery {
+    pub rate_limit: Option<GraphQLRateLimit>,
     pub sponsors: Vec<Sponsor>,
     pub page_info: PageInfo,
 }
@@ -54,9 +61,14 @@ impl<'de> Deserialize<'de> for SponsorsQuery {
         D: Deserializer<'de>,
     {
         let mut response: HashMap<String, Value> = HashMap::deserialize(deserializer)?;
-        let mut sponsors_connection = response
+        let mut data = response
             .remove("data")
-            .ok_or_else(|| serde::de::Error::missing_field("data key missing."))?
+            .ok_or_else(|| serde::de::Error::missing_field("data key missing."))?;
+        let rate_limit = data
+            .get_mut("rateLimit")
+            .map(Value::take)
+            .and_then(|rate_limit| serde_json::from_value(rate_limit).ok());
+        let mut sponsors_connection = data
             .get_mut("user")
             .ok_or_else(|| serde::de::Error::missing_field("user key missing."))?
             .take()
@@ -75,6 +87,7 @@ impl<'de> Deserialize<'de> for SponsorsQuery {
             .take();
 
         Ok(Self {
+            rate_limit,
             sponsors: serde_json::from_value(sponsors)
                 .map_err(|_| serde::de::Error::custom("Failed to deserialize sponsors."))?,
             page_info: serde_json::from_value(page_info)
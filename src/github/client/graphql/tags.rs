@@ -3,7 +3,7 @@ use std::collections::HashMap;
 use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::Value;
 
-use super::PageInfo;
+use super::{GraphQLRateLimit, PageInfo};
 
 #[derive(Serialize)]
 pub struct Variables {
@@ -28,6 +28,12 @@ pub const QUERY: &str = r#"
           }
         }
       }
+      rateLimit {
+        cost
+        limit
+        remaining
+        resetAt
+      }
     }
 "#;
 
@@ -38,6 +44,7 @@ pub struct Tag {
 
 #[derive(Debug)]
 pub struct TagsQuery {
+    pub rate_limit: Option<GraphQLRateLimit>,
     pub tags: Vec<Tag>,
     pub page_info: PageInfo,
 }
@@ -48,9 +55,14 @@ impl<'de> Deserialize<'de> for TagsQuery {
         D: Deserializer<'de>,
     {
         let mut response: HashMap<String, Value> = HashMap::deserialize(deserializer)?;
-        let mut tags_connection = response
+        let mut data = response
             .remove("data")
-            .ok_or_else(|| serde::de::Error::missing_field("data key missing."))?
+            .ok_or_else(|| serde::de::Error::missing_field("data key missing."))?;
+        let rate_limit = data
+            .get_mut("rateLimit")
+            .map(Value::take)
+            .and_then(|rate_limit| serde_json::from_value(rate_limit).ok());
+        let mut tags_connection = data
             .get_mut("repository")
             .ok_or_else(|| serde::de::Error::missing_field("repository key missing."))?
             .take()
@@ -69,6 +81,7 @@ impl<'de> Deserialize<'de> for TagsQuery {
             .take();
 
         Ok(Self {
+            rate_limit,
             tags: serde_json::from_value(tags)
                 .map_err(|_| serde::de::Error::custom("Failed to deserialize tags."))?,
             page_info: serde_json::from_value(page_info)
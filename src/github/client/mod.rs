@@ -2,24 +2,44 @@ pub mod graphql;
 pub mod response;
 pub mod spec;
 
-use std::{convert::TryInto, fmt::Display};
+use std::{
+    cmp::Reverse,
+    collections::HashSet,
+    fmt::Display,
+    future::Future,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 
-use parse_link_header::Link;
+use futures::{stream, StreamExt};
 use reqwest::{
-    blocking::Response,
     header::{HeaderMap, ACCEPT, AUTHORIZATION},
+    Response, StatusCode,
 };
 use serde::{de::DeserializeOwned, Serialize};
 
-use crate::http::client::{Client, HttpEndpoint};
+use crate::{
+    http::{
+        client::{base_url_from_env, Client, HttpEndpoint},
+        retry,
+    },
+    CacheControl,
+};
 
 use self::{
-    graphql::{sponsors::SponsorsQuery, tags::TagsQuery},
+    graphql::{sponsors::SponsorsQuery, tags::TagsQuery, GraphQLRateLimit},
     response::GitHubResponse,
     spec::{GitHubRef, GitHubReleaseDto},
 };
 
-use super::{GitHubRefId, GitHubRepo, GitHubTag};
+use super::{
+    errors::GitHubError,
+    rate_limit::{RateLimit, RateLimitResource, RateLimits},
+    GitHubRefId, GitHubRepo, GitHubTag,
+};
 
 #[derive(Serialize)]
 pub struct GraphQLRequest<Variables: Serialize> {
@@ -29,7 +49,7 @@ pub struct GraphQLRequest<Variables: Serialize> {
 
 enum GitHubApiEndpoint<'a> {
     GraphQL,
-    Link(Link),
+    Link(String),
     Releases(&'a GitHubRepo),
     ReleaseTag(&'a GitHubRepo, &'a GitHubTag),
     LatestRelease(&'a GitHubRepo),
@@ -37,10 +57,29 @@ enum GitHubApiEndpoint<'a> {
 }
 
 impl<'a> HttpEndpoint for GitHubApiEndpoint<'a> {
-    fn as_full_url(&self) -> String {
+    fn as_full_url(&self, base_url: &str) -> String {
+        match self {
+            GitHubApiEndpoint::Link(uri) => uri.to_owned(),
+            endpoint => format!("{}/{}", base_url, endpoint),
+        }
+    }
+
+    fn cache_control(&self) -> CacheControl {
+        match self {
+            // GraphQL errors, e.g. an exhausted rate limit, are reported in the body of a 200
+            // response, and the cache key doesn't tell tokens apart.
+            GitHubApiEndpoint::GraphQL => CacheControl::NoStore,
+            GitHubApiEndpoint::LatestRelease(_) => CacheControl::PublicShort,
+            _ => CacheControl::PublicMedium,
+        }
+    }
+}
+
+impl<'a> GitHubApiEndpoint<'a> {
+    fn resource(&self) -> RateLimitResource {
         match self {
-            GitHubApiEndpoint::Link(uri) => uri.raw_uri.to_owned(),
-            endpoint => format!("https://api.github.com/{}", endpoint),
+            GitHubApiEndpoint::GraphQL => RateLimitResource::GraphQL,
+            _ => RateLimitResource::Core,
         }
     }
 }
@@ -49,7 +88,7 @@ impl<'a> Display for GitHubApiEndpoint<'a> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             GitHubApiEndpoint::GraphQL => f.write_str("graphql"),
-            GitHubApiEndpoint::Link(link) => f.write_str(&link.raw_uri),
+            GitHubApiEndpoint::Link(link) => f.write_str(link),
             GitHubApiEndpoint::Releases(repo) => {
                 f.write_fmt(format_args!("repos/{}/releases", repo))
             }
@@ -78,12 +117,31 @@ impl GitHubPagination {
     pub const MAX_PAGE_LIMIT: u8 = 100;
 }
 
-pub struct GitHubClient {
+/// A request that can be sent with any of the client's tokens.
+enum GitHubRequest<'a> {
+    Get(GitHubApiEndpoint<'a>, Vec<(&'static str, u8)>),
+    GraphQL(serde_json::Value),
+}
+
+impl<'a> GitHubRequest<'a> {
+    async fn send(&self, client: &Client) -> Result<Response, reqwest::Error> {
+        match self {
+            GitHubRequest::Get(endpoint, query) if query.is_empty() => client.get(endpoint).await,
+            GitHubRequest::Get(endpoint, query) => client.get_with_query(endpoint, query).await,
+            GitHubRequest::GraphQL(body) => client.post(GitHubApiEndpoint::GraphQL, body).await,
+        }
+    }
+}
+
+/// A client for a single token, or for unauthenticated requests.
+struct GitHubToken {
     client: Client,
+    rate_limits: Arc<RateLimits>,
+    is_authenticated: bool,
 }
 
-impl GitHubClient {
-    pub fn new(api_key: String) -> Self {
+impl GitHubToken {
+    fn new(api_key: Option<&str>, base_url: String) -> Self {
         let mut headers = HeaderMap::new();
         headers.insert(
             ACCEPT,
@@ -91,23 +149,144 @@ impl GitHubClient {
                 .parse()
                 .unwrap(),
         );
-        headers.insert(
-            AUTHORIZATION,
-            format!("Bearer {}", api_key).parse().unwrap(),
-        );
-        GitHubClient {
-            client: Client::new(Some(headers)),
+        if let Some(api_key) = api_key {
+            headers.insert(
+                AUTHORIZATION,
+                format!("Bearer {}", api_key).parse().unwrap(),
+            );
+        }
+        let rate_limits = RateLimits::for_token(api_key);
+        let hook_rate_limits = rate_limits.clone();
+        Self {
+            client: Client::new(base_url, Some(headers))
+                .with_error_responses()
+                .with_response_hook(Arc::new(move |response: &Response| {
+                    hook_rate_limits.record_headers(response.headers())
+                })),
+            rate_limits,
+            is_authenticated: api_key.is_some(),
         }
     }
+}
+
+/// Returns the pool of GitHub tokens configured via `GITHUB_API_KEYS` (comma-separated) and
+/// `GITHUB_API_KEY`, without duplicates.
+pub fn api_keys_from_env() -> Vec<String> {
+    let mut seen = HashSet::new();
+    ["GITHUB_API_KEYS", "GITHUB_API_KEY"]
+        .iter()
+        .filter_map(|key| std::env::var(key).ok())
+        .flat_map(|value| {
+            value
+                .split(',')
+                .map(str::trim)
+                .filter(|token| !token.is_empty())
+                .map(ToOwned::to_owned)
+                .collect::<Vec<_>>()
+        })
+        .filter(|token| seen.insert(token.clone()))
+        .collect()
+}
+
+pub struct GitHubClient {
+    tokens: Vec<GitHubToken>,
+}
+
+impl GitHubClient {
+    pub const DEFAULT_BASE_URL: &'static str = "https://api.github.com";
+    pub const MAX_CONCURRENT_PAGES: usize = 4;
 
-    pub fn paginate<T, Init, Cond>(&self, init: Init, cond: Cond) -> Result<Vec<T>, reqwest::Error>
+    /// Creates a client for the API configured via `GITHUB_API_URL`, defaulting to api.github.com,
+    /// using the tokens returned by [`api_keys_from_env`].
+    pub fn new() -> Self {
+        Self::with_base_url(
+            api_keys_from_env(),
+            base_url_from_env("GITHUB_API_URL", Self::DEFAULT_BASE_URL),
+        )
+    }
+
+    /// Requests are spread over `api_keys` based on their remaining rate limit budget. Without any
+    /// keys, REST requests are sent unauthenticated.
+    pub fn with_base_url(mut api_keys: Vec<String>, base_url: String) -> Self {
+        let mut seen = HashSet::new();
+        api_keys.retain(|api_key| seen.insert(api_key.clone()));
+        let tokens = if api_keys.is_empty() {
+            vec![GitHubToken::new(None, base_url)]
+        } else {
+            api_keys
+                .iter()
+                .map(|api_key| GitHubToken::new(Some(api_key), base_url.clone()))
+                .collect()
+        };
+        Self { tokens }
+    }
+
+    /// Returns the most recently observed rate limit budget of every token for `resource`.
+    pub fn rate_limits(&self, resource: RateLimitResource) -> Vec<RateLimit> {
+        self.tokens
+            .iter()
+            .filter_map(|token| token.rate_limits.get(resource))
+            .collect()
+    }
+
+    /// Returns the tokens able to send a request for `resource`, the ones with the most remaining
+    /// budget first. Tokens with an equal (or unknown) budget take turns.
+    fn candidates(&self, resource: RateLimitResource) -> Vec<&GitHubToken> {
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+        let mut candidates: Vec<&GitHubToken> = self
+            .tokens
+            .iter()
+            // GitHub's GraphQL API doesn't support unauthenticated requests.
+            .filter(|token| token.is_authenticated || resource == RateLimitResource::Core)
+            .collect();
+        if !candidates.is_empty() {
+            let offset = NEXT.fetch_add(1, Ordering::Relaxed) % candidates.len();
+            candidates.rotate_left(offset);
+        }
+        candidates.sort_by_key(|token| {
+            Reverse(
+                token
+                    .rate_limits
+                    .get(resource)
+                    .map_or(u64::MAX, |rate_limit| rate_limit.remaining),
+            )
+        });
+        candidates
+    }
+
+    /// Collects the data of all pages, starting with `init`, for as long as `cond` holds. Once a
+    /// response links to the last page, the remaining pages are fetched concurrently (at most
+    /// [`Self::MAX_CONCURRENT_PAGES`] at a time), while still being consumed in order.
+    pub async fn paginate<T, Init, Cond>(
+        &self,
+        init: Init,
+        cond: Cond,
+    ) -> Result<Vec<T>, GitHubError>
     where
         T: DeserializeOwned,
-        Init: Fn() -> Result<GitHubResponse<Vec<T>>, reqwest::Error>,
+        Init: Future<Output = Result<GitHubResponse<Vec<T>>, GitHubError>>,
         Cond: Fn(&GitHubResponse<Vec<T>>) -> bool,
     {
         let mut data = Vec::with_capacity(GitHubPagination::MAX_PAGE_LIMIT.into());
-        let mut response = init()?;
+        let mut response = init.await?;
+        if let Some(pages) = remaining_pages(&response) {
+            let mut responses = stream::iter(pages)
+                .map(|page| self.get(GitHubApiEndpoint::Link(page)))
+                .buffered(Self::MAX_CONCURRENT_PAGES);
+            loop {
+                let should_continue = cond(&response);
+                data.append(&mut response.data);
+                if !should_continue {
+                    break;
+                }
+                match responses.next().await {
+                    Some(next) => response = next?,
+                    None => break,
+                }
+            }
+            return Ok(data);
+        }
+
         loop {
             let should_continue = cond(&response);
             data.append(&mut response.data);
@@ -116,7 +295,7 @@ impl GitHubClient {
             }
             if let Some(mut links) = response.links {
                 if let Some(next) = links.remove(&Some("next".to_owned())) {
-                    response = self.client.get(GitHubApiEndpoint::Link(next))?.try_into()?;
+                    response = self.get(GitHubApiEndpoint::Link(next.raw_uri)).await?;
                 } else {
                     break;
                 }
@@ -127,97 +306,470 @@ impl GitHubClient {
         Ok(data)
     }
 
-    pub fn fetch_tags(
+    pub async fn fetch_tags(
         &self,
         repo: &GitHubRepo,
         first: u64,
         after: Option<String>,
-    ) -> Result<GitHubResponse<TagsQuery>, reqwest::Error> {
-        self.graphql(GraphQLRequest {
-            query: graphql::tags::QUERY.to_owned(),
-            variables: graphql::tags::Variables {
-                owner: repo.owner.clone(),
-                name: repo.name.clone(),
-                first,
-                after,
+    ) -> Result<GitHubResponse<TagsQuery>, GitHubError> {
+        self.graphql(
+            GraphQLRequest {
+                query: graphql::tags::QUERY.to_owned(),
+                variables: graphql::tags::Variables {
+                    owner: repo.owner.clone(),
+                    name: repo.name.clone(),
+                    first,
+                    after,
+                },
             },
-        })?
-        .try_into()
+            |query: &TagsQuery| query.rate_limit.as_ref(),
+        )
+        .await
     }
 
-    pub fn fetch_sponsors(
+    pub async fn fetch_sponsors(
         &self,
         login: String,
         first: u64,
         after: Option<String>,
-    ) -> Result<GitHubResponse<SponsorsQuery>, reqwest::Error> {
-        self.graphql(GraphQLRequest {
-            query: graphql::sponsors::QUERY.to_owned(),
-            variables: graphql::sponsors::Variables {
-                login,
-                first,
-                after,
+    ) -> Result<GitHubResponse<SponsorsQuery>, GitHubError> {
+        self.graphql(
+            GraphQLRequest {
+                query: graphql::sponsors::QUERY.to_owned(),
+                variables: graphql::sponsors::Variables {
+                    login,
+                    first,
+                    after,
+                },
             },
-        })?
-        .try_into()
+            |query: &SponsorsQuery| query.rate_limit.as_ref(),
+        )
+        .await
     }
 
-    pub fn fetch_ref<GitRef: GitHubRefId>(
+    pub async fn fetch_ref<GitRef: GitHubRefId>(
         &self,
         repo: &GitHubRepo,
         ref_id: &GitRef,
-    ) -> Result<GitHubResponse<GitHubRef>, reqwest::Error> {
-        self.client
-            .get(GitHubApiEndpoint::GitRef(repo, ref_id))?
-            .try_into()
+    ) -> Result<GitHubResponse<GitHubRef>, GitHubError> {
+        self.get(GitHubApiEndpoint::GitRef(repo, ref_id)).await
     }
 
-    pub fn fetch_releases(
+    pub async fn fetch_releases(
         &self,
         repo: &GitHubRepo,
         pagination: Option<GitHubPagination>,
-    ) -> Result<GitHubResponse<Vec<GitHubReleaseDto>>, reqwest::Error> {
+    ) -> Result<GitHubResponse<Vec<GitHubReleaseDto>>, GitHubError> {
         match pagination {
             Some(pagination) => {
                 self.get_with_pagination(GitHubApiEndpoint::Releases(repo), pagination)
+                    .await
             }
-            None => self.client.get(GitHubApiEndpoint::Releases(repo)),
-        }?
-        .try_into()
+            None => self.get(GitHubApiEndpoint::Releases(repo)).await,
+        }
     }
 
-    pub fn fetch_release_by_tag(
+    pub async fn fetch_release_by_tag(
         &self,
         repo: &GitHubRepo,
         release: &GitHubTag,
-    ) -> Result<GitHubResponse<GitHubReleaseDto>, reqwest::Error> {
-        self.client
-            .get(GitHubApiEndpoint::ReleaseTag(&repo, &release))?
-            .try_into()
+    ) -> Result<GitHubResponse<GitHubReleaseDto>, GitHubError> {
+        self.get(GitHubApiEndpoint::ReleaseTag(repo, release)).await
     }
 
-    pub fn fetch_latest_release(
+    pub async fn fetch_latest_release(
         &self,
         repo: &GitHubRepo,
-    ) -> Result<GitHubResponse<GitHubReleaseDto>, reqwest::Error> {
-        self.client
-            .get(GitHubApiEndpoint::LatestRelease(&repo))?
-            .try_into()
+    ) -> Result<GitHubResponse<GitHubReleaseDto>, GitHubError> {
+        self.get(GitHubApiEndpoint::LatestRelease(repo)).await
     }
 
-    fn graphql<Variables: Serialize>(
+    async fn graphql<Variables: Serialize, T: DeserializeOwned>(
         &self,
         request: GraphQLRequest<Variables>,
-    ) -> Result<Response, reqwest::Error> {
-        self.client.post(GitHubApiEndpoint::GraphQL, &request)
+        rate_limit: impl Fn(&T) -> Option<&GraphQLRateLimit>,
+    ) -> Result<GitHubResponse<T>, GitHubError> {
+        let body = serde_json::to_value(request).expect("GraphQL requests are serializable.");
+        let (token, response) = self
+            .dispatch(RateLimitResource::GraphQL, GitHubRequest::GraphQL(body))
+            .await?;
+        if let Some(rate_limit) = rate_limit(&response.data) {
+            tracing::debug!(
+                "GitHub GraphQL query cost {}, {}/{} remaining.",
+                rate_limit.cost,
+                rate_limit.remaining,
+                rate_limit.limit
+            );
+            if let Some(rate_limit) = rate_limit.to_rate_limit() {
+                token
+                    .rate_limits
+                    .record(RateLimitResource::GraphQL, rate_limit);
+            }
+        }
+        Ok(response)
+    }
+
+    async fn get<T: DeserializeOwned>(
+        &self,
+        endpoint: GitHubApiEndpoint<'_>,
+    ) -> Result<GitHubResponse<T>, GitHubError> {
+        self.send(endpoint.resource(), GitHubRequest::Get(endpoint, vec![]))
+            .await
     }
 
-    fn get_with_pagination(
+    async fn get_with_pagination<T: DeserializeOwned>(
         &self,
-        endpoint: GitHubApiEndpoint,
+        endpoint: GitHubApiEndpoint<'_>,
         pagination: GitHubPagination,
-    ) -> Result<Response, reqwest::Error> {
+    ) -> Result<GitHubResponse<T>, GitHubError> {
         let query = vec![("page", pagination.page), ("per_page", pagination.per_page)];
-        self.client.get_with_query(endpoint, &query)
+        self.send(endpoint.resource(), GitHubRequest::Get(endpoint, query))
+            .await
+    }
+
+    async fn send<T: DeserializeOwned>(
+        &self,
+        resource: RateLimitResource,
+        request: GitHubRequest<'_>,
+    ) -> Result<GitHubResponse<T>, GitHubError> {
+        self.dispatch(resource, request)
+            .await
+            .map(|(_, response)| response)
+    }
+
+    /// Sends a request with the token with the most remaining budget, failing over to the next one
+    /// if its budget turns out to be exhausted. Tokens known to be exhausted are skipped, as
+    /// there's no point in asking GitHub. Returns the token that was used along with the response.
+    async fn dispatch<T: DeserializeOwned>(
+        &self,
+        resource: RateLimitResource,
+        request: GitHubRequest<'_>,
+    ) -> Result<(&GitHubToken, GitHubResponse<T>), GitHubError> {
+        let candidates = self.candidates(resource);
+        if candidates.is_empty() {
+            return Err(GitHubError::MissingToken);
+        }
+
+        let mut retry_after = Duration::MAX;
+        for token in candidates {
+            if let Some(rate_limit) = token
+                .rate_limits
+                .get(resource)
+                .filter(RateLimit::is_exhausted)
+            {
+                retry_after = retry_after.min(rate_limit.retry_after());
+                continue;
+            }
+            let response = match request.send(&token.client).await {
+                Ok(response) => Self::read_response(token, resource, response).await,
+                Err(err) => Err(Self::map_error(token, resource, err)),
+            };
+            match response {
+                Ok(response) => return Ok((token, response)),
+                Err(GitHubError::RateLimitExhausted { retry_after: after }) => {
+                    tracing::warn!(
+                        "GitHub token exhausted its {:?} rate limit, failing over.",
+                        resource
+                    );
+                    retry_after = retry_after.min(after);
+                }
+                Err(err) => return Err(err),
+            }
+        }
+        Err(GitHubError::RateLimitExhausted { retry_after })
+    }
+
+    fn map_error(
+        token: &GitHubToken,
+        resource: RateLimitResource,
+        err: reqwest::Error,
+    ) -> GitHubError {
+        let is_rate_limit_status = matches!(
+            err.status(),
+            Some(StatusCode::FORBIDDEN | StatusCode::TOO_MANY_REQUESTS)
+        );
+        // Exhausted GraphQL budgets are reported in the body of a 200 response, which then fails
+        // to decode.
+        if is_rate_limit_status || err.is_decode() {
+            if let Some(rate_limit) = token
+                .rate_limits
+                .get(resource)
+                .filter(RateLimit::is_exhausted)
+            {
+                return GitHubError::RateLimitExhausted {
+                    retry_after: rate_limit.retry_after(),
+                };
+            }
+        }
+        GitHubError::from(err)
+    }
+
+    /// Decodes a response sent with `token`. 403 and 429 responses which aren't due to an
+    /// exhausted budget are secondary rate limits if they ask to retry later or say so in their
+    /// body.
+    async fn read_response<T: DeserializeOwned>(
+        token: &GitHubToken,
+        resource: RateLimitResource,
+        response: Response,
+    ) -> Result<GitHubResponse<T>, GitHubError> {
+        let status = response.status();
+        let err = match response.error_for_status_ref().err() {
+            Some(err)
+                if matches!(
+                    status,
+                    StatusCode::FORBIDDEN | StatusCode::TOO_MANY_REQUESTS
+                ) =>
+            {
+                err
+            }
+            _ => {
+                return GitHubResponse::from_response(response)
+                    .await
+                    .map_err(|err| Self::map_error(token, resource, err))
+            }
+        };
+        let retry_after = retry::retry_after(response.headers());
+        let body = response.text().await.unwrap_or_default();
+        Err(match Self::map_error(token, resource, err) {
+            GitHubError::ClientError { .. }
+                if status == StatusCode::TOO_MANY_REQUESTS
+                    || retry_after.is_some()
+                    || body.to_lowercase().contains("rate limit") =>
+            {
+                // GitHub recommends waiting at least a minute if it doesn't say how long.
+                GitHubError::RateLimited {
+                    retry_after: retry_after.unwrap_or(Duration::from_secs(60)),
+                }
+            }
+            err => err,
+        })
+    }
+}
+
+/// Returns the URLs of all pages following `response`, in order, if it links to the last page.
+fn remaining_pages<T: DeserializeOwned>(response: &GitHubResponse<T>) -> Option<Vec<String>> {
+    let links = response.links.as_ref()?;
+    let next = links.get(&Some("next".to_owned()))?;
+    let last = links.get(&Some("last".to_owned()))?;
+    let next_page: u32 = next.queries.get("page")?.parse().ok()?;
+    let last_page: u32 = last.queries.get("page")?.parse().ok()?;
+    let url = url::Url::parse(&next.raw_uri).ok()?;
+    Some(
+        (next_page..=last_page)
+            .map(|page| {
+                let query: Vec<(String, String)> = url
+                    .query_pairs()
+                    .map(|(key, value)| match key.as_ref() {
+                        "page" => (key.into_owned(), page.to_string()),
+                        _ => (key.into_owned(), value.into_owned()),
+                    })
+                    .collect();
+                let mut url = url.clone();
+                url.query_pairs_mut().clear().extend_pairs(query);
+                url.to_string()
+            })
+            .collect(),
+    )
+}
+
+impl Default for GitHubClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::SystemTime;
+
+    use crate::http::test_server::serve;
+
+    use super::*;
+
+    /// Records `remaining` requests left of `token`'s budget for `resource`.
+    fn record(token: &str, resource: RateLimitResource, remaining: u64) {
+        RateLimits::for_token(Some(token)).record(
+            resource,
+            RateLimit {
+                limit: 5000,
+                remaining,
+                reset: SystemTime::now() + Duration::from_secs(600),
+            },
+        );
+    }
+
+    fn client(tokens: &[&str]) -> GitHubClient {
+        GitHubClient::with_base_url(
+            tokens.iter().map(|token| token.to_string()).collect(),
+            "http://127.0.0.1:1".to_owned(),
+        )
+    }
+
+    fn candidates(client: &GitHubClient, resource: RateLimitResource) -> Vec<Arc<RateLimits>> {
+        client
+            .candidates(resource)
+            .into_iter()
+            .map(|token| token.rate_limits.clone())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn should_not_send_requests_with_exhausted_budget() {
+        let client = client(&["exhausted-token"]);
+        record("exhausted-token", RateLimitResource::Core, 0);
+
+        let repo = GitHubRepo::new("sumneko".to_owned(), "vscode-lua".to_owned());
+        match client.fetch_latest_release(&repo).await {
+            Err(GitHubError::RateLimitExhausted { retry_after }) => {
+                assert!(retry_after > Duration::from_secs(590));
+            }
+            other => panic!("Expected exhausted rate limit, got {:?}", other),
+        }
+        assert_eq!(
+            vec![0],
+            client
+                .rate_limits(RateLimitResource::Core)
+                .iter()
+                .map(|rate_limit| rate_limit.remaining)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn should_dedup_tokens() {
+        let client = client(&["dedup-a", "dedup-b", "dedup-a"]);
+        assert_eq!(2, client.tokens.len());
+    }
+
+    #[test]
+    fn should_rotate_tokens_with_unknown_budget() {
+        let client = client(&["round-robin-a", "round-robin-b"]);
+        let a = RateLimits::for_token(Some("round-robin-a"));
+        let firsts: Vec<_> = (0..10)
+            .map(|_| candidates(&client, RateLimitResource::Core))
+            .inspect(|candidates| assert_eq!(2, candidates.len()))
+            .map(|candidates| Arc::ptr_eq(&candidates[0], &a))
+            .collect();
+        assert!(firsts.contains(&true));
+        assert!(firsts.contains(&false));
+    }
+
+    #[test]
+    fn should_prefer_tokens_with_most_remaining_budget() {
+        let client = client(&["remaining-a", "remaining-b", "remaining-c"]);
+        record("remaining-a", RateLimitResource::Core, 10);
+        record("remaining-b", RateLimitResource::Core, 4000);
+        record("remaining-c", RateLimitResource::Core, 200);
+        for _ in 0..3 {
+            assert_eq!(
+                vec![4000, 200, 10],
+                candidates(&client, RateLimitResource::Core)
+                    .iter()
+                    .map(|limits| limits.get(RateLimitResource::Core).unwrap().remaining)
+                    .collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn should_fail_over_to_tokens_with_budget() {
+        let client = client(&["failover-exhausted", "failover-unreachable"]);
+        record("failover-exhausted", RateLimitResource::Core, 0);
+
+        // The second token is used, failing to connect rather than being short-circuited.
+        let repo = GitHubRepo::new("sumneko".to_owned(), "vscode-lua".to_owned());
+        match client.fetch_latest_release(&repo).await {
+            Err(GitHubError::NetworkError { .. }) => {}
+            other => panic!("Expected network error, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn should_fail_over_when_graphql_budget_runs_out() {
+        record("graphql-failover-a", RateLimitResource::GraphQL, 4000);
+        record("graphql-failover-b", RateLimitResource::GraphQL, 100);
+        let response = |headers: &str, body: &str| {
+            format!(
+                "HTTP/1.1 200 OK\r\n{headers}content-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                body.len()
+            )
+        };
+        let reset = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            + 1200;
+        let (base_url, server) = serve(vec![
+            response(
+                &format!(
+                    "x-ratelimit-limit: 5000\r\nx-ratelimit-remaining: 0\r\n\
+                     x-ratelimit-reset: {reset}\r\nx-ratelimit-resource: graphql\r\n"
+                ),
+                r#"{"errors":[{"type":"RATE_LIMITED","message":"API rate limit exceeded"}]}"#,
+            ),
+            response(
+                "",
+                r#"{"data":{"repository":{"refs":{"pageInfo":{"startCursor":null,"endCursor":null,"hasNextPage":false,"hasPreviousPage":false},"nodes":[{"name":"v1.0.0"}]}}}}"#,
+            ),
+        ]);
+        let client = GitHubClient::with_base_url(
+            vec![
+                "graphql-failover-a".to_owned(),
+                "graphql-failover-b".to_owned(),
+            ],
+            base_url,
+        );
+
+        let repo = GitHubRepo::new("sumneko".to_owned(), "vscode-lua".to_owned());
+        let tags = client.fetch_tags(&repo, 1, None).await.unwrap();
+        assert_eq!("v1.0.0", tags.data.tags[0].name);
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].contains("authorization: bearer graphql-failover-a\r\n"));
+        assert!(requests[1].contains("authorization: bearer graphql-failover-b\r\n"));
+    }
+
+    #[test]
+    fn should_list_remaining_pages() {
+        let response = |link: &str| GitHubResponse::<Vec<()>> {
+            data: vec![],
+            links: parse_link_header::parse(link).ok(),
+        };
+        assert_eq!(
+            Some(vec![
+                "https://api.github.com/repositories/1/releases?per_page=100&page=2".to_owned(),
+                "https://api.github.com/repositories/1/releases?per_page=100&page=3".to_owned(),
+                "https://api.github.com/repositories/1/releases?per_page=100&page=4".to_owned(),
+            ]),
+            remaining_pages(&response(
+                "<https://api.github.com/repositories/1/releases?per_page=100&page=2>; rel=\"next\", \
+                 <https://api.github.com/repositories/1/releases?per_page=100&page=4>; rel=\"last\""
+            ))
+        );
+        assert_eq!(
+            None,
+            remaining_pages(&response(
+                "<https://api.github.com/repositories/1/releases?per_page=100&page=2>; rel=\"next\""
+            ))
+        );
+        assert_eq!(
+            None,
+            remaining_pages(&response(
+                "<https://api.github.com/repositories/1/releases?per_page=100&page=1>; rel=\"prev\""
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn should_require_token_for_graphql() {
+        let client = client(&[]);
+        assert_eq!(1, candidates(&client, RateLimitResource::Core).len());
+        assert!(candidates(&client, RateLimitResource::GraphQL).is_empty());
+
+        let repo = GitHubRepo::new("sumneko".to_owned(), "vscode-lua".to_owned());
+        match client.fetch_tags(&repo, 1, None).await {
+            Err(GitHubError::MissingToken) => {}
+            other => panic!("Expected missing token, got {:?}", other.map(|_| ())),
+        }
     }
 }
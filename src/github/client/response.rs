@@ -1,6 +1,4 @@
-use std::convert::TryFrom;
-
-use reqwest::blocking::Response;
+use reqwest::Response;
 use serde::de::DeserializeOwned;
 
 #[derive(Debug)]
@@ -14,10 +12,8 @@ pub struct GitHubErrorResponse {
     pub response: Response,
 }
 
-impl<T: DeserializeOwned> TryFrom<Response> for GitHubResponse<T> {
-    type Error = reqwest::Error;
-
-    fn try_from(value: Response) -> Result<Self, Self::Error> {
+impl<T: DeserializeOwned> GitHubResponse<T> {
+    pub async fn from_response(value: Response) -> Result<Self, reqwest::Error> {
         let value = value.error_for_status()?;
         let links = value
             .headers()
@@ -25,7 +21,7 @@ impl<T: DeserializeOwned> TryFrom<Response> for GitHubResponse<T> {
             .and_then(|link| link.to_str().ok())
             .and_then(|link| parse_link_header::parse(link).ok());
         Ok(Self {
-            data: value.json()?,
+            data: value.json().await?,
             links,
         })
     }
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use http::StatusCode;
 use thiserror::Error;
 
@@ -13,16 +15,32 @@ pub enum GitHubError {
     ServerError { source: reqwest::Error },
     #[error("Network error. {:?}", source.status())]
     NetworkError { source: reqwest::Error },
+    #[error("GitHub's API rate limit is exhausted.")]
+    RateLimitExhausted { retry_after: Duration },
+    #[error("GitHub's API is rate limiting requests.")]
+    RateLimited { retry_after: Duration },
+    #[error("GitHub's GraphQL API requires a token, but none is configured.")]
+    MissingToken,
 }
 
 impl ApiError for GitHubError {
     fn status_code(&self) -> StatusCode {
         match self {
             GitHubError::ResourceNotFound { .. } => StatusCode::NOT_FOUND,
-            GitHubError::ClientError { .. } | GitHubError::NetworkError { .. } => {
-                StatusCode::INTERNAL_SERVER_ERROR
-            }
+            GitHubError::ClientError { .. }
+            | GitHubError::NetworkError { .. }
+            | GitHubError::MissingToken => StatusCode::INTERNAL_SERVER_ERROR,
             GitHubError::ServerError { .. } => StatusCode::BAD_GATEWAY,
+            GitHubError::RateLimitExhausted { .. } => StatusCode::SERVICE_UNAVAILABLE,
+            GitHubError::RateLimited { .. } => StatusCode::TOO_MANY_REQUESTS,
+        }
+    }
+
+    fn retry_after(&self) -> Option<Duration> {
+        match self {
+            GitHubError::RateLimitExhausted { retry_after }
+            | GitHubError::RateLimited { retry_after } => Some(*retry_after),
+            _ => None,
         }
     }
 }
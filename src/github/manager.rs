@@ -1,5 +1,7 @@
 use std::collections::VecDeque;
 
+use crate::registry::{PackageVersion, Registry};
+
 use super::{
     client::{
         graphql::{sponsors::Sponsor, tags::Tag},
@@ -20,14 +22,15 @@ impl GitHubManager {
     }
 
     /// Returns all tags in DESCENDING order.
-    pub fn get_all_tags(&self, repo: &GitHubRepo) -> Result<Vec<Tag>, GitHubError> {
+    pub async fn get_all_tags(&self, repo: &GitHubRepo) -> Result<Vec<Tag>, GitHubError> {
         let mut all_tags: Vec<Tag> = vec![];
         let mut cursor = None;
 
         loop {
-            let response =
-                self.client
-                    .fetch_tags(&repo, GitHubPagination::MAX_PAGE_LIMIT.into(), cursor)?;
+            let response = self
+                .client
+                .fetch_tags(repo, GitHubPagination::MAX_PAGE_LIMIT.into(), cursor)
+                .await?;
             let mut tags = response.data.tags;
             all_tags.append(&mut tags);
 
@@ -38,16 +41,19 @@ impl GitHubManager {
         }
     }
 
-    pub fn get_all_sponsors(&self, login: String) -> Result<Vec<Sponsor>, GitHubError> {
+    pub async fn get_all_sponsors(&self, login: String) -> Result<Vec<Sponsor>, GitHubError> {
         let mut all_sponsors: Vec<Sponsor> = vec![];
         let mut cursor = None;
 
         loop {
-            let response = self.client.fetch_sponsors(
-                login.clone(),
-                GitHubPagination::MAX_PAGE_LIMIT.into(),
-                cursor,
-            )?;
+            let response = self
+                .client
+                .fetch_sponsors(
+                    login.clone(),
+                    GitHubPagination::MAX_PAGE_LIMIT.into(),
+                    cursor,
+                )
+                .await?;
             let mut sponsors = response.data.sponsors;
             all_sponsors.append(&mut sponsors);
 
@@ -58,8 +64,8 @@ impl GitHubManager {
         }
     }
 
-    pub fn get_latest_tag(&self, repo: &GitHubRepo) -> Result<Tag, GitHubError> {
-        let response = self.client.fetch_tags(&repo, 1, None)?;
+    pub async fn get_latest_tag(&self, repo: &GitHubRepo) -> Result<Tag, GitHubError> {
+        let response = self.client.fetch_tags(repo, 1, None).await?;
         let mut tags: VecDeque<Tag> = response.data.tags.into();
         let latest_tag = tags
             .pop_front()
@@ -67,39 +73,101 @@ impl GitHubManager {
         Ok(latest_tag)
     }
 
-    pub fn get_ref(&self, repo: &GitHubRepo, tag: &GitHubTag) -> Result<GitHubRef, GitHubError> {
-        let tag = self.client.fetch_ref(repo, tag)?;
+    pub async fn get_ref(
+        &self,
+        repo: &GitHubRepo,
+        tag: &GitHubTag,
+    ) -> Result<GitHubRef, GitHubError> {
+        let tag = self.client.fetch_ref(repo, tag).await?;
         Ok(tag.data)
     }
 
-    /// Returns all releases in DESCENDING order.
-    pub fn get_all_releases(
+    /// Returns all releases in DESCENDING order. Pre-releases and drafts are each included unless
+    /// opted out of.
+    pub async fn get_all_releases(
         &self,
         repo: &GitHubRepo,
     ) -> Result<Vec<GitHubReleaseDto>, GitHubError> {
-        Ok(self.client.paginate(
-            || {
+        let mut releases = self
+            .client
+            .paginate(
                 self.client.fetch_releases(
-                    &repo,
+                    repo,
                     Some(GitHubPagination {
                         page: 1,
                         per_page: GitHubPagination::MAX_PAGE_LIMIT,
                     }),
-                )
-            },
-            |_| true,
-        )?)
+                ),
+                |_| true,
+            )
+            .await?;
+        let include_prerelease = repo.include_prerelease.unwrap_or(true);
+        let include_draft = repo.include_draft.unwrap_or(true);
+        releases.retain(|release| {
+            (include_prerelease || !release.prerelease) && (include_draft || !release.draft)
+        });
+        Ok(releases)
     }
 
-    pub fn get_latest_release(&self, repo: &GitHubRepo) -> Result<GitHubReleaseDto, GitHubError> {
-        Ok(self.client.fetch_latest_release(&repo)?.data)
+    /// Returns the latest release as determined by GitHub or, with `include_prerelease`, the most
+    /// recent release that isn't a draft.
+    pub async fn get_latest_release(
+        &self,
+        repo: &GitHubRepo,
+    ) -> Result<GitHubReleaseDto, GitHubError> {
+        if !repo.include_prerelease.unwrap_or(false) {
+            return Ok(self.client.fetch_latest_release(repo).await?.data);
+        }
+        self.client
+            .fetch_releases(
+                repo,
+                Some(GitHubPagination {
+                    page: 1,
+                    per_page: GitHubPagination::MAX_PAGE_LIMIT,
+                }),
+            )
+            .await?
+            .data
+            .into_iter()
+            .find(|release| !release.draft)
+            .ok_or_else(|| GitHubError::ResourceNotFound { source: None })
     }
 
-    pub fn get_release_by_tag(
+    pub async fn get_release_by_tag(
         &self,
         repo: &GitHubRepo,
         release: &GitHubTag,
     ) -> Result<GitHubReleaseDto, GitHubError> {
-        Ok(self.client.fetch_release_by_tag(&repo, &release)?.data)
+        Ok(self.client.fetch_release_by_tag(repo, release).await?.data)
+    }
+}
+
+impl Registry for GitHubManager {
+    type Package = GitHubRepo;
+    type Error = GitHubError;
+
+    async fn get_all_versions(&self, repo: &GitHubRepo) -> Result<Vec<String>, GitHubError> {
+        Ok(self
+            .get_all_releases(repo)
+            .await?
+            .into_iter()
+            .map(|release| release.tag_name)
+            .collect())
+    }
+
+    async fn get_latest_version(&self, repo: &GitHubRepo) -> Result<PackageVersion, GitHubError> {
+        let release = self.get_latest_release(repo).await?;
+        Ok(PackageVersion::new(repo.to_string(), release.tag_name))
+    }
+
+    async fn get_version(
+        &self,
+        repo: &GitHubRepo,
+        version: &str,
+    ) -> Result<PackageVersion, GitHubError> {
+        let release = self
+            .get_release_by_tag(repo, &GitHubTag(version.to_owned()))
+            .await?;
+        Ok(PackageVersion::new(repo.to_string(), release.tag_name))
     }
 }
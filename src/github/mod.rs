@@ -2,6 +2,7 @@ pub mod api;
 pub mod client;
 pub mod errors;
 pub mod manager;
+pub mod rate_limit;
 
 use std::{fmt::Display, str::FromStr};
 
@@ -11,11 +12,22 @@ use crate::QueryParams;
 pub struct GitHubRepo {
     pub owner: String,
     pub name: String,
+    /// Whether to include pre-releases. Unless set, `releases/all` lists them but
+    /// `releases/latest` doesn't resolve to them.
+    pub include_prerelease: Option<bool>,
+    /// Whether to list draft releases in `releases/all`, which they are unless opted out of.
+    /// Drafts are never resolved as the latest release.
+    pub include_draft: Option<bool>,
 }
 
 impl GitHubRepo {
     pub fn new(owner: String, name: String) -> Self {
-        Self { owner, name }
+        Self {
+            owner,
+            name,
+            include_prerelease: None,
+            include_draft: None,
+        }
     }
 }
 
@@ -31,6 +43,8 @@ impl From<&QueryParams> for GitHubRepo {
             (Some(owner), Some(name)) => Self {
                 owner: owner.to_owned(),
                 name: name.to_owned(),
+                include_prerelease: query.flag("include_prerelease"),
+                include_draft: query.flag("include_draft"),
             },
             (Some(_), None) | (None, None) | (None, Some(_)) => {
                 panic!("Failed to parse GitHub repo from URL.")
@@ -39,7 +53,7 @@ impl From<&QueryParams> for GitHubRepo {
     }
 }
 
-pub trait GitHubRefId {
+pub trait GitHubRefId: Sync {
     fn get_ref_endpoint(&self) -> String;
 }
 
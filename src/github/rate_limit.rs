@@ -0,0 +1,189 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, OnceLock},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use reqwest::header::HeaderMap;
+
+use crate::http::stable_hash;
+
+/// The rate limit buckets of GitHub's API that are used by the client.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RateLimitResource {
+    Core,
+    GraphQL,
+}
+
+impl RateLimitResource {
+    fn from_header(value: &str) -> Option<Self> {
+        match value {
+            "core" => Some(Self::Core),
+            "graphql" => Some(Self::GraphQL),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    pub limit: u64,
+    pub remaining: u64,
+    pub reset: SystemTime,
+}
+
+impl RateLimit {
+    /// Parses the `X-RateLimit-*` headers GitHub sends with every response.
+    pub fn from_headers(headers: &HeaderMap) -> Option<(RateLimitResource, Self)> {
+        let header = |name: &str| headers.get(name)?.to_str().ok();
+        let resource = RateLimitResource::from_header(header("x-ratelimit-resource")?)?;
+        Some((
+            resource,
+            Self {
+                limit: header("x-ratelimit-limit")?.parse().ok()?,
+                remaining: header("x-ratelimit-remaining")?.parse().ok()?,
+                reset: UNIX_EPOCH + Duration::from_secs(header("x-ratelimit-reset")?.parse().ok()?),
+            },
+        ))
+    }
+
+    pub fn is_exhausted(&self) -> bool {
+        self.remaining == 0 && self.reset > SystemTime::now()
+    }
+
+    /// Time until the budget resets.
+    pub fn retry_after(&self) -> Duration {
+        self.reset
+            .duration_since(SystemTime::now())
+            .unwrap_or(Duration::ZERO)
+    }
+
+    fn is_low(&self) -> bool {
+        self.remaining <= self.limit / 10
+    }
+}
+
+/// The most recently observed rate limits of a single token.
+#[derive(Debug, Default)]
+pub struct RateLimits {
+    limits: Mutex<HashMap<RateLimitResource, RateLimit>>,
+}
+
+impl RateLimits {
+    /// Returns the process-wide rate limits for `token`, with `None` tracking unauthenticated
+    /// requests.
+    pub fn for_token(token: Option<&str>) -> Arc<Self> {
+        static ALL: OnceLock<Mutex<HashMap<Option<u64>, Arc<RateLimits>>>> = OnceLock::new();
+        let key = token.map(|token| stable_hash(token.as_bytes()));
+        ALL.get_or_init(Default::default)
+            .lock()
+            .expect("Rate limits lock is not poisoned.")
+            .entry(key)
+            .or_default()
+            .clone()
+    }
+
+    pub fn get(&self, resource: RateLimitResource) -> Option<RateLimit> {
+        self.limits.lock().ok()?.get(&resource).copied()
+    }
+
+    pub fn record_headers(&self, headers: &HeaderMap) {
+        if let Some((resource, rate_limit)) = RateLimit::from_headers(headers) {
+            self.record(resource, rate_limit);
+        }
+    }
+
+    pub fn record(&self, resource: RateLimitResource, rate_limit: RateLimit) {
+        let Ok(mut limits) = self.limits.lock() else {
+            return;
+        };
+        let previous = limits.get(&resource).copied();
+        let current = match previous {
+            // Responses may arrive out of order, so only ever lower the budget within a window.
+            Some(previous) if previous.reset == rate_limit.reset => RateLimit {
+                remaining: previous.remaining.min(rate_limit.remaining),
+                ..rate_limit
+            },
+            Some(previous) if previous.reset > rate_limit.reset => return,
+            _ => rate_limit,
+        };
+        limits.insert(resource, current);
+
+        let was_low =
+            previous.is_some_and(|previous| previous.reset == current.reset && previous.is_low());
+        if current.remaining == 0 && previous.is_none_or(|previous| previous.remaining > 0) {
+            tracing::warn!(
+                "GitHub {:?} rate limit exhausted, resets in {}s.",
+                resource,
+                current.retry_after().as_secs()
+            );
+        } else if current.is_low() && !was_low {
+            tracing::warn!(
+                "GitHub {:?} rate limit is running low: {}/{} remaining, resets in {}s.",
+                resource,
+                current.remaining,
+                current.limit,
+                current.retry_after().as_secs()
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rate_limit(remaining: u64, reset: u64) -> RateLimit {
+        RateLimit {
+            limit: 5000,
+            remaining,
+            reset: UNIX_EPOCH + Duration::from_secs(reset),
+        }
+    }
+
+    #[test]
+    fn should_parse_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-limit", "5000".parse().unwrap());
+        headers.insert("x-ratelimit-remaining", "4999".parse().unwrap());
+        headers.insert("x-ratelimit-reset", "1681210800".parse().unwrap());
+        headers.insert("x-ratelimit-resource", "graphql".parse().unwrap());
+        assert_eq!(
+            Some((RateLimitResource::GraphQL, rate_limit(4999, 1681210800))),
+            RateLimit::from_headers(&headers)
+        );
+
+        headers.insert("x-ratelimit-resource", "search".parse().unwrap());
+        assert_eq!(None, RateLimit::from_headers(&headers));
+    }
+
+    #[test]
+    fn should_keep_lowest_remaining_within_window() {
+        let limits = RateLimits::default();
+        limits.record(RateLimitResource::Core, rate_limit(10, 1000));
+        limits.record(RateLimitResource::Core, rate_limit(12, 1000));
+        assert_eq!(
+            Some(rate_limit(10, 1000)),
+            limits.get(RateLimitResource::Core)
+        );
+
+        limits.record(RateLimitResource::Core, rate_limit(4999, 4600));
+        limits.record(RateLimitResource::Core, rate_limit(3, 1000));
+        assert_eq!(
+            Some(rate_limit(4999, 4600)),
+            limits.get(RateLimitResource::Core)
+        );
+        assert_eq!(None, limits.get(RateLimitResource::GraphQL));
+    }
+
+    #[test]
+    fn should_only_be_exhausted_until_reset() {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        assert!(rate_limit(0, now + 60).is_exhausted());
+        assert!(!rate_limit(1, now + 60).is_exhausted());
+        assert!(!rate_limit(0, now - 60).is_exhausted());
+    }
+}
@@ -1,46 +1,110 @@
 use std::fmt::Display;
 
-use crate::http::client::{Client, HttpEndpoint};
+use crate::{
+    http::client::{base_url_from_env, Client, HttpEndpoint},
+    CacheControl,
+};
+
+use self::spec::GolangVersionInfoDto;
 
 use super::GolangPackage;
 
+pub mod spec;
+
 pub struct GolangClient {
     client: Client,
 }
 
 enum GolangEndpoint<'a> {
     VersionsList(&'a GolangPackage),
+    Latest(&'a GolangPackage),
+    VersionInfo(&'a GolangPackage, &'a str),
+}
+
+/// Escapes module paths and versions for the module proxy protocol, which replaces uppercase
+/// letters with an exclamation mark followed by the lowercase letter, e.g.
+/// `github.com/BurntSushi/toml` becomes `github.com/!burnt!sushi/toml`.
+fn escape(path: &str) -> String {
+    let mut escaped = String::with_capacity(path.len());
+    for char in path.chars() {
+        if char.is_ascii_uppercase() {
+            escaped.push('!');
+            escaped.push(char.to_ascii_lowercase());
+        } else {
+            escaped.push(char);
+        }
+    }
+    escaped
 }
 
 impl<'a> HttpEndpoint for GolangEndpoint<'a> {
-    fn as_full_url(&self) -> String {
-        format!("https://proxy.golang.org/{}", self)
+    fn as_full_url(&self, base_url: &str) -> String {
+        format!("{}/{}", base_url, self)
+    }
+
+    fn cache_control(&self) -> CacheControl {
+        match self {
+            GolangEndpoint::VersionsList(_) | GolangEndpoint::Latest(_) => {
+                CacheControl::PublicMedium
+            }
+            // Module versions, including pseudo-versions, are immutable.
+            GolangEndpoint::VersionInfo(_, _) => CacheControl::PublicLong,
+        }
     }
 }
 
 impl<'a> Display for GolangEndpoint<'a> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            GolangEndpoint::VersionsList(pkg) => f.write_fmt(format_args!("{}/@v/list", pkg.name)),
+            GolangEndpoint::VersionsList(pkg) => {
+                f.write_fmt(format_args!("{}/@v/list", escape(&pkg.name)))
+            }
+            GolangEndpoint::Latest(pkg) => {
+                f.write_fmt(format_args!("{}/@latest", escape(&pkg.name)))
+            }
+            GolangEndpoint::VersionInfo(pkg, version) => f.write_fmt(format_args!(
+                "{}/@v/{}.info",
+                escape(&pkg.name),
+                escape(version)
+            )),
         }
     }
 }
 
+impl Default for GolangClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl GolangClient {
+    pub const DEFAULT_BASE_URL: &'static str = "https://proxy.golang.org";
+
+    /// Creates a client for the module proxy configured via `GOLANG_PROXY_URL`, defaulting to
+    /// proxy.golang.org.
     pub fn new() -> Self {
+        Self::with_base_url(base_url_from_env(
+            "GOLANG_PROXY_URL",
+            Self::DEFAULT_BASE_URL,
+        ))
+    }
+
+    pub fn with_base_url(base_url: String) -> Self {
         Self {
-            client: Client::new(None),
+            client: Client::new(base_url, None),
         }
     }
 
-    pub fn fetch_package_versions(
+    pub async fn fetch_package_versions(
         &self,
         package: &GolangPackage,
     ) -> Result<Vec<String>, reqwest::Error> {
         Ok(self
             .client
-            .get(GolangEndpoint::VersionsList(package))?
-            .text()?
+            .get(GolangEndpoint::VersionsList(package))
+            .await?
+            .text()
+            .await?
             .split('\n')
             .filter_map(|line| match line {
                 "" => None,
@@ -48,4 +112,51 @@ impl GolangClient {
             })
             .collect())
     }
+
+    /// Fetches the latest release, or the latest pre-release or pseudo-version if there are none.
+    pub async fn fetch_latest_version(
+        &self,
+        package: &GolangPackage,
+    ) -> Result<GolangVersionInfoDto, reqwest::Error> {
+        self.client
+            .get(GolangEndpoint::Latest(package))
+            .await?
+            .json()
+            .await
+    }
+
+    pub async fn fetch_version_info(
+        &self,
+        package: &GolangPackage,
+        version: &str,
+    ) -> Result<GolangVersionInfoDto, reqwest::Error> {
+        self.client
+            .get(GolangEndpoint::VersionInfo(package, version))
+            .await?
+            .json()
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_escape_uppercase_letters() {
+        let toml = GolangPackage::new("github.com/BurntSushi/toml".to_owned());
+        assert_eq!(
+            "github.com/!burnt!sushi/toml/@v/list",
+            GolangEndpoint::VersionsList(&toml).to_string()
+        );
+        assert_eq!(
+            "github.com/!burnt!sushi/toml/@v/v1.0.0-!r!c1.info",
+            GolangEndpoint::VersionInfo(&toml, "v1.0.0-RC1").to_string()
+        );
+        assert_eq!(
+            "golang.org/x/tools/gopls/@latest",
+            GolangEndpoint::Latest(&GolangPackage::new("golang.org/x/tools/gopls".to_owned()))
+                .to_string()
+        );
+    }
 }
@@ -0,0 +1,10 @@
+use serde::Deserialize;
+
+/// The response of the `@latest` and `@v/{version}.info` endpoints.
+#[derive(Deserialize, Debug)]
+pub struct GolangVersionInfoDto {
+    #[serde(rename = "Version")]
+    pub version: String,
+    #[serde(rename = "Time")]
+    pub time: String,
+}
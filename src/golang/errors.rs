@@ -30,9 +30,12 @@ impl ApiError for GolangError {
 impl From<reqwest::Error> for GolangError {
     fn from(req_error: reqwest::Error) -> Self {
         match req_error.status() {
-            Some(reqwest::StatusCode::NOT_FOUND) => Self::ResourceNotFound {
-                source: Some(req_error),
-            },
+            // The module proxy responds with 410 Gone for versions that don't exist.
+            Some(reqwest::StatusCode::NOT_FOUND | reqwest::StatusCode::GONE) => {
+                Self::ResourceNotFound {
+                    source: Some(req_error),
+                }
+            }
             Some(status_code) if status_code.is_server_error() => {
                 Self::ServerError { source: req_error }
             }
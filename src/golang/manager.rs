@@ -1,29 +1,150 @@
-use std::cmp::Ordering;
+use crate::{
+    registry::{PackageVersion, Registry},
+    versioning::semver::{self, SemVer},
+};
 
-use super::{client::GolangClient, errors::GolangError, GolangPackage};
+use super::{
+    client::{spec::GolangVersionInfoDto, GolangClient},
+    errors::GolangError,
+    GolangPackage,
+};
 
 pub struct GolangManager {
     client: GolangClient,
 }
 
-fn semver_sort_desc(a: &String, b: &String) -> Ordering {
-    let a_semver = a.strip_prefix("v").unwrap_or(a).parse::<semver::Version>();
-    let b_semver = b.strip_prefix("v").unwrap_or(b).parse::<semver::Version>();
-    if let (Ok(a), Ok(b)) = (&a_semver, &b_semver) {
-        return b.cmp(a);
-    }
-    Ordering::Equal
-}
-
 impl GolangManager {
+    /// How many successor module paths to probe at most when listing all major versions.
+    pub const MAX_SUCCESSOR_MAJORS: usize = 16;
+
     pub fn new(client: GolangClient) -> Self {
         Self { client }
     }
 
-    /// Returns all package versions in DESCENDING order.
-    pub fn get_all_versions(&self, package: &GolangPackage) -> Result<Vec<String>, GolangError> {
-        let mut unsorted_versions = self.client.fetch_package_versions(package)?;
-        unsorted_versions.sort_by(semver_sort_desc);
+    /// Returns all package versions in DESCENDING order. Pre-releases are included unless opted
+    /// out of.
+    pub async fn get_all_versions(
+        &self,
+        package: &GolangPackage,
+    ) -> Result<Vec<String>, GolangError> {
+        let mut unsorted_versions = self.client.fetch_package_versions(package).await?;
+        if package.all_majors {
+            let successor_versions = self
+                .get_successor_versions(package, &unsorted_versions)
+                .await?;
+            unsorted_versions.extend(successor_versions);
+        }
+        if !package.include_prerelease.unwrap_or(true) {
+            unsorted_versions.retain(|version| !semver::is_prerelease(version));
+        }
+        semver::sort_desc(&mut unsorted_versions);
         Ok(unsorted_versions)
     }
+
+    /// Lists the versions of the module paths of all following major versions, stopping at the
+    /// first one without versions. Majors released as `+incompatible` versions of the module
+    /// itself, e.g. v4 of `github.com/go-chi/chi`, are skipped.
+    async fn get_successor_versions(
+        &self,
+        package: &GolangPackage,
+        versions: &[String],
+    ) -> Result<Vec<String>, GolangError> {
+        let latest_major = versions
+            .iter()
+            .filter_map(|version| version.parse::<SemVer>().ok())
+            .map(|SemVer(version)| version.major)
+            .chain(package.major_version())
+            .max()
+            .unwrap_or(1);
+
+        let mut successor_versions = vec![];
+        for major in (latest_major.max(1) + 1..).take(Self::MAX_SUCCESSOR_MAJORS) {
+            let successor = package.with_major_version(major);
+            match self.client.fetch_package_versions(&successor).await {
+                Ok(versions) if !versions.is_empty() => successor_versions.extend(versions),
+                Ok(_) => break,
+                Err(err) => match GolangError::from(err) {
+                    GolangError::ResourceNotFound { .. } => break,
+                    err => return Err(err),
+                },
+            }
+        }
+        Ok(successor_versions)
+    }
+
+    /// Returns the version the proxy resolves `@latest` to or, with `include_prerelease`, a newer
+    /// pre-release if there is one.
+    pub async fn get_latest_version(
+        &self,
+        package: &GolangPackage,
+    ) -> Result<GolangVersionInfoDto, GolangError> {
+        let latest = self.client.fetch_latest_version(package).await?;
+        if !package.include_prerelease.unwrap_or(false) {
+            return Ok(latest);
+        }
+        let versions = self.client.fetch_package_versions(package).await?;
+        let prereleases = versions
+            .iter()
+            .filter(|version| semver::is_prerelease(version));
+        let newest = semver::latest(
+            prereleases.chain([&latest.version]).map(String::as_str),
+            true,
+        )
+        .map(ToOwned::to_owned);
+        match newest {
+            Some(newest) if newest != latest.version => {
+                Ok(self.client.fetch_version_info(package, &newest).await?)
+            }
+            Some(_) | None => Ok(latest),
+        }
+    }
+
+    /// Looks up a version, which may also be a pseudo-version, of the module. Versions that
+    /// can't belong to the module's major version are rejected without asking the proxy.
+    pub async fn get_version(
+        &self,
+        package: &GolangPackage,
+        version: &str,
+    ) -> Result<GolangVersionInfoDto, GolangError> {
+        if !package.accepts_version(version) {
+            return Err(GolangError::ResourceNotFound { source: None });
+        }
+        Ok(self.client.fetch_version_info(package, version).await?)
+    }
+}
+
+impl GolangVersionInfoDto {
+    fn into_package_version(self, package: &GolangPackage) -> PackageVersion {
+        PackageVersion::new(package.name.clone(), self.version).with_metadata("time", self.time)
+    }
+}
+
+impl Registry for GolangManager {
+    type Package = GolangPackage;
+    type Error = GolangError;
+
+    async fn get_all_versions(&self, package: &GolangPackage) -> Result<Vec<String>, GolangError> {
+        self.get_all_versions(package).await
+    }
+
+    async fn get_latest_version(
+        &self,
+        package: &GolangPackage,
+    ) -> Result<PackageVersion, GolangError> {
+        Ok(self
+            .get_latest_version(package)
+            .await?
+            .into_package_version(package))
+    }
+
+    async fn get_version(
+        &self,
+        package: &GolangPackage,
+        version: &str,
+    ) -> Result<PackageVersion, GolangError> {
+        Ok(self
+            .get_version(package, version)
+            .await?
+            .into_package_version(package))
+    }
 }
@@ -7,6 +7,72 @@ pub mod manager;
 #[derive(Debug)]
 pub struct GolangPackage {
     pub name: String,
+    /// Whether to also list the versions of the module's successors at higher major versions,
+    /// e.g. `example.com/mod/v2` and `example.com/mod/v3` for `example.com/mod`.
+    pub all_majors: bool,
+    /// Whether to include pre-releases, e.g. `v0.12.0-pre.1`. Unless set, `versions/all` lists
+    /// them but `versions/latest` doesn't resolve to them.
+    pub include_prerelease: Option<bool>,
+}
+
+impl GolangPackage {
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            all_majors: false,
+            include_prerelease: None,
+        }
+    }
+
+    /// The module path of the given major version of this module, e.g. `example.com/mod/v3` for
+    /// `example.com/mod/v2` and `gopkg.in/yaml.v3` for `gopkg.in/yaml.v2`.
+    pub fn with_major_version(&self, major: u64) -> Self {
+        let is_gopkg = self.name.starts_with("gopkg.in/");
+        let (path, separator) = match (self.major_version(), is_gopkg) {
+            (Some(_), true) => (self.name.rsplit_once(".v").map(|(path, _)| path), ".v"),
+            (Some(_), false) => (self.name.rsplit_once("/v").map(|(path, _)| path), "/v"),
+            (None, _) => (Some(self.name.as_str()), "/v"),
+        };
+        let path = path.unwrap_or(&self.name);
+        match major {
+            0 | 1 if !is_gopkg => Self::new(path.to_owned()),
+            major => Self::new(format!("{}{}{}", path, separator, major)),
+        }
+    }
+
+    /// The major version required by the module path's suffix, e.g. 2 for
+    /// `github.com/owner/repo/v2` and 3 for `gopkg.in/yaml.v3`. Modules without a suffix are at
+    /// v0 or v1.
+    pub fn major_version(&self) -> Option<u64> {
+        let (_, suffix) = match self.name.strip_prefix("gopkg.in/") {
+            Some(path) => path.rsplit_once(".v")?,
+            None => self.name.rsplit_once("/v")?,
+        };
+        match suffix.parse() {
+            // Suffixes are only valid for v2 and up, except for gopkg.in.
+            Ok(major) if major >= 2 || self.name.starts_with("gopkg.in/") => Some(major),
+            Ok(_) | Err(_) => None,
+        }
+    }
+
+    /// Whether `version` can be a version of this module. Versions of major v2 and up must be
+    /// published under a `/vN` module path, unless they are marked `+incompatible`.
+    pub fn accepts_version(&self, version: &str) -> bool {
+        let Ok(version) = version
+            .strip_prefix('v')
+            .unwrap_or(version)
+            .parse::<semver::Version>()
+        else {
+            // Queries such as branch names are resolved by the proxy.
+            return true;
+        };
+        let is_incompatible = version.build.as_str() == "incompatible";
+        match self.major_version() {
+            Some(major) if major <= 1 => version.major <= 1 && !is_incompatible,
+            Some(major) => version.major == major && !is_incompatible,
+            None => version.major <= 1 || is_incompatible,
+        }
+    }
 }
 
 impl From<&QueryParams> for GolangPackage {
@@ -16,6 +82,70 @@ impl From<&QueryParams> for GolangPackage {
                 .get("package")
                 .expect("No [package] query param")
                 .to_owned(),
+            all_majors: query.has_flag("all_majors"),
+            include_prerelease: query.flag("include_prerelease"),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn package(name: &str) -> GolangPackage {
+        GolangPackage::new(name.to_owned())
+    }
+
+    #[test]
+    fn should_parse_major_version_suffixes() {
+        assert_eq!(None, package("golang.org/x/tools/gopls").major_version());
+        assert_eq!(Some(2), package("github.com/go-chi/chi/v2").major_version());
+        assert_eq!(Some(3), package("gopkg.in/yaml.v3").major_version());
+        assert_eq!(Some(1), package("gopkg.in/yaml.v1").major_version());
+        assert_eq!(None, package("github.com/owner/v1").major_version());
+        assert_eq!(None, package("github.com/owner/vim").major_version());
+    }
+
+    #[test]
+    fn should_build_major_version_module_paths() {
+        let chi = package("github.com/go-chi/chi");
+        assert_eq!("github.com/go-chi/chi/v5", chi.with_major_version(5).name);
+        assert_eq!(
+            "github.com/go-chi/chi/v6",
+            package("github.com/go-chi/chi/v5")
+                .with_major_version(6)
+                .name
+        );
+        assert_eq!(
+            "github.com/go-chi/chi",
+            package("github.com/go-chi/chi/v5")
+                .with_major_version(1)
+                .name
+        );
+        assert_eq!(
+            "gopkg.in/yaml.v4",
+            package("gopkg.in/yaml.v3").with_major_version(4).name
+        );
+    }
+
+    #[test]
+    fn should_accept_versions_matching_major_version() {
+        let gopls = package("golang.org/x/tools/gopls");
+        assert!(gopls.accepts_version("v0.12.0"));
+        assert!(gopls.accepts_version("v1.0.0-20230101120000-abcdef123456"));
+        assert!(gopls.accepts_version("v0.0.0-20230101120000-abcdef123456"));
+        assert!(gopls.accepts_version("v2.0.0+incompatible"));
+        assert!(gopls.accepts_version("master"));
+        assert!(!gopls.accepts_version("v2.0.0"));
+
+        let chi = package("github.com/go-chi/chi/v5");
+        assert!(chi.accepts_version("v5.0.8"));
+        assert!(chi.accepts_version("v5.0.0-20230101120000-abcdef123456"));
+        assert!(!chi.accepts_version("v4.1.2"));
+        assert!(!chi.accepts_version("v5.0.8+incompatible"));
+
+        let yaml = package("gopkg.in/yaml.v1");
+        assert!(yaml.accepts_version("v1.0.0"));
+        assert!(!yaml.accepts_version("v2.0.0"));
+    }
+}
@@ -0,0 +1,17 @@
+use serde::Serialize;
+
+use crate::registry::PackageVersion;
+
+#[derive(Serialize)]
+pub struct HackageResponse {
+    pub name: String,
+    pub version: String,
+    pub deprecated: bool,
+}
+
+impl From<HackageResponse> for PackageVersion {
+    fn from(response: HackageResponse) -> Self {
+        PackageVersion::new(response.name, response.version)
+            .with_metadata("deprecated", response.deprecated)
+    }
+}
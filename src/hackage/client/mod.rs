@@ -0,0 +1,73 @@
+pub mod spec;
+
+use std::fmt::Display;
+
+use crate::{
+    http::client::{base_url_from_env, Client, HttpEndpoint},
+    CacheControl,
+};
+
+use self::spec::HackagePreferredDto;
+
+use super::HackagePackage;
+
+enum HackageEndpoint<'a> {
+    Preferred(&'a HackagePackage),
+}
+
+impl<'a> HttpEndpoint for HackageEndpoint<'a> {
+    fn as_full_url(&self, base_url: &str) -> String {
+        format!("{}/{}", base_url, self)
+    }
+
+    fn cache_control(&self) -> CacheControl {
+        CacheControl::PublicMedium
+    }
+}
+
+impl<'a> Display for HackageEndpoint<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HackageEndpoint::Preferred(pkg) => {
+                f.write_fmt(format_args!("package/{}/preferred", pkg.name))
+            }
+        }
+    }
+}
+
+pub struct HackageClient {
+    client: Client,
+}
+
+impl Default for HackageClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HackageClient {
+    pub const DEFAULT_BASE_URL: &'static str = "https://hackage.haskell.org";
+
+    /// Creates a client for the Hackage server configured via `HACKAGE_URL`, defaulting to
+    /// hackage.haskell.org.
+    pub fn new() -> Self {
+        Self::with_base_url(base_url_from_env("HACKAGE_URL", Self::DEFAULT_BASE_URL))
+    }
+
+    pub fn with_base_url(base_url: String) -> Self {
+        Self {
+            client: Client::new(base_url, None),
+        }
+    }
+
+    pub async fn fetch_preferred_versions(
+        &self,
+        package: &HackagePackage,
+    ) -> Result<HackagePreferredDto, reqwest::Error> {
+        self.client
+            .get(HackageEndpoint::Preferred(package))
+            .await?
+            .json()
+            .await
+    }
+}
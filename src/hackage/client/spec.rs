@@ -0,0 +1,43 @@
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+
+/// The preferred versions of a package, split by their preference. Deprecated versions are
+/// marked as such by the package maintainers, and shouldn't be used. Versions of any other
+/// preference, e.g. `unpreferred-version`, are still available.
+#[derive(Deserialize)]
+pub struct HackagePreferredDto {
+    #[serde(rename = "normal-version", default)]
+    pub normal_version: Vec<String>,
+    #[serde(rename = "deprecated-version", default)]
+    pub deprecated_version: Vec<String>,
+    #[serde(flatten)]
+    pub other_versions: BTreeMap<String, Vec<String>>,
+}
+
+impl HackagePreferredDto {
+    /// All versions that aren't deprecated.
+    pub fn available_versions(&self) -> impl Iterator<Item = &String> {
+        self.normal_version
+            .iter()
+            .chain(self.other_versions.values().flatten())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_read_every_preference() {
+        let preferred: HackagePreferredDto = serde_json::from_str(
+            r#"{"normal-version":["1.1"],"unpreferred-version":["1.0"],"deprecated-version":["0.9"]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            vec!["1.1", "1.0"],
+            preferred.available_versions().collect::<Vec<_>>()
+        );
+        assert_eq!(vec!["0.9"], preferred.deprecated_version);
+    }
+}
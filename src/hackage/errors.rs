@@ -0,0 +1,48 @@
+use http::StatusCode;
+use thiserror::Error;
+
+use crate::errors::ApiError;
+
+#[derive(Error, Debug)]
+pub enum HackageError {
+    #[error("The requested resource was not found when interfacing with Hackage.")]
+    ResourceNotFound { source: Option<reqwest::Error> },
+    #[error("Client error. {:?}", source.status())]
+    ClientError { source: reqwest::Error },
+    #[error("Hackage had a server error. {:?}", source.status())]
+    ServerError { source: reqwest::Error },
+    #[error("Network error. {:?}", source.status())]
+    NetworkError { source: reqwest::Error },
+    #[error("Invalid Hackage package name {package:?}.")]
+    InvalidPackage { package: String },
+}
+
+impl ApiError for HackageError {
+    fn status_code(&self) -> http::StatusCode {
+        match self {
+            HackageError::ResourceNotFound { .. } => StatusCode::NOT_FOUND,
+            HackageError::ClientError { .. } | HackageError::NetworkError { .. } => {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+            HackageError::ServerError { .. } => StatusCode::BAD_GATEWAY,
+            HackageError::InvalidPackage { .. } => StatusCode::BAD_REQUEST,
+        }
+    }
+}
+
+impl From<reqwest::Error> for HackageError {
+    fn from(req_error: reqwest::Error) -> Self {
+        match req_error.status() {
+            Some(reqwest::StatusCode::NOT_FOUND) => Self::ResourceNotFound {
+                source: Some(req_error),
+            },
+            Some(status_code) if status_code.is_server_error() => {
+                Self::ServerError { source: req_error }
+            }
+            Some(status_code) if status_code.is_client_error() => {
+                Self::ClientError { source: req_error }
+            }
+            Some(_) | None => Self::NetworkError { source: req_error },
+        }
+    }
+}
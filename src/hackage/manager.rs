@@ -0,0 +1,108 @@
+use crate::{
+    registry::{PackageVersion, Registry},
+    versioning::pvp,
+};
+
+use super::{
+    api::HackageResponse,
+    client::{spec::HackagePreferredDto, HackageClient},
+    errors::HackageError,
+    HackagePackage,
+};
+
+pub struct HackageManager {
+    client: HackageClient,
+}
+
+impl HackageManager {
+    pub fn new(client: HackageClient) -> Self {
+        Self { client }
+    }
+
+    pub async fn get_preferred_versions(
+        &self,
+        package: &HackagePackage,
+    ) -> Result<HackagePreferredDto, HackageError> {
+        Ok(self.client.fetch_preferred_versions(package).await?)
+    }
+
+    /// Returns all package versions in DESCENDING order. Deprecated versions are only included
+    /// if requested.
+    pub async fn get_all_package_versions(
+        &self,
+        package: &HackagePackage,
+    ) -> Result<Vec<String>, HackageError> {
+        let preferred = self.get_preferred_versions(package).await?;
+        let mut versions: Vec<String> = preferred.available_versions().cloned().collect();
+        if package.include_deprecated.unwrap_or(false) {
+            versions.extend(preferred.deprecated_version);
+        }
+        pvp::sort_desc(&mut versions);
+        Ok(versions)
+    }
+
+    /// Returns the greatest preferred version or, if there are none, the greatest version that
+    /// isn't deprecated.
+    pub async fn get_latest_package_version(
+        &self,
+        package: &HackagePackage,
+    ) -> Result<HackageResponse, HackageError> {
+        let preferred = self.get_preferred_versions(package).await?;
+        let latest = pvp::latest(preferred.normal_version.iter().map(String::as_str))
+            .or_else(|| pvp::latest(preferred.available_versions().map(String::as_str)))
+            .ok_or(HackageError::ResourceNotFound { source: None })?;
+        Ok(HackageResponse {
+            name: package.name.clone(),
+            version: latest.to_owned(),
+            deprecated: false,
+        })
+    }
+
+    pub async fn get_package_version(
+        &self,
+        package: &HackagePackage,
+        version: &str,
+    ) -> Result<HackageResponse, HackageError> {
+        let preferred = self.get_preferred_versions(package).await?;
+        let deprecated = match (
+            preferred.available_versions().any(|v| v == version),
+            preferred.deprecated_version.iter().any(|v| v == version),
+        ) {
+            (true, _) => false,
+            (false, true) => true,
+            (false, false) => return Err(HackageError::ResourceNotFound { source: None }),
+        };
+        Ok(HackageResponse {
+            name: package.name.clone(),
+            version: version.to_owned(),
+            deprecated,
+        })
+    }
+}
+
+impl Registry for HackageManager {
+    type Package = HackagePackage;
+    type Error = HackageError;
+
+    async fn get_all_versions(
+        &self,
+        package: &HackagePackage,
+    ) -> Result<Vec<String>, HackageError> {
+        self.get_all_package_versions(package).await
+    }
+
+    async fn get_latest_version(
+        &self,
+        package: &HackagePackage,
+    ) -> Result<PackageVersion, HackageError> {
+        Ok(self.get_latest_package_version(package).await?.into())
+    }
+
+    async fn get_version(
+        &self,
+        package: &HackagePackage,
+        version: &str,
+    ) -> Result<PackageVersion, HackageError> {
+        Ok(self.get_package_version(package, version).await?.into())
+    }
+}
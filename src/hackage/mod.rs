@@ -0,0 +1,81 @@
+use crate::QueryParams;
+
+use self::errors::HackageError;
+
+pub mod api;
+pub mod client;
+pub mod errors;
+pub mod manager;
+
+#[derive(Debug)]
+pub struct HackagePackage {
+    pub name: String,
+    /// Whether to include deprecated versions. Unless set, they are left out. PVP versions have no
+    /// notion of pre-releases.
+    pub include_deprecated: Option<bool>,
+}
+
+impl HackagePackage {
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            include_deprecated: None,
+        }
+    }
+
+    /// Whether the name is a valid package name, i.e. alphanumeric words separated by hyphens.
+    pub fn is_valid(&self) -> bool {
+        self.name.split('-').all(|word| {
+            !word.is_empty()
+                && word.chars().all(|c| c.is_ascii_alphanumeric())
+                && word.chars().any(|c| c.is_ascii_alphabetic())
+        })
+    }
+}
+
+impl TryFrom<&QueryParams> for HackagePackage {
+    type Error = HackageError;
+
+    fn try_from(query: &QueryParams) -> Result<Self, Self::Error> {
+        let package = Self {
+            include_deprecated: query.flag("include_deprecated"),
+            ..Self::new(
+                query
+                    .get("package")
+                    .expect("No [package] query param")
+                    .to_owned(),
+            )
+        };
+        match package.is_valid() {
+            true => Ok(package),
+            false => Err(HackageError::InvalidPackage {
+                package: package.name,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_validate_package_names() {
+        for valid in [
+            "hlint",
+            "ormolu",
+            "haskell-language-server",
+            "base64-bytestring",
+        ] {
+            assert!(HackagePackage::new(valid.to_owned()).is_valid(), "{valid}");
+        }
+        for invalid in [
+            "", "-hlint", "hlint-", "foo--bar", "foo-1", "foo/bar", "foo_bar",
+        ] {
+            assert!(
+                !HackagePackage::new(invalid.to_owned()).is_valid(),
+                "{invalid}"
+            );
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use http::Method;
+use vercel_runtime::{Body, Error, Request, Response};
+
+use crate::{
+    batch::{errors::BatchError, BatchManager, BatchRequest},
+    vercel::{err_json, method_not_allowed, ok_json},
+    CacheControl,
+};
+
+pub async fn latest_versions(request: Request) -> Result<Response<Body>, Error> {
+    if request.method() != Method::POST {
+        return method_not_allowed();
+    }
+
+    let batch: BatchRequest = match serde_json::from_slice(request.body()) {
+        Ok(batch) => batch,
+        Err(err) => return err_json(BatchError::from(err)),
+    };
+    let manager = BatchManager::new();
+
+    match manager.get_latest_versions(batch.packages).await {
+        Ok(items) => ok_json(items, CacheControl::NoStore),
+        Err(err) => err_json(err),
+    }
+}
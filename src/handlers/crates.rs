@@ -0,0 +1,34 @@
+use vercel_runtime::{Body, Error, Request, Response};
+
+use crate::{
+    crates::{client::CratesClient, manager::CratesManager},
+    vercel::{registry_all_versions, registry_latest_version, registry_version},
+    CacheControl,
+};
+
+pub async fn all_versions(request: Request) -> Result<Response<Body>, Error> {
+    registry_all_versions(
+        &request,
+        &CratesManager::new(CratesClient::new()),
+        CacheControl::PublicMedium,
+    )
+    .await
+}
+
+pub async fn latest_version(request: Request) -> Result<Response<Body>, Error> {
+    registry_latest_version(
+        &request,
+        &CratesManager::new(CratesClient::new()),
+        CacheControl::PublicMedium,
+    )
+    .await
+}
+
+pub async fn version(request: Request) -> Result<Response<Body>, Error> {
+    registry_version(
+        &request,
+        &CratesManager::new(CratesClient::new()),
+        CacheControl::PublicMedium,
+    )
+    .await
+}
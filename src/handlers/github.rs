@@ -0,0 +1,130 @@
+use http::Method;
+use serde::Serialize;
+use vercel_runtime::{Body, Error, Request, Response};
+
+use crate::{
+    github::{
+        api::TagResponse,
+        client::{graphql::tags::Tag, spec::GitHubReleaseDto, GitHubClient},
+        manager::GitHubManager,
+        GitHubRepo, GitHubTag,
+    },
+    vercel::{err_json, method_not_allowed, ok_json, parse_url},
+    CacheControl, QueryParams,
+};
+
+#[derive(Serialize)]
+struct ReleasesResponse(Vec<String>);
+
+impl From<Vec<GitHubReleaseDto>> for ReleasesResponse {
+    fn from(releases: Vec<GitHubReleaseDto>) -> Self {
+        ReleasesResponse(releases.into_iter().map(|r| r.tag_name).collect())
+    }
+}
+
+#[derive(Serialize)]
+struct TagsResponse(Vec<String>);
+
+impl From<Vec<Tag>> for TagsResponse {
+    fn from(tags: Vec<Tag>) -> Self {
+        Self(tags.into_iter().map(|t| t.name).collect())
+    }
+}
+
+pub async fn all_releases(request: Request) -> Result<Response<Body>, Error> {
+    if request.method() != Method::GET {
+        return method_not_allowed();
+    }
+
+    let url = parse_url(&request)?;
+    let query_params: QueryParams = (&url).into();
+    let repo = (&query_params).into();
+    let manager = GitHubManager::new(GitHubClient::new());
+
+    match manager.get_all_releases(&repo).await {
+        Ok(releases) => ok_json::<ReleasesResponse>(releases.into(), CacheControl::PublicMedium),
+        Err(err) => err_json(err),
+    }
+}
+
+pub async fn latest_release(request: Request) -> Result<Response<Body>, Error> {
+    if request.method() != Method::GET {
+        return method_not_allowed();
+    }
+
+    let url = parse_url(&request)?;
+    let query_params: QueryParams = (&url).into();
+    let repo = (&query_params).into();
+    let manager = GitHubManager::new(GitHubClient::new());
+
+    match manager.get_latest_release(&repo).await {
+        Ok(latest_release) => ok_json(latest_release, CacheControl::PublicShort),
+        Err(err) => err_json(err),
+    }
+}
+
+pub async fn release(request: Request) -> Result<Response<Body>, Error> {
+    if request.method() != Method::GET {
+        return method_not_allowed();
+    }
+
+    let url = parse_url(&request)?;
+    let query_params: QueryParams = (&url).into();
+    let release: GitHubTag = query_params.get("release").unwrap().parse()?;
+    let repo = (&query_params).into();
+    let manager = GitHubManager::new(GitHubClient::new());
+
+    match manager.get_release_by_tag(&repo, &release).await {
+        Ok(release) => ok_json(release, CacheControl::PublicMedium),
+        Err(err) => err_json(err),
+    }
+}
+
+pub async fn all_tags(request: Request) -> Result<Response<Body>, Error> {
+    if request.method() != Method::GET {
+        return method_not_allowed();
+    }
+
+    let url = parse_url(&request)?;
+    let query_params: QueryParams = (&url).into();
+    let repo = (&query_params).into();
+    let manager = GitHubManager::new(GitHubClient::new());
+
+    match manager.get_all_tags(&repo).await {
+        Ok(tags) => ok_json::<TagsResponse>(tags.into(), CacheControl::PublicMedium),
+        Err(err) => err_json(err),
+    }
+}
+
+pub async fn latest_tag(request: Request) -> Result<Response<Body>, Error> {
+    if request.method() != Method::GET {
+        return method_not_allowed();
+    }
+
+    let url = parse_url(&request)?;
+    let query_params: QueryParams = (&url).into();
+    let repo: GitHubRepo = (&query_params).into();
+    let manager = GitHubManager::new(GitHubClient::new());
+
+    match manager.get_latest_tag(&repo).await {
+        Ok(latest_tag) => ok_json::<TagResponse>(latest_tag.into(), CacheControl::PublicMedium),
+        Err(err) => err_json(err),
+    }
+}
+
+pub async fn tag(request: Request) -> Result<Response<Body>, Error> {
+    if request.method() != Method::GET {
+        return method_not_allowed();
+    }
+
+    let url = parse_url(&request)?;
+    let query_params: QueryParams = (&url).into();
+    let tag: GitHubTag = query_params.get("tag").unwrap().parse()?;
+    let repo = (&query_params).into();
+    let manager = GitHubManager::new(GitHubClient::new());
+
+    match manager.get_ref(&repo, &tag).await {
+        Ok(github_ref) => ok_json::<TagResponse>(github_ref.into(), CacheControl::PublicMedium),
+        Err(err) => err_json(err),
+    }
+}
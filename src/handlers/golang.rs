@@ -0,0 +1,34 @@
+use vercel_runtime::{Body, Error, Request, Response};
+
+use crate::{
+    golang::{client::GolangClient, manager::GolangManager},
+    vercel::{registry_all_versions, registry_latest_version, registry_version},
+    CacheControl,
+};
+
+pub async fn all_versions(request: Request) -> Result<Response<Body>, Error> {
+    registry_all_versions(
+        &request,
+        &GolangManager::new(GolangClient::new()),
+        CacheControl::PublicMedium,
+    )
+    .await
+}
+
+pub async fn latest_version(request: Request) -> Result<Response<Body>, Error> {
+    registry_latest_version(
+        &request,
+        &GolangManager::new(GolangClient::new()),
+        CacheControl::PublicMedium,
+    )
+    .await
+}
+
+pub async fn version(request: Request) -> Result<Response<Body>, Error> {
+    registry_version(
+        &request,
+        &GolangManager::new(GolangClient::new()),
+        CacheControl::PublicMedium,
+    )
+    .await
+}
@@ -0,0 +1,34 @@
+use vercel_runtime::{Body, Error, Request, Response};
+
+use crate::{
+    hackage::{client::HackageClient, manager::HackageManager},
+    vercel::{registry_all_versions, registry_latest_version, registry_version},
+    CacheControl,
+};
+
+pub async fn all_versions(request: Request) -> Result<Response<Body>, Error> {
+    registry_all_versions(
+        &request,
+        &HackageManager::new(HackageClient::new()),
+        CacheControl::PublicMedium,
+    )
+    .await
+}
+
+pub async fn latest_version(request: Request) -> Result<Response<Body>, Error> {
+    registry_latest_version(
+        &request,
+        &HackageManager::new(HackageClient::new()),
+        CacheControl::PublicMedium,
+    )
+    .await
+}
+
+pub async fn version(request: Request) -> Result<Response<Body>, Error> {
+    registry_version(
+        &request,
+        &HackageManager::new(HackageClient::new()),
+        CacheControl::PublicMedium,
+    )
+    .await
+}
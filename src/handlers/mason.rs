@@ -0,0 +1,55 @@
+use http::Method;
+use serde::Serialize;
+use vercel_runtime::{Body, Error, Request, Response};
+
+use crate::{
+    github::{
+        client::{api_keys_from_env, graphql::sponsors::Sponsor, GitHubClient},
+        manager::GitHubManager,
+        GitHubRepo,
+    },
+    renovate::{client::RenovateClient, errors::RenovateError, manager::RenovateManager},
+    vercel::{err_json, method_not_allowed, ok_json},
+    CacheControl,
+};
+
+#[derive(Serialize)]
+pub struct SponsorsResponse {
+    pub current_sponsors: Vec<String>,
+}
+
+impl From<Vec<Sponsor>> for SponsorsResponse {
+    fn from(current_sponsors: Vec<Sponsor>) -> Self {
+        Self {
+            current_sponsors: current_sponsors.into_iter().map(|s| s.login).collect(),
+        }
+    }
+}
+
+pub async fn sponsors(request: Request) -> Result<Response<Body>, Error> {
+    if request.method() != Method::GET {
+        return method_not_allowed();
+    }
+
+    let manager = GitHubManager::new(GitHubClient::new());
+    match manager.get_all_sponsors("williamboman".to_owned()).await {
+        Ok(sponsors) => ok_json::<SponsorsResponse>(sponsors.into(), CacheControl::PublicMedium),
+        Err(err) => err_json(err),
+    }
+}
+
+pub async fn renovate_badge(request: Request) -> Result<Response<Body>, Error> {
+    if request.method() != Method::GET {
+        return method_not_allowed();
+    }
+
+    let Some(api_key) = api_keys_from_env().into_iter().next() else {
+        return err_json(RenovateError::MissingApiKey);
+    };
+    let manager = RenovateManager::new(RenovateClient::new(api_key));
+    let registry_repo = GitHubRepo::new("mason-org".to_owned(), "mason-registry".to_owned());
+    match manager.get_badge(&registry_repo).await {
+        Ok(badge) => ok_json(badge, CacheControl::NoStore),
+        Err(err) => err_json(err),
+    }
+}
@@ -0,0 +1,19 @@
+//! Request handlers for every API route. These are shared by the Vercel functions in `api/` and
+//! the standalone server.
+
+pub mod batch;
+pub mod crates;
+pub mod github;
+pub mod golang;
+pub mod hackage;
+pub mod luarocks;
+pub mod mason;
+pub mod maven;
+pub mod npm;
+pub mod nuget;
+pub mod opam;
+pub mod openvsx;
+pub mod packagist;
+pub mod purl;
+pub mod pypi;
+pub mod rubygems;
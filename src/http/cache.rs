@@ -0,0 +1,648 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    num::NonZeroUsize,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, OnceLock,
+    },
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use lru::LruCache;
+use reqwest::header::{
+    HeaderMap, HeaderName, HeaderValue, CONTENT_ENCODING, CONTENT_LENGTH, TRANSFER_ENCODING,
+};
+use serde::{Deserialize, Serialize};
+
+/// An upstream response stored in a [`Cache`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CachedResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    #[serde(skip)]
+    pub body: Vec<u8>,
+    /// Seconds since the UNIX epoch after which the response is stale.
+    pub expires_at: u64,
+}
+
+impl CachedResponse {
+    pub fn new(status: u16, headers: &HeaderMap, body: Vec<u8>, ttl: Duration) -> Self {
+        Self {
+            status,
+            headers: storable_headers(headers).collect(),
+            body,
+            expires_at: expires_at(ttl),
+        }
+    }
+
+    /// Marks the response as fresh again after the upstream confirmed it is unchanged, updating
+    /// the stored headers with the ones sent along with the `304 Not Modified`.
+    pub fn revalidated(mut self, headers: &HeaderMap, ttl: Duration) -> Self {
+        for (name, value) in storable_headers(headers) {
+            match self.headers.iter_mut().find(|(stored, _)| *stored == name) {
+                Some((_, stored_value)) => *stored_value = value,
+                None => self.headers.push((name, value)),
+            }
+        }
+        self.expires_at = expires_at(ttl);
+        self
+    }
+
+    pub fn header(&self, name: &HeaderName) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(stored, _)| stored == name.as_str())
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn is_expired(&self) -> bool {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs()
+            >= self.expires_at
+    }
+}
+
+fn storable_headers(headers: &HeaderMap) -> impl Iterator<Item = (String, String)> + '_ {
+    headers
+        .iter()
+        // The body is stored as received, so framing headers no longer apply when replaying it.
+        .filter(|(name, _)| ![CONTENT_ENCODING, CONTENT_LENGTH, TRANSFER_ENCODING].contains(name))
+        .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_owned())))
+}
+
+fn expires_at(ttl: Duration) -> u64 {
+    (SystemTime::now() + ttl)
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+impl From<CachedResponse> for reqwest::Response {
+    fn from(cached: CachedResponse) -> Self {
+        let mut response = http::Response::builder().status(cached.status);
+        for (name, value) in &cached.headers {
+            response = response.header(name, value);
+        }
+        response
+            .body(cached.body)
+            .expect("Cached response is valid.")
+            .into()
+    }
+}
+
+/// A store for upstream responses. Expired entries are still returned until evicted, so they can be
+/// revalidated with the upstream instead of being refetched.
+///
+/// Implementations may block, e.g. on file IO, so [`Client`](super::client::Client) calls them on a
+/// blocking thread.
+pub trait Cache: Send + Sync {
+    fn get(&self, key: &str) -> Option<CachedResponse>;
+
+    fn insert(&self, key: &str, response: CachedResponse);
+}
+
+impl<C: Cache + ?Sized> Cache for Arc<C> {
+    fn get(&self, key: &str) -> Option<CachedResponse> {
+        (**self).get(key)
+    }
+
+    fn insert(&self, key: &str, response: CachedResponse) {
+        (**self).insert(key, response)
+    }
+}
+
+/// In-memory cache evicting the least recently used entries once their total size exceeds
+/// `max_size` bytes.
+pub struct MemoryCache {
+    entries: Mutex<MemoryEntries>,
+}
+
+struct MemoryEntries {
+    lru: LruCache<String, CachedResponse>,
+    size: usize,
+    max_size: usize,
+}
+
+impl MemoryCache {
+    pub fn new(max_size: NonZeroUsize) -> Self {
+        Self {
+            entries: Mutex::new(MemoryEntries {
+                lru: LruCache::unbounded(),
+                size: 0,
+                max_size: max_size.get(),
+            }),
+        }
+    }
+}
+
+impl CachedResponse {
+    /// The approximate memory held by the response, stored under `key`.
+    fn size(&self, key: &str) -> usize {
+        key.len()
+            + self.body.len()
+            + self
+                .headers
+                .iter()
+                .map(|(name, value)| name.len() + value.len())
+                .sum::<usize>()
+    }
+}
+
+impl Cache for MemoryCache {
+    fn get(&self, key: &str) -> Option<CachedResponse> {
+        self.entries.lock().ok()?.lru.get(key).cloned()
+    }
+
+    fn insert(&self, key: &str, response: CachedResponse) {
+        let Ok(mut entries) = self.entries.lock() else {
+            return;
+        };
+        if let Some(previous) = entries.lru.pop(key) {
+            entries.size -= previous.size(key);
+        }
+        let size = response.size(key);
+        if size > entries.max_size {
+            return;
+        }
+        entries.size += size;
+        entries.lru.put(key.to_owned(), response);
+        while entries.size > entries.max_size {
+            let Some((key, evicted)) = entries.lru.pop_lru() else {
+                break;
+            };
+            entries.size -= evicted.size(&key);
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct DiskEntry {
+    key: String,
+    #[serde(flatten)]
+    response: CachedResponse,
+}
+
+/// On-disk cache storing one file per entry in `dir`, so entries survive restarts. Once the files
+/// exceed `max_size` bytes in total, the least recently written ones are deleted.
+///
+/// Each file holds a JSON header line followed by the raw response body. Entries are written to a
+/// temporary file first and then renamed, so readers never see a partially written entry.
+pub struct DiskCache {
+    dir: PathBuf,
+    max_size: u64,
+    /// The total size of the entries, as of the last scan plus the entries written since.
+    size: AtomicU64,
+}
+
+impl DiskCache {
+    const TEMP_PREFIX: &'static str = ".tmp-";
+
+    pub fn new(dir: PathBuf, max_size: u64) -> std::io::Result<Self> {
+        fs::create_dir_all(&dir)?;
+        let cache = Self {
+            dir,
+            max_size,
+            size: AtomicU64::new(0),
+        };
+        // Temporary files are left behind by writes that were interrupted.
+        for entry in fs::read_dir(&cache.dir)?.flatten() {
+            if entry
+                .file_name()
+                .to_string_lossy()
+                .starts_with(Self::TEMP_PREFIX)
+            {
+                let _ = fs::remove_file(entry.path());
+            }
+        }
+        cache.evict()?;
+        Ok(cache)
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir
+            .join(format!("{:016x}", super::stable_hash(key.as_bytes())))
+    }
+
+    fn write(&self, key: &str, contents: &[u8]) -> std::io::Result<()> {
+        static WRITES: AtomicU64 = AtomicU64::new(0);
+        let path = self.path(key);
+        let temp = self.dir.join(format!(
+            "{}{}-{}",
+            Self::TEMP_PREFIX,
+            std::process::id(),
+            WRITES.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::write(&temp, contents)?;
+        let replaced = fs::metadata(&path).map_or(0, |metadata| metadata.len());
+        if let Err(err) = fs::rename(&temp, &path) {
+            let _ = fs::remove_file(&temp);
+            return Err(err);
+        }
+        let size = self
+            .size
+            .fetch_add(contents.len() as u64, Ordering::Relaxed)
+            + contents.len() as u64;
+        if size.saturating_sub(replaced) > self.max_size {
+            self.evict()?;
+        }
+        Ok(())
+    }
+
+    /// Deletes the least recently written entries until the rest fit into `max_size`.
+    fn evict(&self) -> std::io::Result<()> {
+        let mut entries: Vec<_> = fs::read_dir(&self.dir)?
+            .flatten()
+            .filter(|entry| {
+                !entry
+                    .file_name()
+                    .to_string_lossy()
+                    .starts_with(Self::TEMP_PREFIX)
+            })
+            .filter_map(|entry| {
+                let metadata = entry.metadata().ok()?;
+                Some((metadata.modified().ok()?, metadata.len(), entry.path()))
+            })
+            .collect();
+        entries.sort();
+        let mut size: u64 = entries.iter().map(|(_, len, _)| len).sum();
+        for (_, len, path) in entries {
+            if size <= self.max_size {
+                break;
+            }
+            if fs::remove_file(path).is_ok() {
+                size -= len;
+            }
+        }
+        self.size.store(size, Ordering::Relaxed);
+        Ok(())
+    }
+}
+
+impl Cache for DiskCache {
+    fn get(&self, key: &str) -> Option<CachedResponse> {
+        let contents = fs::read(self.path(key)).ok()?;
+        let newline = contents.iter().position(|byte| *byte == b'\n')?;
+        let entry: DiskEntry = serde_json::from_slice(&contents[..newline]).ok()?;
+        if entry.key != key {
+            return None;
+        }
+        Some(CachedResponse {
+            body: contents[newline + 1..].to_vec(),
+            ..entry.response
+        })
+    }
+
+    fn insert(&self, key: &str, response: CachedResponse) {
+        let body = response.body.clone();
+        let entry = DiskEntry {
+            key: key.to_owned(),
+            response,
+        };
+        let Ok(mut contents) = serde_json::to_vec(&entry) else {
+            return;
+        };
+        contents.push(b'\n');
+        contents.extend(body);
+        if let Err(err) = self.write(key, &contents) {
+            tracing::warn!("Failed to write cache entry for {}: {}", key, err);
+        }
+    }
+}
+
+/// Consults each cache in order, copying fresh hits into the caches before it.
+pub struct TieredCache {
+    tiers: Vec<Box<dyn Cache>>,
+}
+
+impl TieredCache {
+    pub fn new(tiers: Vec<Box<dyn Cache>>) -> Self {
+        Self { tiers }
+    }
+}
+
+impl Cache for TieredCache {
+    fn get(&self, key: &str) -> Option<CachedResponse> {
+        let mut stale = None;
+        for (index, tier) in self.tiers.iter().enumerate() {
+            match tier.get(key) {
+                Some(response) if !response.is_expired() => {
+                    for faster_tier in &self.tiers[..index] {
+                        faster_tier.insert(key, response.clone());
+                    }
+                    return Some(response);
+                }
+                Some(response) => {
+                    stale.get_or_insert(response);
+                }
+                None => {}
+            }
+        }
+        stale
+    }
+
+    fn insert(&self, key: &str, response: CachedResponse) {
+        for tier in &self.tiers {
+            tier.insert(key, response.clone());
+        }
+    }
+}
+
+/// A value parsed from an upstream response, along with the conditional request headers to
+/// revalidate it with.
+pub struct ParsedEntry<T> {
+    value: Arc<T>,
+    conditions: Vec<(HeaderName, HeaderValue)>,
+    /// Seconds since the UNIX epoch after which the value is stale.
+    expires_at: u64,
+}
+
+impl<T> ParsedEntry<T> {
+    pub fn new(value: Arc<T>, headers: &HeaderMap, ttl: Duration) -> Self {
+        let conditions = VALIDATORS
+            .iter()
+            .filter_map(|(validator, condition)| {
+                Some((condition.clone(), headers.get(validator)?.clone()))
+            })
+            .collect();
+        Self {
+            value,
+            conditions,
+            expires_at: expires_at(ttl),
+        }
+    }
+
+    pub fn is_expired(&self) -> bool {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs()
+            >= self.expires_at
+    }
+
+    pub fn revalidated(&mut self, ttl: Duration) {
+        self.expires_at = expires_at(ttl);
+    }
+}
+
+/// The parsed value stored for a single URL.
+pub struct ParsedSlot<T> {
+    /// Only locked to read or replace the entry, never while fetching or parsing it.
+    pub(super) entry: Mutex<Option<ParsedEntry<T>>>,
+    /// Held while the entry is refreshed, so concurrent lookups share a single fetch.
+    pub(super) refresh: tokio::sync::Mutex<()>,
+}
+
+impl<T> ParsedSlot<T> {
+    /// Returns the stored value, unless it is stale.
+    pub fn fresh(&self) -> Option<Arc<T>> {
+        let entry = self.entry.lock().ok()?;
+        entry
+            .as_ref()
+            .filter(|entry| !entry.is_expired())
+            .map(|entry| entry.value.clone())
+    }
+
+    /// The headers to revalidate the stored value with.
+    pub fn conditions(&self) -> Vec<(HeaderName, HeaderValue)> {
+        match self.entry.lock().ok().as_deref() {
+            Some(Some(entry)) => entry.conditions.clone(),
+            _ => vec![],
+        }
+    }
+
+    /// Marks the stored value as fresh again after the upstream confirmed it is unchanged.
+    pub fn revalidated(&self, ttl: Duration) -> Option<Arc<T>> {
+        let mut entry = self.entry.lock().ok()?;
+        let entry = entry.as_mut()?;
+        entry.revalidated(ttl);
+        Some(entry.value.clone())
+    }
+
+    pub fn insert(&self, entry: ParsedEntry<T>) {
+        if let Ok(mut stored) = self.entry.lock() {
+            *stored = Some(entry);
+        }
+    }
+}
+
+/// In-memory store for values parsed from large upstream responses, e.g. repository indexes, keyed
+/// by URL, so they're neither stored nor parsed again on every request. See
+/// [`Client::get_parsed`](super::client::Client::get_parsed).
+pub struct ParsedCache<T> {
+    slots: Mutex<BTreeMap<String, Arc<ParsedSlot<T>>>>,
+}
+
+impl<T> ParsedCache<T> {
+    pub const fn new() -> Self {
+        Self {
+            slots: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    pub fn slot(&self, url: &str) -> Arc<ParsedSlot<T>> {
+        let mut slots = self.slots.lock().unwrap_or_else(|err| err.into_inner());
+        slots
+            .entry(url.to_owned())
+            .or_insert_with(|| {
+                Arc::new(ParsedSlot {
+                    entry: Mutex::new(None),
+                    refresh: tokio::sync::Mutex::new(()),
+                })
+            })
+            .clone()
+    }
+}
+
+impl<T> Default for ParsedCache<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Response headers validating a stored response, and the request headers to send them back in.
+pub const VALIDATORS: [(HeaderName, HeaderName); 2] = [
+    (reqwest::header::ETAG, reqwest::header::IF_NONE_MATCH),
+    (
+        reqwest::header::LAST_MODIFIED,
+        reqwest::header::IF_MODIFIED_SINCE,
+    ),
+];
+
+/// The default limit for the in-memory cache, see [`shared`].
+pub const DEFAULT_MEMORY_SIZE: usize = 256 * 1024 * 1024;
+/// The default limit for the on-disk cache, see [`shared`].
+pub const DEFAULT_DISK_SIZE: u64 = 1024 * 1024 * 1024;
+
+/// Returns the process-wide cache configured via `HTTP_CACHE_SIZE` (bytes kept in memory, defaults
+/// to [`DEFAULT_MEMORY_SIZE`], `0` disables it), `HTTP_CACHE_DIR` (enables the on-disk cache) and
+/// `HTTP_CACHE_DIR_SIZE` (bytes kept on disk, defaults to [`DEFAULT_DISK_SIZE`]).
+pub fn shared() -> Option<Arc<dyn Cache>> {
+    static CACHE: OnceLock<Option<Arc<dyn Cache>>> = OnceLock::new();
+    CACHE
+        .get_or_init(|| {
+            let mut tiers: Vec<Box<dyn Cache>> = vec![];
+            let size = std::env::var("HTTP_CACHE_SIZE")
+                .ok()
+                .and_then(|size| size.parse().ok())
+                .unwrap_or(DEFAULT_MEMORY_SIZE);
+            if let Some(size) = NonZeroUsize::new(size) {
+                tiers.push(Box::new(MemoryCache::new(size)));
+            }
+            if let Some(dir) = std::env::var_os("HTTP_CACHE_DIR").filter(|dir| !dir.is_empty()) {
+                let size = std::env::var("HTTP_CACHE_DIR_SIZE")
+                    .ok()
+                    .and_then(|size| size.parse().ok())
+                    .unwrap_or(DEFAULT_DISK_SIZE);
+                match DiskCache::new(dir.into(), size) {
+                    Ok(disk) => tiers.push(Box::new(disk)),
+                    Err(err) => tracing::error!("Failed to set up on-disk cache: {}", err),
+                }
+            }
+            match tiers.len() {
+                0 => None,
+                1 => tiers.pop().map(Arc::from),
+                _ => Some(Arc::new(TieredCache::new(tiers))),
+            }
+        })
+        .clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::header::ETAG;
+
+    use super::*;
+
+    fn response(body: &str, ttl: Duration) -> CachedResponse {
+        let mut headers = HeaderMap::new();
+        headers.insert("etag", "\"abc\"".parse().unwrap());
+        headers.insert(CONTENT_LENGTH, body.len().into());
+        CachedResponse::new(200, &headers, body.as_bytes().to_vec(), ttl)
+    }
+
+    /// The size of two entries returned by [`response`] with single character keys and bodies.
+    fn two_entries() -> usize {
+        2 * response("a", Duration::ZERO).size("a")
+    }
+
+    fn capacity(size: usize) -> NonZeroUsize {
+        NonZeroUsize::new(size).unwrap()
+    }
+
+    #[test]
+    fn should_evict_least_recently_used() {
+        let cache = MemoryCache::new(capacity(two_entries()));
+        cache.insert("a", response("a", Duration::from_secs(60)));
+        cache.insert("b", response("b", Duration::from_secs(60)));
+        assert!(cache.get("a").is_some());
+        cache.insert("c", response("c", Duration::from_secs(60)));
+
+        assert!(cache.get("a").is_some());
+        assert!(cache.get("b").is_none());
+        assert!(cache.get("c").is_some());
+    }
+
+    #[test]
+    fn should_bound_memory_by_size() {
+        let cache = MemoryCache::new(capacity(two_entries()));
+        cache.insert(
+            "a",
+            response(&"a".repeat(two_entries()), Duration::from_secs(60)),
+        );
+        assert!(cache.get("a").is_none());
+
+        cache.insert("a", response("a", Duration::from_secs(60)));
+        cache.insert("b", response("b", Duration::from_secs(60)));
+        // Replacing an entry doesn't count it twice.
+        cache.insert("b", response("b", Duration::from_secs(60)));
+        assert!(cache.get("a").is_some());
+        cache.insert("c", response("cc", Duration::from_secs(60)));
+        assert!(cache.get("a").is_none());
+        assert!(cache.get("b").is_none());
+        assert!(cache.get("c").is_some());
+    }
+
+    #[test]
+    fn should_keep_expired_entries_for_revalidation() {
+        let cache = MemoryCache::new(capacity(two_entries()));
+        cache.insert("a", response("a", Duration::ZERO));
+        let stale = cache.get("a").unwrap();
+        assert!(stale.is_expired());
+
+        let mut headers = HeaderMap::new();
+        headers.insert("etag", "\"def\"".parse().unwrap());
+        headers.insert("x-request-id", "1".parse().unwrap());
+        let revalidated = stale.revalidated(&headers, Duration::from_secs(60));
+        assert!(!revalidated.is_expired());
+        assert_eq!(b"a".to_vec(), revalidated.body);
+        assert_eq!(Some("\"def\""), revalidated.header(&ETAG));
+        assert_eq!(
+            Some("1"),
+            revalidated.header(&HeaderName::from_static("x-request-id"))
+        );
+    }
+
+    #[test]
+    fn should_persist_to_disk() {
+        let dir = std::env::temp_dir().join(format!("mason-cache-test-{}", std::process::id()));
+        let cache = DiskCache::new(dir.clone(), DEFAULT_DISK_SIZE).unwrap();
+        let expected = response("{\"version\":\"1.0.0\"}\n", Duration::from_secs(60));
+        cache.insert("GET https://example.com", expected.clone());
+
+        let cached = DiskCache::new(dir.clone(), DEFAULT_DISK_SIZE)
+            .unwrap()
+            .get("GET https://example.com")
+            .unwrap();
+        assert_eq!(expected, cached);
+        assert_eq!(
+            vec![("etag".to_owned(), "\"abc\"".to_owned())],
+            cached.headers
+        );
+        assert!(cache.get("GET https://example.org").is_none());
+        // Only the entry itself is left behind, without temporary files.
+        assert_eq!(1, fs::read_dir(&dir).unwrap().count());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn should_evict_oldest_files() {
+        let dir = std::env::temp_dir().join(format!("mason-cache-evict-{}", std::process::id()));
+        let entry = |key: &str| {
+            let mut contents = serde_json::to_vec(&DiskEntry {
+                key: key.to_owned(),
+                response: response("a", Duration::from_secs(60)),
+            })
+            .unwrap();
+            contents.extend(b"\na");
+            contents.len() as u64
+        };
+        let cache = DiskCache::new(dir.clone(), entry("a") + entry("b")).unwrap();
+        cache.insert("a", response("a", Duration::from_secs(60)));
+        std::thread::sleep(Duration::from_millis(10));
+        cache.insert("b", response("a", Duration::from_secs(60)));
+        assert!(cache.get("a").is_some());
+        std::thread::sleep(Duration::from_millis(10));
+        cache.insert("c", response("a", Duration::from_secs(60)));
+
+        assert!(cache.get("a").is_none());
+        assert!(cache.get("b").is_some());
+        assert!(cache.get("c").is_some());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn should_promote_hits_to_faster_tiers() {
+        let slow = Arc::new(MemoryCache::new(capacity(two_entries())));
+        slow.insert("a", response("a", Duration::from_secs(60)));
+        let fast = Arc::new(MemoryCache::new(capacity(two_entries())));
+        let cache = TieredCache::new(vec![Box::new(fast.clone()), Box::new(slow)]);
+
+        assert!(fast.get("a").is_none());
+        assert!(cache.get("a").is_some());
+        assert!(fast.get("a").is_some());
+    }
+}
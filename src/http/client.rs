@@ -1,26 +1,145 @@
+use std::{sync::Arc, time::Duration};
+
 use reqwest::{
-    blocking::Response,
-    header::{HeaderMap, ACCEPT, USER_AGENT},
+    header::{HeaderMap, HeaderValue, ACCEPT, RETRY_AFTER, USER_AGENT},
+    Request, RequestBuilder, Response, StatusCode,
 };
 use serde::Serialize;
 
+use crate::CacheControl;
+
+use super::{
+    cache::{self, Cache, CachedResponse, ParsedCache, ParsedEntry, VALIDATORS},
+    retry::RetryPolicy,
+    throttle::Throttle,
+    validators::record_last_modified,
+};
+
 pub struct Client {
-    client: reqwest::blocking::Client,
+    client: reqwest::Client,
+    base_url: String,
     headers: Option<HeaderMap>,
+    cache: Option<Arc<dyn Cache>>,
+    retry_policy: RetryPolicy,
+    response_hook: Option<ResponseHook>,
+    throttle: Option<Arc<Throttle>>,
+    max_throttle_wait: Duration,
+    error_responses: bool,
+}
+
+/// The default limit for how long requests queue up for a throttled upstream, see
+/// [`Client::with_min_request_interval`].
+pub const DEFAULT_MAX_THROTTLE_WAIT: Duration = Duration::from_secs(10);
+
+/// Called with every response received from the upstream, including error responses and ones
+/// that are retried, but not with responses served from the cache.
+pub type ResponseHook = Arc<dyn Fn(&Response) + Send + Sync>;
+
+fn is_error(response: &Response) -> bool {
+    response.status().is_client_error() || response.status().is_server_error()
+}
+
+/// A local `503 Service Unavailable` response, standing in for a request that would have had to
+/// wait `wait` for the upstream's throttle.
+fn throttled(wait: Duration) -> Response {
+    http::Response::builder()
+        .status(StatusCode::SERVICE_UNAVAILABLE)
+        .header(RETRY_AFTER, wait.as_secs_f64().ceil().to_string())
+        .body(reqwest::Body::from(""))
+        .expect("Response parts are valid.")
+        .into()
 }
 
 pub trait HttpEndpoint {
-    fn as_full_url(&self) -> String;
+    fn as_full_url(&self, base_url: &str) -> String;
+
+    /// How long successful responses from this endpoint may be cached. Defaults to not caching.
+    fn cache_control(&self) -> CacheControl {
+        CacheControl::NoStore
+    }
+}
+
+impl<E: HttpEndpoint + ?Sized> HttpEndpoint for &E {
+    fn as_full_url(&self, base_url: &str) -> String {
+        (**self).as_full_url(base_url)
+    }
+
+    fn cache_control(&self) -> CacheControl {
+        (**self).cache_control()
+    }
+}
+
+/// Reads an upstream base URL from the environment variable `key`, falling back to `default`.
+/// Trailing slashes are stripped so endpoints can be appended with `{base_url}/{path}`.
+pub fn base_url_from_env(key: &str, default: &str) -> String {
+    base_url_or(std::env::var(key).ok(), default)
+}
+
+fn base_url_or(value: Option<String>, default: &str) -> String {
+    value
+        .filter(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| default.to_owned())
+        .trim_end_matches('/')
+        .to_owned()
 }
 
 impl Client {
-    pub fn new(headers: Option<HeaderMap>) -> Self {
+    pub fn new(base_url: String, headers: Option<HeaderMap>) -> Self {
         Self {
             headers,
-            client: reqwest::blocking::Client::new(),
+            base_url: base_url.trim_end_matches('/').to_owned(),
+            client: reqwest::Client::new(),
+            cache: cache::shared(),
+            retry_policy: RetryPolicy::from_env(),
+            response_hook: None,
+            throttle: None,
+            max_throttle_wait: DEFAULT_MAX_THROTTLE_WAIT,
+            error_responses: false,
         }
     }
 
+    /// Spaces requests to the upstream's host at least `min_interval` apart, including retries
+    /// and revalidations, across all clients of the process. Responses served from the cache
+    /// aren't throttled. Requests that would have to wait longer than
+    /// [`DEFAULT_MAX_THROTTLE_WAIT`] fail right away with `503 Service Unavailable` instead.
+    pub fn with_min_request_interval(mut self, min_interval: Duration) -> Self {
+        let host = url::Url::parse(&self.base_url)
+            .ok()
+            .and_then(|url| {
+                Some(format!(
+                    "{}:{}",
+                    url.host_str()?,
+                    url.port_or_known_default()?
+                ))
+            })
+            .unwrap_or_else(|| self.base_url.clone());
+        self.throttle = Some(Throttle::shared(&host, min_interval));
+        self
+    }
+
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Returns error responses instead of failing with their status, for upstreams whose error
+    /// headers or bodies are needed to tell errors apart. Error responses are never cached.
+    pub fn with_error_responses(mut self) -> Self {
+        self.error_responses = true;
+        self
+    }
+
+    pub fn with_response_hook(mut self, response_hook: ResponseHook) -> Self {
+        self.response_hook = Some(response_hook);
+        self
+    }
+
+    /// Replaces the process-wide response cache, see [`cache::shared`].
+    pub fn with_cache(mut self, cache: Option<Arc<dyn Cache>>) -> Self {
+        self.cache = cache;
+        self
+    }
+
     fn headers(&self) -> HeaderMap {
         let mut headers = HeaderMap::new();
         headers.insert(ACCEPT, "application/json".parse().unwrap());
@@ -36,40 +155,419 @@ impl Client {
         headers
     }
 
-    pub fn get_with_query<Endpoint: HttpEndpoint, Query: Serialize + ?Sized>(
+    pub async fn get_with_query<Endpoint: HttpEndpoint, Query: Serialize + ?Sized>(
         &self,
         endpoint: Endpoint,
         query: &Query,
     ) -> Result<Response, reqwest::Error> {
-        self.client
-            .get(endpoint.as_full_url())
-            .query(query)
-            .headers(self.headers())
-            .send()?
-            .error_for_status()
+        self.send(
+            self.client
+                .get(endpoint.as_full_url(&self.base_url))
+                .query(query)
+                .headers(self.headers()),
+            endpoint.cache_control(),
+        )
+        .await
     }
 
-    pub fn get<Endpoint: HttpEndpoint>(
+    pub async fn get<Endpoint: HttpEndpoint>(
         &self,
         endpoint: Endpoint,
     ) -> Result<Response, reqwest::Error> {
-        self.client
-            .get(endpoint.as_full_url())
-            .headers(self.headers())
-            .send()?
-            .error_for_status()
+        self.send(
+            self.client
+                .get(endpoint.as_full_url(&self.base_url))
+                .headers(self.headers()),
+            endpoint.cache_control(),
+        )
+        .await
     }
 
-    pub fn post<Json: Serialize, Endpoint: HttpEndpoint>(
+    pub async fn post<Json: Serialize, Endpoint: HttpEndpoint>(
         &self,
         endpoint: Endpoint,
         json: &Json,
     ) -> Result<Response, reqwest::Error> {
-        self.client
-            .post(endpoint.as_full_url())
-            .headers(self.headers())
-            .json(json)
-            .send()?
-            .error_for_status()
+        self.send(
+            self.client
+                .post(endpoint.as_full_url(&self.base_url))
+                .headers(self.headers())
+                .json(json),
+            endpoint.cache_control(),
+        )
+        .await
+    }
+
+    /// Fetches `endpoint` and parses its body with `parse`, storing the parsed value in `cache`
+    /// rather than the response in the shared response cache. Stale values are revalidated with
+    /// the upstream, and only fetched and parsed again if they changed. Concurrent lookups of the
+    /// same URL share a single refresh, and parsing runs on a blocking thread.
+    pub async fn get_parsed<Endpoint, T, E, Parse>(
+        &self,
+        endpoint: Endpoint,
+        cache: &ParsedCache<T>,
+        parse: Parse,
+    ) -> Result<Arc<T>, E>
+    where
+        Endpoint: HttpEndpoint,
+        T: Send + 'static,
+        E: From<reqwest::Error> + Send + 'static,
+        Parse: FnOnce(&[u8]) -> Result<T, E> + Send + 'static,
+    {
+        let url = endpoint.as_full_url(&self.base_url);
+        let ttl = endpoint.cache_control().max_age().unwrap_or_default();
+        let slot = cache.slot(&url);
+        if let Some(value) = slot.fresh() {
+            tracing::debug!("Cache hit for parsed {}", url);
+            return Ok(value);
+        }
+        let _refresh = slot.refresh.lock().await;
+        if let Some(value) = slot.fresh() {
+            return Ok(value);
+        }
+
+        let mut request = self.client.get(&url).headers(self.headers()).build()?;
+        for (condition, value) in slot.conditions() {
+            request.headers_mut().insert(condition, value);
+        }
+        let response = self.execute(request).await?;
+        record_last_modified(response.headers());
+        if response.status() == StatusCode::NOT_MODIFIED {
+            if let Some(value) = slot.revalidated(ttl) {
+                tracing::debug!("Revalidated parsed {}", url);
+                return Ok(value);
+            }
+        }
+        let response = response.error_for_status()?;
+        let headers = response.headers().clone();
+        let body = response.bytes().await?;
+        let value = match tokio::task::spawn_blocking(move || parse(&body)).await {
+            Ok(value) => Arc::new(value?),
+            Err(err) => std::panic::resume_unwind(err.into_panic()),
+        };
+        slot.insert(ParsedEntry::new(value.clone(), &headers, ttl));
+        Ok(value)
+    }
+
+    async fn execute(&self, request: Request) -> Result<Response, reqwest::Error> {
+        let mut attempt = 1;
+        loop {
+            if let Some(throttle) = &self.throttle {
+                if let Err(wait) = throttle.wait(self.max_throttle_wait).await {
+                    tracing::warn!(
+                        "Not queueing {} {} for {:?} behind earlier requests",
+                        request.method(),
+                        request.url(),
+                        wait
+                    );
+                    return Ok(throttled(wait));
+                }
+            }
+            // Bodies are always buffered, so requests can be cloned.
+            let Some(retry) = request.try_clone() else {
+                return self.client.execute(request).await;
+            };
+            let outcome = self.client.execute(retry).await;
+            if let (Ok(response), Some(hook)) = (&outcome, &self.response_hook) {
+                hook(response);
+            }
+            let Some(delay) = self.retry_policy.retry_delay(attempt, &outcome) else {
+                return outcome;
+            };
+            match &outcome {
+                Ok(response) => tracing::warn!(
+                    "{} {} responded with {}, retrying in {:?}",
+                    request.method(),
+                    request.url(),
+                    response.status(),
+                    delay
+                ),
+                Err(err) => tracing::warn!(
+                    "{} {} failed: {}, retrying in {:?}",
+                    request.method(),
+                    request.url(),
+                    err,
+                    delay
+                ),
+            }
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    async fn send(
+        &self,
+        request: RequestBuilder,
+        cache_control: CacheControl,
+    ) -> Result<Response, reqwest::Error> {
+        let request = request.build()?;
+        let (Some(cache), Some(ttl)) = (self.cache.as_ref(), cache_control.max_age()) else {
+            let response = self.execute(request).await?;
+            if self.error_responses && is_error(&response) {
+                return Ok(response);
+            }
+            let response = response.error_for_status()?;
+            record_last_modified(response.headers());
+            return Ok(response);
+        };
+
+        // Requests are keyed by method, URL and body, e.g. to tell GraphQL queries apart.
+        let mut key = format!("{} {}", request.method(), request.url());
+        if let Some(body) = request.body().and_then(|body| body.as_bytes()) {
+            key.push(' ');
+            key.push_str(&String::from_utf8_lossy(body));
+        }
+        let stored = cache_get(cache, &key).await;
+        if let Some(stored) = stored.as_ref().filter(|stored| !stored.is_expired()) {
+            tracing::debug!("Cache hit for {}", key);
+            let response: Response = stored.clone().into();
+            record_last_modified(response.headers());
+            return Ok(response);
+        }
+
+        // Revalidate stale responses instead of refetching them in full.
+        let mut request = request;
+        if let Some(stored) = stored.as_ref() {
+            for (validator, condition) in VALIDATORS {
+                if let Some(value) = stored
+                    .header(&validator)
+                    .and_then(|value| HeaderValue::from_str(value).ok())
+                {
+                    request.headers_mut().insert(condition, value);
+                }
+            }
+        }
+
+        let response = self.execute(request).await?;
+        if self.error_responses && is_error(&response) {
+            return Ok(response);
+        }
+        let cached = match stored {
+            Some(stored) if response.status() == StatusCode::NOT_MODIFIED => {
+                tracing::debug!("Revalidated {}", key);
+                stored.revalidated(response.headers(), ttl)
+            }
+            _ => {
+                let response = response.error_for_status()?;
+                let status = response.status().as_u16();
+                let headers = response.headers().clone();
+                CachedResponse::new(status, &headers, response.bytes().await?.to_vec(), ttl)
+            }
+        };
+        cache_insert(cache, &key, cached.clone()).await;
+        let response: Response = cached.into();
+        record_last_modified(response.headers());
+        Ok(response)
+    }
+}
+
+/// Looks `key` up in `cache` on a blocking thread, see [`Cache`].
+async fn cache_get(cache: &Arc<dyn Cache>, key: &str) -> Option<CachedResponse> {
+    let (cache, key) = (cache.clone(), key.to_owned());
+    match tokio::task::spawn_blocking(move || cache.get(&key)).await {
+        Ok(stored) => stored,
+        Err(err) => std::panic::resume_unwind(err.into_panic()),
+    }
+}
+
+/// Stores `response` in `cache` on a blocking thread, see [`Cache`].
+async fn cache_insert(cache: &Arc<dyn Cache>, key: &str, response: CachedResponse) {
+    let (cache, key) = (cache.clone(), key.to_owned());
+    if let Err(err) = tokio::task::spawn_blocking(move || cache.insert(&key, response)).await {
+        std::panic::resume_unwind(err.into_panic());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{num::NonZeroUsize, time::Duration};
+
+    use reqwest::header::{ETAG, LAST_MODIFIED};
+
+    use crate::http::{cache::MemoryCache, test_server::serve};
+
+    use super::*;
+
+    struct TestEndpoint;
+
+    impl HttpEndpoint for TestEndpoint {
+        fn as_full_url(&self, base_url: &str) -> String {
+            format!("{}/package", base_url)
+        }
+
+        fn cache_control(&self) -> CacheControl {
+            CacheControl::PublicShort
+        }
+    }
+
+    #[tokio::test]
+    async fn should_revalidate_stale_responses() {
+        let (base_url, server) = serve(vec![
+            "HTTP/1.1 304 Not Modified\r\netag: \"abc\"\r\nconnection: close\r\n\r\n",
+        ]);
+        let cache = Arc::new(MemoryCache::new(NonZeroUsize::new(1024).unwrap()));
+        let mut headers = HeaderMap::new();
+        headers.insert(ETAG, "\"abc\"".parse().unwrap());
+        headers.insert(
+            LAST_MODIFIED,
+            "Tue, 11 Apr 2023 10:00:00 GMT".parse().unwrap(),
+        );
+        let key = format!("GET {}/package", base_url);
+        cache.insert(
+            &key,
+            CachedResponse::new(200, &headers, b"{}".to_vec(), Duration::ZERO),
+        );
+
+        let client = Client::new(base_url, None).with_cache(Some(cache.clone()));
+        let response = client.get(TestEndpoint).await.unwrap();
+        assert_eq!(StatusCode::OK, response.status());
+        assert_eq!("{}", response.text().await.unwrap());
+
+        let request = &server.join().unwrap()[0];
+        assert!(request.contains("if-none-match: \"abc\"\r\n"));
+        assert!(request.contains("if-modified-since: tue, 11 apr 2023 10:00:00 gmt\r\n"));
+        assert!(!cache.get(&key).unwrap().is_expired());
+    }
+
+    #[tokio::test]
+    async fn should_not_queue_throttled_requests_for_long() {
+        let (base_url, server) = serve(vec![
+            "HTTP/1.1 200 OK\r\ncontent-length: 2\r\nconnection: close\r\n\r\n{}",
+        ]);
+        let client = Client::new(base_url.clone(), None)
+            .with_cache(None)
+            .with_min_request_interval(Duration::from_secs(3600));
+        client.get(TestEndpoint).await.unwrap();
+
+        let request = client.client.get(format!("{}/package", base_url)).build();
+        let response = client.execute(request.unwrap()).await.unwrap();
+        assert_eq!(StatusCode::SERVICE_UNAVAILABLE, response.status());
+        let retry_after: u64 = response.headers()[RETRY_AFTER]
+            .to_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!(retry_after > 3590 && retry_after <= 3600);
+        let err = client.get(TestEndpoint).await.unwrap_err();
+        assert_eq!(Some(StatusCode::SERVICE_UNAVAILABLE), err.status());
+        assert_eq!(1, server.join().unwrap().len());
+    }
+
+    #[tokio::test]
+    async fn should_return_error_responses_if_asked_to() {
+        let (base_url, server) = serve(vec![
+            "HTTP/1.1 403 Forbidden\r\ncontent-length: 10\r\nconnection: close\r\n\r\nrate limit",
+            "HTTP/1.1 200 OK\r\ncontent-length: 2\r\nconnection: close\r\n\r\n{}",
+        ]);
+        let cache = Arc::new(MemoryCache::new(NonZeroUsize::new(1024).unwrap()));
+        let client = Client::new(base_url, None)
+            .with_cache(Some(cache))
+            .with_retry_policy(RetryPolicy::none())
+            .with_error_responses();
+        let response = client.get(TestEndpoint).await.unwrap();
+        assert_eq!(StatusCode::FORBIDDEN, response.status());
+        assert_eq!("rate limit", response.text().await.unwrap());
+        // Error responses aren't cached.
+        let response = client.get(TestEndpoint).await.unwrap();
+        assert_eq!("{}", response.text().await.unwrap());
+        assert_eq!(2, server.join().unwrap().len());
+    }
+
+    #[tokio::test]
+    async fn should_revalidate_parsed_values() {
+        let (base_url, server) = serve(vec![
+            "HTTP/1.1 200 OK\r\netag: \"abc\"\r\ncontent-length: 2\r\nconnection: close\r\n\r\n42",
+            "HTTP/1.1 304 Not Modified\r\netag: \"abc\"\r\nconnection: close\r\n\r\n",
+        ]);
+        let cache = ParsedCache::new();
+        let client = Client::new(base_url, None);
+        let parse = |body: &[u8]| -> Result<u32, reqwest::Error> {
+            Ok(String::from_utf8_lossy(body).parse().unwrap())
+        };
+        assert_eq!(
+            42,
+            *client
+                .get_parsed(TestEndpoint, &cache, parse)
+                .await
+                .unwrap()
+        );
+
+        // Expire the value, and make sure it isn't parsed again once revalidated.
+        let slot = cache.slot(&TestEndpoint.as_full_url(&client.base_url));
+        if let Some(entry) = slot.entry.lock().unwrap().as_mut() {
+            entry.revalidated(Duration::ZERO);
+        }
+        let value = client
+            .get_parsed(TestEndpoint, &cache, |_| -> Result<u32, reqwest::Error> {
+                unreachable!()
+            })
+            .await;
+        assert_eq!(42, *value.unwrap());
+
+        let requests = server.join().unwrap();
+        assert!(!requests[0].contains("if-none-match"));
+        assert!(requests[1].contains("if-none-match: \"abc\"\r\n"));
+    }
+
+    #[tokio::test]
+    async fn should_share_refreshes_of_parsed_values() {
+        let (base_url, server) = serve(vec![
+            "HTTP/1.1 200 OK\r\ncontent-length: 2\r\nconnection: close\r\n\r\n42",
+        ]);
+        let cache = ParsedCache::new();
+        let client = Client::new(base_url, None);
+        let parse = |body: &[u8]| -> Result<u32, reqwest::Error> {
+            std::thread::sleep(Duration::from_millis(50));
+            Ok(String::from_utf8_lossy(body).parse().unwrap())
+        };
+        let (a, b) = tokio::join!(
+            client.get_parsed(TestEndpoint, &cache, parse),
+            client.get_parsed(TestEndpoint, &cache, parse)
+        );
+        assert_eq!((42, 42), (*a.unwrap(), *b.unwrap()));
+        assert_eq!(1, server.join().unwrap().len());
+    }
+
+    #[tokio::test]
+    async fn should_retry_transient_errors() {
+        let (base_url, server) = serve(vec![
+            "HTTP/1.1 503 Service Unavailable\r\nretry-after: 0\r\nconnection: close\r\n\r\n",
+            "HTTP/1.1 502 Bad Gateway\r\ncontent-length: 0\r\nconnection: close\r\n\r\n",
+            "HTTP/1.1 200 OK\r\ncontent-length: 2\r\nconnection: close\r\n\r\n{}",
+        ]);
+        let client = Client::new(base_url, None)
+            .with_cache(None)
+            .with_retry_policy(RetryPolicy {
+                max_attempts: 3,
+                base_delay: Duration::from_millis(1),
+                ..RetryPolicy::default()
+            });
+        let response = client.get(TestEndpoint).await.unwrap();
+        assert_eq!("{}", response.text().await.unwrap());
+        assert_eq!(3, server.join().unwrap().len());
+    }
+
+    #[tokio::test]
+    async fn should_give_up_after_max_attempts() {
+        let (base_url, server) = serve(vec![
+            "HTTP/1.1 503 Service Unavailable\r\ncontent-length: 0\r\nconnection: close\r\n\r\n",
+        ]);
+        let client = Client::new(base_url, None)
+            .with_cache(None)
+            .with_retry_policy(RetryPolicy::none());
+        let err = client.get(TestEndpoint).await.unwrap_err();
+        assert_eq!(Some(StatusCode::SERVICE_UNAVAILABLE), err.status());
+        assert_eq!(1, server.join().unwrap().len());
+    }
+
+    #[test]
+    fn should_fall_back_to_default_base_url() {
+        let default = "https://registry.npmjs.com";
+        assert_eq!(
+            "http://localhost:4873",
+            base_url_or(Some("http://localhost:4873/".to_owned()), default)
+        );
+        assert_eq!(default, base_url_or(Some(" ".to_owned()), default));
+        assert_eq!(default, base_url_or(None, default));
     }
 }
@@ -1 +1,14 @@
+pub mod cache;
 pub mod client;
+pub mod retry;
+#[cfg(test)]
+pub(crate) mod test_server;
+pub mod throttle;
+pub mod validators;
+
+/// FNV-1a hash of `bytes`, which unlike std's hasher is stable across builds and processes.
+pub(crate) fn stable_hash(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325_u64, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3)
+    })
+}
@@ -0,0 +1,298 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use reqwest::{
+    header::{HeaderMap, RETRY_AFTER},
+    Response, StatusCode,
+};
+
+/// Determines whether and when failed upstream requests are retried.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first one.
+    pub max_attempts: u32,
+    /// Delay before the first retry, doubled for every subsequent one.
+    pub base_delay: Duration,
+    /// Upper bound for any delay. Requests asked to wait longer than this (e.g. until a rate limit
+    /// resets) are not retried.
+    pub max_delay: Duration,
+    /// Whether to randomize delays, so concurrent requests don't retry in lockstep.
+    pub jitter: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(250),
+            max_delay: Duration::from_secs(10),
+            jitter: true,
+        }
+    }
+}
+
+fn parse_var<T: std::str::FromStr>(var: impl Fn(&str) -> Option<String>, key: &str) -> Option<T> {
+    var(key).and_then(|value| value.parse().ok())
+}
+
+impl RetryPolicy {
+    /// Never retries.
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// Reads the policy from `HTTP_RETRY_MAX_ATTEMPTS`, `HTTP_RETRY_BASE_DELAY_MS` and
+    /// `HTTP_RETRY_MAX_DELAY_MS`, falling back to the defaults.
+    pub fn from_env() -> Self {
+        Self::from_vars(|key| std::env::var(key).ok())
+    }
+
+    /// Like [`RetryPolicy::from_env`], but looks the variables up with `var`.
+    pub fn from_vars(var: impl Fn(&str) -> Option<String>) -> Self {
+        let default = Self::default();
+        Self {
+            max_attempts: parse_var(&var, "HTTP_RETRY_MAX_ATTEMPTS")
+                .unwrap_or(default.max_attempts),
+            base_delay: parse_var(&var, "HTTP_RETRY_BASE_DELAY_MS")
+                .map(Duration::from_millis)
+                .unwrap_or(default.base_delay),
+            max_delay: parse_var(&var, "HTTP_RETRY_MAX_DELAY_MS")
+                .map(Duration::from_millis)
+                .unwrap_or(default.max_delay),
+            ..default
+        }
+    }
+
+    fn backoff(&self, attempt: u32) -> Duration {
+        let delay = self
+            .base_delay
+            .saturating_mul(2_u32.saturating_pow(attempt.saturating_sub(1)))
+            .min(self.max_delay);
+        if self.jitter {
+            // "Equal jitter": wait at least half of the delay.
+            delay / 2 + delay.mul_f64(fastrand::f64() / 2.0)
+        } else {
+            delay
+        }
+    }
+
+    /// Returns how long to wait before retrying after `attempt` (starting at 1) failed with
+    /// `outcome`, or `None` if it should not be retried.
+    pub fn retry_delay(
+        &self,
+        attempt: u32,
+        outcome: &Result<Response, reqwest::Error>,
+    ) -> Option<Duration> {
+        if attempt >= self.max_attempts {
+            return None;
+        }
+        match outcome {
+            Ok(response) => self.response_delay(attempt, response.status(), response.headers()),
+            Err(err) if err.is_connect() || err.is_timeout() => Some(self.backoff(attempt)),
+            Err(_) => None,
+        }
+    }
+
+    fn response_delay(
+        &self,
+        attempt: u32,
+        status: StatusCode,
+        headers: &HeaderMap,
+    ) -> Option<Duration> {
+        let is_rate_limited = status == StatusCode::TOO_MANY_REQUESTS
+            // GitHub answers exceeded (secondary) rate limits with 403.
+            || (status == StatusCode::FORBIDDEN
+                && (headers.contains_key(RETRY_AFTER)
+                    || header::<u64>(headers, "x-ratelimit-remaining") == Some(0)));
+        let is_transient = matches!(
+            status,
+            StatusCode::INTERNAL_SERVER_ERROR
+                | StatusCode::BAD_GATEWAY
+                | StatusCode::SERVICE_UNAVAILABLE
+                | StatusCode::GATEWAY_TIMEOUT
+        );
+        if !is_rate_limited && !is_transient {
+            return None;
+        }
+
+        match requested_delay(headers) {
+            Some(delay) if delay > self.max_delay => None,
+            Some(delay) => Some(delay),
+            None => Some(self.backoff(attempt)),
+        }
+    }
+}
+
+fn header<T: std::str::FromStr>(headers: &HeaderMap, name: &str) -> Option<T> {
+    headers.get(name)?.to_str().ok()?.trim().parse().ok()
+}
+
+fn until(time: SystemTime) -> Duration {
+    time.duration_since(SystemTime::now())
+        .unwrap_or(Duration::ZERO)
+}
+
+/// The delay requested by the upstream via `Retry-After`, in seconds or as an HTTP date.
+pub fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let retry_after = headers.get(RETRY_AFTER)?.to_str().ok()?;
+    match retry_after.trim().parse() {
+        Ok(seconds) => Some(Duration::from_secs(seconds)),
+        Err(_) => httpdate::parse_http_date(retry_after).ok().map(until),
+    }
+}
+
+/// The delay requested by the upstream via `Retry-After` or `X-RateLimit-Reset` (as seconds since
+/// the UNIX epoch).
+fn requested_delay(headers: &HeaderMap) -> Option<Duration> {
+    if headers.contains_key(RETRY_AFTER) {
+        return retry_after(headers);
+    }
+    header(headers, "x-ratelimit-reset").map(|reset| until(UNIX_EPOCH + Duration::from_secs(reset)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn policy() -> RetryPolicy {
+        RetryPolicy {
+            jitter: false,
+            ..RetryPolicy::default()
+        }
+    }
+
+    fn headers(pairs: &[(&'static str, String)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.insert(*name, value.parse().unwrap());
+        }
+        headers
+    }
+
+    #[test]
+    fn should_back_off_exponentially() {
+        let policy = policy();
+        assert_eq!(Duration::from_millis(250), policy.backoff(1));
+        assert_eq!(Duration::from_millis(500), policy.backoff(2));
+        assert_eq!(Duration::from_millis(1000), policy.backoff(3));
+        assert_eq!(Duration::from_secs(10), policy.backoff(10));
+        assert_eq!(Duration::from_secs(10), policy.backoff(100));
+    }
+
+    #[test]
+    fn should_apply_jitter() {
+        let policy = RetryPolicy::default();
+        for _ in 0..100 {
+            let delay = policy.backoff(2);
+            assert!(delay >= Duration::from_millis(250));
+            assert!(delay <= Duration::from_millis(500));
+        }
+    }
+
+    #[test]
+    fn should_only_retry_transient_errors() {
+        let policy = policy();
+        let none = HeaderMap::new();
+        for status in [
+            StatusCode::INTERNAL_SERVER_ERROR,
+            StatusCode::BAD_GATEWAY,
+            StatusCode::SERVICE_UNAVAILABLE,
+            StatusCode::GATEWAY_TIMEOUT,
+            StatusCode::TOO_MANY_REQUESTS,
+        ] {
+            assert_eq!(
+                Some(Duration::from_millis(250)),
+                policy.response_delay(1, status, &none)
+            );
+        }
+        for status in [
+            StatusCode::OK,
+            StatusCode::NOT_MODIFIED,
+            StatusCode::NOT_FOUND,
+            StatusCode::FORBIDDEN,
+            StatusCode::NOT_IMPLEMENTED,
+        ] {
+            assert_eq!(None, policy.response_delay(1, status, &none));
+        }
+    }
+
+    #[test]
+    fn should_respect_retry_after() {
+        let policy = policy();
+        assert_eq!(
+            Some(Duration::from_secs(3)),
+            policy.response_delay(
+                1,
+                StatusCode::FORBIDDEN,
+                &headers(&[("retry-after", "3".to_owned())])
+            )
+        );
+        let in_five_minutes = httpdate::fmt_http_date(SystemTime::now() + Duration::from_secs(300));
+        assert_eq!(
+            None,
+            policy.response_delay(
+                1,
+                StatusCode::SERVICE_UNAVAILABLE,
+                &headers(&[("retry-after", in_five_minutes)])
+            )
+        );
+        let past = httpdate::fmt_http_date(SystemTime::now() - Duration::from_secs(300));
+        assert_eq!(
+            Some(Duration::ZERO),
+            policy.response_delay(
+                1,
+                StatusCode::SERVICE_UNAVAILABLE,
+                &headers(&[("retry-after", past)])
+            )
+        );
+    }
+
+    #[test]
+    fn should_respect_rate_limit_reset() {
+        let policy = policy();
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+        let delay = policy
+            .response_delay(
+                1,
+                StatusCode::FORBIDDEN,
+                &headers(&[
+                    ("x-ratelimit-remaining", "0".to_owned()),
+                    ("x-ratelimit-reset", (now.as_secs() + 5).to_string()),
+                ]),
+            )
+            .unwrap();
+        assert!(delay <= Duration::from_secs(5) && delay >= Duration::from_secs(4));
+        assert_eq!(
+            None,
+            policy.response_delay(
+                1,
+                StatusCode::FORBIDDEN,
+                &headers(&[
+                    ("x-ratelimit-remaining", "0".to_owned()),
+                    ("x-ratelimit-reset", (now.as_secs() + 3600).to_string()),
+                ]),
+            )
+        );
+    }
+
+    #[test]
+    fn should_read_policy_from_vars() {
+        let vars = HashMap::from([
+            ("HTTP_RETRY_MAX_ATTEMPTS", "5"),
+            ("HTTP_RETRY_BASE_DELAY_MS", "100"),
+            ("HTTP_RETRY_MAX_DELAY_MS", "soon"),
+        ]);
+        assert_eq!(
+            RetryPolicy {
+                max_attempts: 5,
+                base_delay: Duration::from_millis(100),
+                ..RetryPolicy::default()
+            },
+            RetryPolicy::from_vars(|key| vars.get(key).map(|value| value.to_string()))
+        );
+    }
+}
@@ -0,0 +1,42 @@
+//! A stub upstream for unit tests of the HTTP clients.
+
+use std::{
+    io::{Read, Write},
+    net::TcpListener,
+    thread::JoinHandle,
+};
+
+/// Answers one request per response, in order, returning the raw requests.
+pub fn serve<R: AsRef<str> + Send + 'static>(
+    responses: Vec<R>,
+) -> (String, JoinHandle<Vec<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let handle = std::thread::spawn(move || {
+        let mut requests = vec![];
+        for response in responses {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = vec![];
+            let mut buf = [0; 1024];
+            loop {
+                let read = stream.read(&mut buf).unwrap();
+                request.extend_from_slice(&buf[..read]);
+                let text = String::from_utf8_lossy(&request).to_lowercase();
+                if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                    let content_length = head
+                        .lines()
+                        .find_map(|line| line.strip_prefix("content-length: "))
+                        .and_then(|length| length.parse().ok())
+                        .unwrap_or(0);
+                    if body.len() >= content_length {
+                        break;
+                    }
+                }
+            }
+            stream.write_all(response.as_ref().as_bytes()).unwrap();
+            requests.push(String::from_utf8(request).unwrap().to_lowercase());
+        }
+        requests
+    });
+    (base_url, handle)
+}
@@ -0,0 +1,108 @@
+//! Spacing of requests to upstreams that ask clients to limit their request rate, e.g. crates.io's
+//! crawler policy allows at most one request per second.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, OnceLock},
+    time::Duration,
+};
+
+use tokio::time::Instant;
+
+/// Spaces requests at least `min_interval` apart.
+#[derive(Debug)]
+pub struct Throttle {
+    min_interval: Duration,
+    /// When the next request may be sent.
+    next_request: Mutex<Option<Instant>>,
+}
+
+impl Throttle {
+    pub fn new(min_interval: Duration) -> Self {
+        Self {
+            min_interval,
+            next_request: Mutex::new(None),
+        }
+    }
+
+    /// Returns the process-wide throttle for `host`, so that requests are spaced across all clients
+    /// and concurrent lookups, e.g. of a batch request, rather than per client.
+    pub fn shared(host: &str, min_interval: Duration) -> Arc<Self> {
+        static THROTTLES: OnceLock<Mutex<HashMap<String, Arc<Throttle>>>> = OnceLock::new();
+        let mut throttles = THROTTLES
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        throttles
+            .entry(host.to_owned())
+            .or_insert_with(|| Arc::new(Self::new(min_interval)))
+            .clone()
+    }
+
+    /// Waits until a request may be sent, reserving the slot for it. Concurrent callers are
+    /// queued up in the order they call this. If the next free slot is more than `max_wait` away,
+    /// returns how long that is instead, without queueing up.
+    pub async fn wait(&self, max_wait: Duration) -> Result<(), Duration> {
+        let slot = {
+            let mut next_request = self
+                .next_request
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            let now = Instant::now();
+            let slot = next_request.map_or(now, |next_request| next_request.max(now));
+            if slot - now > max_wait {
+                return Err(slot - now);
+            }
+            *next_request = Some(slot + self.min_interval);
+            slot
+        };
+        tokio::time::sleep_until(slot).await;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn should_space_requests() {
+        let throttle = Throttle::new(Duration::from_millis(50));
+        let start = Instant::now();
+        throttle.wait(Duration::MAX).await.unwrap();
+        assert!(start.elapsed() < Duration::from_millis(50));
+        throttle.wait(Duration::MAX).await.unwrap();
+        throttle.wait(Duration::MAX).await.unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(100));
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let start = Instant::now();
+        throttle.wait(Duration::MAX).await.unwrap();
+        assert!(start.elapsed() < Duration::from_millis(50));
+    }
+
+    #[tokio::test]
+    async fn should_not_queue_beyond_max_wait() {
+        let throttle = Throttle::new(Duration::from_secs(1));
+        throttle.wait(Duration::ZERO).await.unwrap();
+        let wait = throttle.wait(Duration::from_millis(500)).await.unwrap_err();
+        assert!(wait > Duration::from_millis(500) && wait <= Duration::from_secs(1));
+        // Requests that were turned away don't reserve a slot.
+        let start = Instant::now();
+        throttle.wait(Duration::from_secs(1)).await.unwrap();
+        assert!(start.elapsed() < Duration::from_millis(1100));
+    }
+
+    #[test]
+    fn should_share_throttles_per_host() {
+        let throttle = Throttle::shared("throttle.test:443", Duration::from_secs(1));
+        assert!(Arc::ptr_eq(
+            &throttle,
+            &Throttle::shared("throttle.test:443", Duration::from_secs(1))
+        ));
+        assert!(!Arc::ptr_eq(
+            &throttle,
+            &Throttle::shared("throttle.test:8443", Duration::from_secs(1))
+        ));
+    }
+}
@@ -0,0 +1,73 @@
+//! Tracks the validators of upstream responses received while handling a request, so they can be
+//! forwarded on the API response.
+
+use std::{cell::Cell, future::Future, time::SystemTime};
+
+use reqwest::header::{HeaderMap, LAST_MODIFIED};
+
+tokio::task_local! {
+    static UPSTREAM_LAST_MODIFIED: Cell<Option<SystemTime>>;
+}
+
+/// Runs `future` to completion, returning its output along with the most recent `Last-Modified`
+/// date of the upstream responses received while running it.
+pub async fn track_last_modified<F: Future>(future: F) -> (F::Output, Option<SystemTime>) {
+    UPSTREAM_LAST_MODIFIED
+        .scope(Cell::new(None), async move {
+            let output = future.await;
+            (output, UPSTREAM_LAST_MODIFIED.with(Cell::get))
+        })
+        .await
+}
+
+/// Records the `Last-Modified` header of an upstream response, if it is being tracked.
+pub fn record_last_modified(headers: &HeaderMap) {
+    let Some(last_modified) = headers
+        .get(LAST_MODIFIED)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| httpdate::parse_http_date(value).ok())
+    else {
+        return;
+    };
+    let _ = UPSTREAM_LAST_MODIFIED.try_with(|latest| {
+        latest.set(latest.get().max(Some(last_modified)));
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn should_track_latest_last_modified() {
+        let headers = |date: &str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(LAST_MODIFIED, date.parse().unwrap());
+            headers
+        };
+        let (output, last_modified) = futures::executor::block_on(track_last_modified(async {
+            record_last_modified(&headers("Tue, 11 Apr 2023 10:00:00 GMT"));
+            record_last_modified(&headers("Tue, 11 Apr 2023 12:00:00 GMT"));
+            record_last_modified(&headers("Tue, 11 Apr 2023 11:00:00 GMT"));
+            record_last_modified(&headers("invalid"));
+            42
+        }));
+        assert_eq!(42, output);
+        assert_eq!(
+            Some(SystemTime::UNIX_EPOCH + Duration::from_secs(1681214400)),
+            last_modified
+        );
+    }
+
+    #[test]
+    fn should_ignore_untracked_responses() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            LAST_MODIFIED,
+            "Tue, 11 Apr 2023 10:00:00 GMT".parse().unwrap(),
+        );
+        record_last_modified(&headers);
+    }
+}
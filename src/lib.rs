@@ -1,21 +1,32 @@
-use std::{collections::HashMap, ops::Deref};
+use std::{collections::HashMap, ops::Deref, time::Duration};
 
 use serde::Serialize;
 use tracing_subscriber::FmtSubscriber;
 
 pub mod badges;
+pub mod batch;
 pub mod crates;
 pub mod errors;
 pub mod github;
 pub mod golang;
+pub mod hackage;
+pub mod handlers;
 pub mod http;
+pub mod luarocks;
+pub mod maven;
 pub mod npm;
+pub mod nuget;
+pub mod opam;
 pub mod openvsx;
 pub mod packagist;
+pub mod purl;
 pub mod pypi;
+pub mod registry;
 pub mod renovate;
 pub mod rubygems;
+pub mod server;
 pub mod vercel;
+pub mod versioning;
 
 pub struct QueryParams(HashMap<String, String>);
 
@@ -25,10 +36,16 @@ impl QueryParams {
     }
 
     pub fn has_flag(&self, query: &str) -> bool {
-        match self.0.get(query).map(Deref::deref) {
-            Some("") | Some("1") | Some("true") => return true,
-            _ => return false,
-        }
+        matches!(
+            self.0.get(query).map(Deref::deref),
+            Some("") | Some("1") | Some("true")
+        )
+    }
+
+    /// Like [`QueryParams::has_flag`], but `None` if the flag is absent, for flags whose default
+    /// depends on the route.
+    pub fn flag(&self, query: &str) -> Option<bool> {
+        self.get(query).map(|_| self.has_flag(query))
     }
 }
 
@@ -58,6 +75,16 @@ impl CacheControl {
             CacheControl::PublicLong => "max-age=0, s-maxage=86400",
         }
     }
+
+    /// How long a response may be served from a shared cache, i.e. its `s-maxage`.
+    pub fn max_age(&self) -> Option<Duration> {
+        match self {
+            CacheControl::NoStore => None,
+            CacheControl::PublicShort => Some(Duration::from_secs(60)),
+            CacheControl::PublicMedium => Some(Duration::from_secs(900)),
+            CacheControl::PublicLong => Some(Duration::from_secs(86400)),
+        }
+    }
 }
 
 #[derive(Serialize)]
@@ -93,5 +120,10 @@ mod tests {
         assert!(query.has_flag("do"));
         assert!(!query.has_flag("do_nothing"));
         assert!(!query.has_flag("not"));
+
+        assert_eq!(Some(true), query.flag("do_something"));
+        assert_eq!(Some(true), query.flag("do"));
+        assert_eq!(Some(false), query.flag("not"));
+        assert_eq!(None, query.flag("do_nothing"));
     }
 }
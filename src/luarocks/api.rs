@@ -0,0 +1,15 @@
+use serde::Serialize;
+
+use crate::registry::PackageVersion;
+
+#[derive(Serialize)]
+pub struct LuaRocksResponse {
+    pub name: String,
+    pub version: String,
+}
+
+impl From<LuaRocksResponse> for PackageVersion {
+    fn from(response: LuaRocksResponse) -> Self {
+        PackageVersion::new(response.name, response.version)
+    }
+}
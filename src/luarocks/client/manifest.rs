@@ -0,0 +1,417 @@
+//! A parser for LuaRocks manifests, which are Lua files assigning tables of strings, numbers and
+//! booleans to global names, e.g.
+//!
+//! ```lua
+//! repository = {
+//!    luacheck = {
+//!       ["0.26.1-1"] = {
+//!          { arch = "rockspec" },
+//!          { arch = "src" }
+//!       }
+//!    }
+//! }
+//! ```
+
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LuaValue {
+    Nil,
+    Boolean(bool),
+    /// Numbers are kept as written, as they're only ever used as keys.
+    Number(String),
+    String(String),
+    Table(LuaTable),
+}
+
+/// A table constructor's fields in order. Positional fields have no key, and keys are kept as
+/// strings, i.e. `["1.0-1"] = x` and `[1] = x` have the keys `1.0-1` and `1`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct LuaTable {
+    pub fields: Vec<(Option<String>, LuaValue)>,
+}
+
+impl LuaTable {
+    pub fn get(&self, key: &str) -> Option<&LuaValue> {
+        self.fields
+            .iter()
+            .find(|(field_key, _)| field_key.as_deref() == Some(key))
+            .map(|(_, value)| value)
+    }
+
+    pub fn get_table(&self, key: &str) -> Option<&LuaTable> {
+        match self.get(key) {
+            Some(LuaValue::Table(table)) => Some(table),
+            Some(_) | None => None,
+        }
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.fields.iter().filter_map(|(key, _)| key.as_deref())
+    }
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Invalid manifest at byte {position}: {message}.")]
+pub struct ManifestError {
+    position: usize,
+    message: &'static str,
+}
+
+struct Parser<'a> {
+    input: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error<T>(&self, message: &'static str) -> Result<T, ManifestError> {
+        Err(ManifestError {
+            position: self.position,
+            message,
+        })
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.get(self.position).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<u8> {
+        self.input.get(self.position + offset).copied()
+    }
+
+    fn expect(&mut self, byte: u8, message: &'static str) -> Result<(), ManifestError> {
+        self.skip_whitespace()?;
+        match self.peek() {
+            Some(next) if next == byte => {
+                self.position += 1;
+                Ok(())
+            }
+            Some(_) | None => self.error(message),
+        }
+    }
+
+    /// Skips whitespace and comments.
+    fn skip_whitespace(&mut self) -> Result<(), ManifestError> {
+        loop {
+            match self.peek() {
+                Some(byte) if byte.is_ascii_whitespace() => self.position += 1,
+                Some(b'-') if self.peek_at(1) == Some(b'-') => {
+                    self.position += 2;
+                    if self.long_bracket_level().is_some() {
+                        self.parse_long_bracket()?;
+                    } else {
+                        while !matches!(self.peek(), Some(b'\n') | None) {
+                            self.position += 1;
+                        }
+                    }
+                }
+                Some(_) | None => return Ok(()),
+            }
+        }
+    }
+
+    /// The level of a long bracket opening at the current position, i.e. the number of `=` in
+    /// `[==[`.
+    fn long_bracket_level(&self) -> Option<usize> {
+        if self.peek() != Some(b'[') {
+            return None;
+        }
+        let level = self.input[self.position + 1..]
+            .iter()
+            .take_while(|byte| **byte == b'=')
+            .count();
+        match self.peek_at(level + 1) {
+            Some(b'[') => Some(level),
+            Some(_) | None => None,
+        }
+    }
+
+    fn parse_long_bracket(&mut self) -> Result<String, ManifestError> {
+        let Some(level) = self.long_bracket_level() else {
+            return self.error("expected long bracket");
+        };
+        self.position += level + 2;
+        // A newline immediately following the opening bracket is skipped.
+        if self.peek() == Some(b'\r') {
+            self.position += 1;
+        }
+        if self.peek() == Some(b'\n') {
+            self.position += 1;
+        }
+        let closing = format!("]{}]", "=".repeat(level));
+        let Some(length) = self.input[self.position..]
+            .windows(closing.len())
+            .position(|window| window == closing.as_bytes())
+        else {
+            return self.error("unterminated long bracket");
+        };
+        let contents = String::from_utf8_lossy(&self.input[self.position..self.position + length])
+            .into_owned();
+        self.position += length + closing.len();
+        Ok(contents)
+    }
+
+    fn parse_name(&mut self) -> Result<&'a str, ManifestError> {
+        let start = self.position;
+        while matches!(self.peek(), Some(byte) if byte.is_ascii_alphanumeric() || byte == b'_') {
+            self.position += 1;
+        }
+        match self.input.get(start) {
+            Some(byte) if byte.is_ascii_alphabetic() || *byte == b'_' => {
+                // Names are ASCII, so they're valid UTF-8.
+                Ok(std::str::from_utf8(&self.input[start..self.position]).unwrap_or_default())
+            }
+            Some(_) | None => self.error("expected name"),
+        }
+    }
+
+    fn parse_string(&mut self, quote: u8) -> Result<String, ManifestError> {
+        self.position += 1;
+        let mut bytes = Vec::new();
+        loop {
+            let Some(byte) = self.peek() else {
+                return self.error("unterminated string");
+            };
+            self.position += 1;
+            match byte {
+                byte if byte == quote => break,
+                b'\n' => return self.error("unterminated string"),
+                b'\\' => {
+                    let Some(escaped) = self.peek() else {
+                        return self.error("unterminated string");
+                    };
+                    self.position += 1;
+                    match escaped {
+                        b'n' | b'\n' => bytes.push(b'\n'),
+                        b't' => bytes.push(b'\t'),
+                        b'r' => bytes.push(b'\r'),
+                        b'a' => bytes.push(0x07),
+                        b'b' => bytes.push(0x08),
+                        b'f' => bytes.push(0x0c),
+                        b'v' => bytes.push(0x0b),
+                        b'z' => {
+                            while matches!(self.peek(), Some(byte) if byte.is_ascii_whitespace()) {
+                                self.position += 1;
+                            }
+                        }
+                        b'0'..=b'9' => {
+                            let start = self.position - 1;
+                            while self.position - start < 3
+                                && matches!(self.peek(), Some(b'0'..=b'9'))
+                            {
+                                self.position += 1;
+                            }
+                            match std::str::from_utf8(&self.input[start..self.position])
+                                .ok()
+                                .and_then(|digits| digits.parse().ok())
+                            {
+                                Some(byte) => bytes.push(byte),
+                                None => return self.error("invalid escape sequence"),
+                            }
+                        }
+                        escaped => bytes.push(escaped),
+                    }
+                }
+                byte => bytes.push(byte),
+            }
+        }
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+
+    fn parse_number(&mut self) -> Result<String, ManifestError> {
+        let start = self.position;
+        if self.peek() == Some(b'-') {
+            self.position += 1;
+        }
+        while let Some(byte) = self.peek() {
+            let is_exponent_sign = matches!(byte, b'+' | b'-')
+                && matches!(self.input[self.position - 1], b'e' | b'E' | b'p' | b'P');
+            if byte.is_ascii_alphanumeric() || byte == b'.' || is_exponent_sign {
+                self.position += 1;
+            } else {
+                break;
+            }
+        }
+        match self.position - start {
+            0 => self.error("expected number"),
+            _ => Ok(String::from_utf8_lossy(&self.input[start..self.position]).into_owned()),
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<LuaValue, ManifestError> {
+        self.skip_whitespace()?;
+        match self.peek() {
+            Some(b'{') => Ok(LuaValue::Table(self.parse_table()?)),
+            Some(quote @ (b'"' | b'\'')) => Ok(LuaValue::String(self.parse_string(quote)?)),
+            Some(b'[') => Ok(LuaValue::String(self.parse_long_bracket()?)),
+            Some(b'0'..=b'9' | b'-' | b'.') => Ok(LuaValue::Number(self.parse_number()?)),
+            Some(_) => match self.parse_name()? {
+                "nil" => Ok(LuaValue::Nil),
+                "true" => Ok(LuaValue::Boolean(true)),
+                "false" => Ok(LuaValue::Boolean(false)),
+                _ => self.error("expected value"),
+            },
+            None => self.error("expected value"),
+        }
+    }
+
+    fn parse_key(&mut self) -> Result<String, ManifestError> {
+        match self.parse_value()? {
+            LuaValue::String(key) | LuaValue::Number(key) => Ok(key),
+            LuaValue::Boolean(key) => Ok(key.to_string()),
+            LuaValue::Nil | LuaValue::Table(_) => self.error("unsupported table key"),
+        }
+    }
+
+    /// Whether a `name =` field follows, as opposed to a positional value.
+    fn is_named_field(&self) -> bool {
+        let mut position = self.position;
+        while matches!(self.input.get(position), Some(byte) if byte.is_ascii_alphanumeric() || *byte == b'_')
+        {
+            position += 1;
+        }
+        while matches!(self.input.get(position), Some(byte) if byte.is_ascii_whitespace()) {
+            position += 1;
+        }
+        position > self.position
+            && self.input.get(position) == Some(&b'=')
+            && self.input.get(position + 1) != Some(&b'=')
+    }
+
+    fn parse_table(&mut self) -> Result<LuaTable, ManifestError> {
+        self.expect(b'{', "expected table")?;
+        let mut table = LuaTable::default();
+        loop {
+            self.skip_whitespace()?;
+            if self.peek() == Some(b'}') {
+                self.position += 1;
+                return Ok(table);
+            }
+
+            let field = if self.peek() == Some(b'[') && self.long_bracket_level().is_none() {
+                self.position += 1;
+                let key = self.parse_key()?;
+                self.expect(b']', "expected ]")?;
+                self.expect(b'=', "expected =")?;
+                (Some(key), self.parse_value()?)
+            } else if self.is_named_field() {
+                let key = self.parse_name()?.to_owned();
+                self.expect(b'=', "expected =")?;
+                (Some(key), self.parse_value()?)
+            } else {
+                (None, self.parse_value()?)
+            };
+            table.fields.push(field);
+
+            self.skip_whitespace()?;
+            match self.peek() {
+                Some(b',' | b';') => self.position += 1,
+                Some(b'}') => {}
+                Some(_) | None => return self.error("expected , or }"),
+            }
+        }
+    }
+}
+
+/// Parses a manifest into a table of its global assignments, e.g. `repository` and `modules`.
+pub fn parse(manifest: &str) -> Result<LuaTable, ManifestError> {
+    let mut parser = Parser {
+        input: manifest.as_bytes(),
+        position: 0,
+    };
+    let mut globals = LuaTable::default();
+    loop {
+        parser.skip_whitespace()?;
+        if parser.peek().is_none() {
+            return Ok(globals);
+        }
+        let name = parser.parse_name()?.to_owned();
+        parser.expect(b'=', "expected =")?;
+        globals.fields.push((Some(name), parser.parse_value()?));
+        parser.skip_whitespace()?;
+        if parser.peek() == Some(b';') {
+            parser.position += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_manifest() {
+        let globals = parse(
+            r#"
+-- A comment.
+commands = {}
+modules = {}
+repository = {
+   luacheck = {
+      ["0.26.1-1"] = {
+         {
+            arch = "rockspec"
+         },
+         {
+            arch = 'src'
+         }
+      },
+      ["1.1.0-1"] = { { arch = "all" } };
+   },
+   ["lua-cjson"] = {
+      ["2.1.0.10-1"] = {
+         {
+            arch = "src", size = 1e3, ["escaped\"key"] = [[long
+string]], flag = true, none = nil
+         }
+      }
+   }
+}
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            vec!["commands", "modules", "repository"],
+            globals.keys().collect::<Vec<_>>()
+        );
+        let repository = globals.get_table("repository").unwrap();
+        assert_eq!(
+            vec!["0.26.1-1", "1.1.0-1"],
+            repository
+                .get_table("luacheck")
+                .unwrap()
+                .keys()
+                .collect::<Vec<_>>()
+        );
+        let rock = match &repository
+            .get_table("lua-cjson")
+            .unwrap()
+            .get_table("2.1.0.10-1")
+            .unwrap()
+            .fields[0]
+        {
+            (None, LuaValue::Table(rock)) => rock.clone(),
+            field => panic!("Unexpected field {field:?}"),
+        };
+        assert_eq!(Some(&LuaValue::Number("1e3".to_owned())), rock.get("size"));
+        assert_eq!(
+            Some(&LuaValue::String("long\nstring".to_owned())),
+            rock.get("escaped\"key")
+        );
+        assert_eq!(Some(&LuaValue::Boolean(true)), rock.get("flag"));
+        assert_eq!(Some(&LuaValue::Nil), rock.get("none"));
+    }
+
+    #[test]
+    fn should_reject_invalid_manifests() {
+        for invalid in [
+            "repository = {",
+            "repository = { luacheck = }",
+            "repository = \"unterminated",
+            "= {}",
+            "repository = { [{}] = 1 }",
+        ] {
+            assert!(parse(invalid).is_err(), "{invalid}");
+        }
+    }
+}
@@ -0,0 +1,127 @@
+pub mod manifest;
+
+use std::{collections::HashMap, fmt::Display};
+
+use crate::{
+    http::{
+        cache::ParsedCache,
+        client::{base_url_from_env, Client, HttpEndpoint},
+    },
+    CacheControl,
+};
+
+use super::{errors::LuaRocksError, LuaRocksPackage};
+
+enum LuaRocksEndpoint {
+    Manifest,
+}
+
+impl HttpEndpoint for LuaRocksEndpoint {
+    fn as_full_url(&self, base_url: &str) -> String {
+        format!("{}/{}", base_url, self)
+    }
+
+    fn cache_control(&self) -> CacheControl {
+        CacheControl::PublicMedium
+    }
+}
+
+impl Display for LuaRocksEndpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LuaRocksEndpoint::Manifest => f.write_str("manifest"),
+        }
+    }
+}
+
+/// The versions of every package in a server's manifest, i.e. its `repository` table.
+type Repository = HashMap<String, Vec<String>>;
+
+/// Manifests are several megabytes large, so they're only parsed once per change.
+static REPOSITORIES: ParsedCache<Repository> = ParsedCache::new();
+
+pub struct LuaRocksClient {
+    client: Client,
+}
+
+impl Default for LuaRocksClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LuaRocksClient {
+    pub const DEFAULT_BASE_URL: &'static str = "https://luarocks.org";
+
+    /// Creates a client for the rocks server configured via `LUAROCKS_URL`, defaulting to
+    /// luarocks.org.
+    pub fn new() -> Self {
+        Self::with_base_url(base_url_from_env("LUAROCKS_URL", Self::DEFAULT_BASE_URL))
+    }
+
+    pub fn with_base_url(base_url: String) -> Self {
+        Self {
+            client: Client::new(base_url, None),
+        }
+    }
+
+    /// Fetches the versions of the package listed in the server's manifest, in no particular
+    /// order.
+    pub async fn fetch_package_versions(
+        &self,
+        package: &LuaRocksPackage,
+    ) -> Result<Vec<String>, LuaRocksError> {
+        let repository = self
+            .client
+            .get_parsed(LuaRocksEndpoint::Manifest, &REPOSITORIES, read_repository)
+            .await?;
+        repository
+            .get(&package.name)
+            .cloned()
+            .ok_or(LuaRocksError::ResourceNotFound { source: None })
+    }
+}
+
+/// Reads the `repository` table of a manifest, which maps every package to a table keyed by its
+/// versions.
+fn read_repository(manifest: &[u8]) -> Result<Repository, LuaRocksError> {
+    let globals = manifest::parse(&String::from_utf8_lossy(manifest))?;
+    let Some(repository) = globals.get_table("repository") else {
+        return Ok(Repository::new());
+    };
+    Ok(repository
+        .fields
+        .iter()
+        .filter_map(|field| match field {
+            (Some(name), manifest::LuaValue::Table(versions)) => Some((
+                name.to_owned(),
+                versions.keys().map(ToOwned::to_owned).collect(),
+            )),
+            _ => None,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_read_repository() {
+        let repository = read_repository(
+            br#"repository = {
+   luacheck = { ["0.26.1-1"] = {}, ["1.1.0-1"] = {} },
+   ["lua-cjson"] = { ["2.1.0.10-1"] = {} },
+   invalid = "not a table"
+}"#,
+        )
+        .unwrap();
+        let mut versions = repository["luacheck"].clone();
+        versions.sort();
+        assert_eq!(vec!["0.26.1-1", "1.1.0-1"], versions);
+        assert_eq!(vec!["2.1.0.10-1"], repository["lua-cjson"]);
+        assert_eq!(2, repository.len());
+        assert!(read_repository(b"commands = {}").unwrap().is_empty());
+        assert!(read_repository(b"repository = {").is_err());
+    }
+}
@@ -0,0 +1,57 @@
+use http::StatusCode;
+use thiserror::Error;
+
+use crate::errors::ApiError;
+
+use super::client::manifest::ManifestError;
+
+#[derive(Error, Debug)]
+pub enum LuaRocksError {
+    #[error("The requested resource was not found when interfacing with the LuaRocks server.")]
+    ResourceNotFound { source: Option<reqwest::Error> },
+    #[error("Client error. {:?}", source.status())]
+    ClientError { source: reqwest::Error },
+    #[error("LuaRocks server had a server error. {:?}", source.status())]
+    ServerError { source: reqwest::Error },
+    #[error("Network error. {:?}", source.status())]
+    NetworkError { source: reqwest::Error },
+    #[error("LuaRocks server responded with an invalid manifest. {source}")]
+    InvalidManifest { source: ManifestError },
+}
+
+impl ApiError for LuaRocksError {
+    fn status_code(&self) -> http::StatusCode {
+        match self {
+            LuaRocksError::ResourceNotFound { .. } => StatusCode::NOT_FOUND,
+            LuaRocksError::ClientError { .. } | LuaRocksError::NetworkError { .. } => {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+            LuaRocksError::ServerError { .. } | LuaRocksError::InvalidManifest { .. } => {
+                StatusCode::BAD_GATEWAY
+            }
+        }
+    }
+}
+
+impl From<reqwest::Error> for LuaRocksError {
+    fn from(req_error: reqwest::Error) -> Self {
+        match req_error.status() {
+            Some(reqwest::StatusCode::NOT_FOUND) => Self::ResourceNotFound {
+                source: Some(req_error),
+            },
+            Some(status_code) if status_code.is_server_error() => {
+                Self::ServerError { source: req_error }
+            }
+            Some(status_code) if status_code.is_client_error() => {
+                Self::ClientError { source: req_error }
+            }
+            Some(_) | None => Self::NetworkError { source: req_error },
+        }
+    }
+}
+
+impl From<ManifestError> for LuaRocksError {
+    fn from(source: ManifestError) -> Self {
+        Self::InvalidManifest { source }
+    }
+}
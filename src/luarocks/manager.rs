@@ -0,0 +1,90 @@
+use crate::{
+    registry::{PackageVersion, Registry},
+    versioning::luarocks,
+};
+
+use super::{
+    api::LuaRocksResponse, client::LuaRocksClient, errors::LuaRocksError, LuaRocksPackage,
+};
+
+pub struct LuaRocksManager {
+    client: LuaRocksClient,
+}
+
+impl LuaRocksManager {
+    pub fn new(client: LuaRocksClient) -> Self {
+        Self { client }
+    }
+
+    /// Returns all package versions in DESCENDING order. Pre-releases and development versions
+    /// are only included if requested.
+    pub async fn get_all_package_versions(
+        &self,
+        package: &LuaRocksPackage,
+    ) -> Result<Vec<String>, LuaRocksError> {
+        let mut versions = self.client.fetch_package_versions(package).await?;
+        if !package.include_prerelease.unwrap_or(false) {
+            versions.retain(|version| !luarocks::is_prerelease(version));
+        }
+        luarocks::sort_desc(&mut versions);
+        Ok(versions)
+    }
+
+    pub async fn get_latest_package_version(
+        &self,
+        package: &LuaRocksPackage,
+    ) -> Result<LuaRocksResponse, LuaRocksError> {
+        let versions = self.client.fetch_package_versions(package).await?;
+        let latest = luarocks::latest(
+            versions.iter().map(String::as_str),
+            package.include_prerelease.unwrap_or(false),
+        )
+        .ok_or(LuaRocksError::ResourceNotFound { source: None })?;
+        Ok(LuaRocksResponse {
+            name: package.name.clone(),
+            version: latest.to_owned(),
+        })
+    }
+
+    pub async fn get_package_version(
+        &self,
+        package: &LuaRocksPackage,
+        version: &str,
+    ) -> Result<LuaRocksResponse, LuaRocksError> {
+        let versions = self.client.fetch_package_versions(package).await?;
+        match versions.iter().any(|v| v == version) {
+            true => Ok(LuaRocksResponse {
+                name: package.name.clone(),
+                version: version.to_owned(),
+            }),
+            false => Err(LuaRocksError::ResourceNotFound { source: None }),
+        }
+    }
+}
+
+impl Registry for LuaRocksManager {
+    type Package = LuaRocksPackage;
+    type Error = LuaRocksError;
+
+    async fn get_all_versions(
+        &self,
+        package: &LuaRocksPackage,
+    ) -> Result<Vec<String>, LuaRocksError> {
+        self.get_all_package_versions(package).await
+    }
+
+    async fn get_latest_version(
+        &self,
+        package: &LuaRocksPackage,
+    ) -> Result<PackageVersion, LuaRocksError> {
+        Ok(self.get_latest_package_version(package).await?.into())
+    }
+
+    async fn get_version(
+        &self,
+        package: &LuaRocksPackage,
+        version: &str,
+    ) -> Result<PackageVersion, LuaRocksError> {
+        Ok(self.get_package_version(package, version).await?.into())
+    }
+}
@@ -0,0 +1,35 @@
+use crate::QueryParams;
+
+pub mod api;
+pub mod client;
+pub mod errors;
+pub mod manager;
+
+#[derive(Debug)]
+pub struct LuaRocksPackage {
+    pub name: String,
+    /// Whether to include pre-releases and development versions, e.g. `1.0rc1-1` and `scm-1`.
+    /// Unless set, they are left out.
+    pub include_prerelease: Option<bool>,
+}
+
+impl LuaRocksPackage {
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            include_prerelease: None,
+        }
+    }
+}
+
+impl From<&QueryParams> for LuaRocksPackage {
+    fn from(query: &QueryParams) -> Self {
+        Self {
+            name: query
+                .get("package")
+                .expect("No [package] query param")
+                .to_owned(),
+            include_prerelease: query.flag("include_prerelease"),
+        }
+    }
+}
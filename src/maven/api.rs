@@ -0,0 +1,26 @@
+use serde::Serialize;
+
+use crate::registry::PackageVersion;
+
+use super::client::spec::MavenMetadataDto;
+
+#[derive(Serialize)]
+pub struct MavenResponse {
+    pub name: String,
+    pub version: String,
+}
+
+impl MavenResponse {
+    pub fn from_maven_metadata_dto(version: String, dto: &MavenMetadataDto) -> Self {
+        Self {
+            name: format!("{}:{}", dto.group_id, dto.artifact_id),
+            version,
+        }
+    }
+}
+
+impl From<MavenResponse> for PackageVersion {
+    fn from(response: MavenResponse) -> Self {
+        PackageVersion::new(response.name, response.version)
+    }
+}
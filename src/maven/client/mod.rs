@@ -0,0 +1,111 @@
+pub mod spec;
+
+use std::fmt::Display;
+
+use crate::{
+    http::client::{base_url_from_env, Client, HttpEndpoint},
+    CacheControl,
+};
+
+use self::spec::MavenMetadataDto;
+
+use super::{errors::MavenError, MavenPackage};
+
+enum MavenEndpoint<'a> {
+    Metadata(&'a MavenPackage),
+}
+
+impl<'a> HttpEndpoint for MavenEndpoint<'a> {
+    fn as_full_url(&self, base_url: &str) -> String {
+        format!("{}/{}", base_url, self)
+    }
+
+    fn cache_control(&self) -> CacheControl {
+        CacheControl::PublicMedium
+    }
+}
+
+impl<'a> Display for MavenEndpoint<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MavenEndpoint::Metadata(pkg) => {
+                f.write_fmt(format_args!("{}/maven-metadata.xml", pkg.path()))
+            }
+        }
+    }
+}
+
+pub struct MavenClient {
+    client: Client,
+}
+
+impl Default for MavenClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MavenClient {
+    pub const DEFAULT_BASE_URL: &'static str = "https://repo.maven.apache.org/maven2";
+
+    /// Creates a client for the repository configured via `MAVEN_REPOSITORY_URL`, defaulting to
+    /// Maven Central.
+    pub fn new() -> Self {
+        Self::with_base_url(base_url_from_env(
+            "MAVEN_REPOSITORY_URL",
+            Self::DEFAULT_BASE_URL,
+        ))
+    }
+
+    pub fn with_base_url(base_url: String) -> Self {
+        Self {
+            client: Client::new(base_url, None),
+        }
+    }
+
+    pub async fn fetch_metadata(
+        &self,
+        package: &MavenPackage,
+    ) -> Result<MavenMetadataDto, MavenError> {
+        let metadata = self
+            .client
+            .get(MavenEndpoint::Metadata(package))
+            .await?
+            .text()
+            .await?;
+        Ok(quick_xml::de::from_str(&metadata)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_deserialize_metadata() {
+        let metadata: MavenMetadataDto = quick_xml::de::from_str(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<metadata>
+  <groupId>com.google.guava</groupId>
+  <artifactId>guava</artifactId>
+  <versioning>
+    <latest>33.0.0-jre</latest>
+    <release>33.0.0-jre</release>
+    <versions>
+      <version>32.1.3-jre</version>
+      <version>33.0.0-jre</version>
+    </versions>
+    <lastUpdated>20231218183422</lastUpdated>
+  </versioning>
+</metadata>"#,
+        )
+        .unwrap();
+        assert_eq!("com.google.guava", metadata.group_id);
+        assert_eq!("guava", metadata.artifact_id);
+        assert_eq!(
+            vec!["32.1.3-jre", "33.0.0-jre"],
+            metadata.versioning.versions.version
+        );
+        assert_eq!(Some("33.0.0-jre".to_owned()), metadata.versioning.release);
+    }
+}
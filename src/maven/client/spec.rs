@@ -0,0 +1,27 @@
+use serde::Deserialize;
+
+/// A `maven-metadata.xml` document, listing the versions of an artifact.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MavenMetadataDto {
+    pub group_id: String,
+    pub artifact_id: String,
+    #[serde(default)]
+    pub versioning: MavenVersioningDto,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct MavenVersioningDto {
+    pub latest: Option<String>,
+    pub release: Option<String>,
+    #[serde(default)]
+    pub versions: MavenVersionsDto,
+    pub last_updated: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+pub struct MavenVersionsDto {
+    #[serde(default)]
+    pub version: Vec<String>,
+}
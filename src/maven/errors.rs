@@ -0,0 +1,58 @@
+use http::StatusCode;
+use thiserror::Error;
+
+use crate::errors::ApiError;
+
+#[derive(Error, Debug)]
+pub enum MavenError {
+    #[error("The requested resource was not found when interfacing with the Maven repository.")]
+    ResourceNotFound { source: Option<reqwest::Error> },
+    #[error("Client error. {:?}", source.status())]
+    ClientError { source: reqwest::Error },
+    #[error("Maven repository had a server error. {:?}", source.status())]
+    ServerError { source: reqwest::Error },
+    #[error("Network error. {:?}", source.status())]
+    NetworkError { source: reqwest::Error },
+    #[error("Maven repository responded with invalid metadata. {source}")]
+    InvalidMetadata { source: quick_xml::DeError },
+    #[error("Invalid Maven package {package:?}.")]
+    InvalidPackage { package: String },
+}
+
+impl ApiError for MavenError {
+    fn status_code(&self) -> http::StatusCode {
+        match self {
+            MavenError::ResourceNotFound { .. } => StatusCode::NOT_FOUND,
+            MavenError::ClientError { .. } | MavenError::NetworkError { .. } => {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+            MavenError::ServerError { .. } | MavenError::InvalidMetadata { .. } => {
+                StatusCode::BAD_GATEWAY
+            }
+            MavenError::InvalidPackage { .. } => StatusCode::BAD_REQUEST,
+        }
+    }
+}
+
+impl From<reqwest::Error> for MavenError {
+    fn from(req_error: reqwest::Error) -> Self {
+        match req_error.status() {
+            Some(reqwest::StatusCode::NOT_FOUND) => Self::ResourceNotFound {
+                source: Some(req_error),
+            },
+            Some(status_code) if status_code.is_server_error() => {
+                Self::ServerError { source: req_error }
+            }
+            Some(status_code) if status_code.is_client_error() => {
+                Self::ClientError { source: req_error }
+            }
+            Some(_) | None => Self::NetworkError { source: req_error },
+        }
+    }
+}
+
+impl From<quick_xml::DeError> for MavenError {
+    fn from(source: quick_xml::DeError) -> Self {
+        Self::InvalidMetadata { source }
+    }
+}
@@ -0,0 +1,117 @@
+use crate::{
+    registry::{PackageVersion, Registry},
+    versioning::maven,
+};
+
+use super::{
+    api::MavenResponse,
+    client::{spec::MavenMetadataDto, MavenClient},
+    errors::MavenError,
+    MavenPackage,
+};
+
+pub struct MavenManager {
+    client: MavenClient,
+}
+
+impl MavenManager {
+    pub fn new(client: MavenClient) -> Self {
+        Self { client }
+    }
+
+    pub async fn get_metadata(
+        &self,
+        package: &MavenPackage,
+    ) -> Result<MavenMetadataDto, MavenError> {
+        self.client.fetch_metadata(package).await
+    }
+
+    /// Returns all package versions in DESCENDING order, as ordered by Maven. Pre-releases, such
+    /// as milestones and snapshots, are only included if requested.
+    pub async fn get_all_package_versions(
+        &self,
+        package: &MavenPackage,
+    ) -> Result<Vec<String>, MavenError> {
+        let mut versions: Vec<String> = self
+            .get_metadata(package)
+            .await?
+            .versioning
+            .versions
+            .version
+            .into_iter()
+            .filter(|version| {
+                package.include_prerelease.unwrap_or(false) || !maven::is_prerelease(version)
+            })
+            .collect();
+        maven::sort_desc(&mut versions);
+        Ok(versions)
+    }
+
+    /// Returns the greatest version. The metadata's own `<release>` isn't used, as it's merely
+    /// the most recently deployed release, which may be a pre-release or a backport.
+    pub async fn get_latest_package_version(
+        &self,
+        package: &MavenPackage,
+    ) -> Result<MavenResponse, MavenError> {
+        let metadata = self.get_metadata(package).await?;
+        let latest = maven::latest(
+            metadata
+                .versioning
+                .versions
+                .version
+                .iter()
+                .map(String::as_str),
+            package.include_prerelease.unwrap_or(false),
+        )
+        .ok_or(MavenError::ResourceNotFound { source: None })?;
+        Ok(MavenResponse::from_maven_metadata_dto(
+            latest.to_owned(),
+            &metadata,
+        ))
+    }
+
+    pub async fn get_package_version(
+        &self,
+        package: &MavenPackage,
+        version: &str,
+    ) -> Result<MavenResponse, MavenError> {
+        let metadata = self.get_metadata(package).await?;
+        match metadata
+            .versioning
+            .versions
+            .version
+            .iter()
+            .any(|v| v == version)
+        {
+            true => Ok(MavenResponse::from_maven_metadata_dto(
+                version.to_owned(),
+                &metadata,
+            )),
+            false => Err(MavenError::ResourceNotFound { source: None }),
+        }
+    }
+}
+
+impl Registry for MavenManager {
+    type Package = MavenPackage;
+    type Error = MavenError;
+
+    async fn get_all_versions(&self, package: &MavenPackage) -> Result<Vec<String>, MavenError> {
+        self.get_all_package_versions(package).await
+    }
+
+    async fn get_latest_version(
+        &self,
+        package: &MavenPackage,
+    ) -> Result<PackageVersion, MavenError> {
+        Ok(self.get_latest_package_version(package).await?.into())
+    }
+
+    async fn get_version(
+        &self,
+        package: &MavenPackage,
+        version: &str,
+    ) -> Result<PackageVersion, MavenError> {
+        Ok(self.get_package_version(package, version).await?.into())
+    }
+}
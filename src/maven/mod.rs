@@ -0,0 +1,101 @@
+use std::fmt::Display;
+
+use crate::{registry::is_path_safe_id, QueryParams};
+
+use self::errors::MavenError;
+
+pub mod api;
+pub mod client;
+pub mod errors;
+pub mod manager;
+
+#[derive(Debug)]
+pub struct MavenPackage {
+    pub group_id: String,
+    pub artifact_id: String,
+    /// Whether to include pre-releases, e.g. `2.0.0-M1` and `1.0-SNAPSHOT`. Unless set,
+    /// they are left out.
+    pub include_prerelease: Option<bool>,
+}
+
+impl MavenPackage {
+    pub fn new(group_id: String, artifact_id: String) -> Self {
+        Self {
+            group_id,
+            artifact_id,
+            include_prerelease: None,
+        }
+    }
+
+    /// Whether the group and artifact ids are valid, as they're mapped to repository paths.
+    pub fn is_valid(&self) -> bool {
+        is_path_safe_id(&self.group_id) && is_path_safe_id(&self.artifact_id)
+    }
+
+    /// The directory of the artifact in a repository, e.g. `org/apache/maven/maven-core`.
+    pub fn path(&self) -> String {
+        format!("{}/{}", self.group_id.replace('.', "/"), self.artifact_id)
+    }
+}
+
+impl TryFrom<&QueryParams> for MavenPackage {
+    type Error = MavenError;
+
+    fn try_from(query: &QueryParams) -> Result<Self, Self::Error> {
+        let package = Self {
+            include_prerelease: query.flag("include_prerelease"),
+            ..Self::new(
+                query
+                    .get("group")
+                    .expect("No [group] query param")
+                    .to_owned(),
+                query
+                    .get("artifact")
+                    .expect("No [artifact] query param")
+                    .to_owned(),
+            )
+        };
+        match package.is_valid() {
+            true => Ok(package),
+            false => Err(MavenError::InvalidPackage {
+                package: package.to_string(),
+            }),
+        }
+    }
+}
+
+impl Display for MavenPackage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{}:{}", self.group_id, self.artifact_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_map_package_to_path() {
+        let package = MavenPackage::new("org.apache.maven".to_owned(), "maven-core".to_owned());
+        assert!(package.is_valid());
+        assert_eq!("org/apache/maven/maven-core", package.path());
+        assert_eq!("org.apache.maven:maven-core", package.to_string());
+    }
+
+    #[test]
+    fn should_reject_invalid_packages() {
+        for (group_id, artifact_id) in [
+            ("", "maven-core"),
+            ("org.apache.maven", ""),
+            ("..", "maven-core"),
+            ("org..maven", "maven-core"),
+            ("org.apache.maven", "../maven-core"),
+            ("org/apache", "maven-core"),
+        ] {
+            assert!(
+                !MavenPackage::new(group_id.to_owned(), artifact_id.to_owned()).is_valid(),
+                "{group_id}:{artifact_id}"
+            );
+        }
+    }
+}
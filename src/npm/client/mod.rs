@@ -4,7 +4,10 @@ use std::fmt::Display;
 
 use reqwest::header::{HeaderMap, ACCEPT};
 
-use crate::http::client::{Client, HttpEndpoint};
+use crate::{
+    http::client::{base_url_from_env, Client, HttpEndpoint},
+    CacheControl,
+};
 
 use self::spec::NpmAbbrevPackageDto;
 
@@ -15,8 +18,12 @@ enum NpmEndpoint<'a> {
 }
 
 impl<'a> HttpEndpoint for NpmEndpoint<'a> {
-    fn as_full_url(&self) -> String {
-        format!("https://registry.npmjs.com/{}", self)
+    fn as_full_url(&self, base_url: &str) -> String {
+        format!("{}/{}", base_url, self)
+    }
+
+    fn cache_control(&self) -> CacheControl {
+        CacheControl::PublicMedium
     }
 }
 
@@ -24,10 +31,13 @@ impl<'a> Display for NpmEndpoint<'a> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             NpmEndpoint::Package(pkg) => match pkg {
-                NpmPackage { scope: None, name } => f.write_fmt(format_args!("{}", name)),
+                NpmPackage {
+                    scope: None, name, ..
+                } => f.write_fmt(format_args!("{}", name)),
                 NpmPackage {
                     scope: Some(scope),
                     name,
+                    ..
                 } => f.write_fmt(format_args!("{}/{}", scope, name)),
             },
         }
@@ -38,8 +48,25 @@ pub struct NpmClient {
     client: Client,
 }
 
+impl Default for NpmClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl NpmClient {
+    pub const DEFAULT_BASE_URL: &'static str = "https://registry.npmjs.com";
+
+    /// Creates a client for the registry configured via `NPM_REGISTRY_URL`, defaulting to the
+    /// public npm registry.
     pub fn new() -> Self {
+        Self::with_base_url(base_url_from_env(
+            "NPM_REGISTRY_URL",
+            Self::DEFAULT_BASE_URL,
+        ))
+    }
+
+    pub fn with_base_url(base_url: String) -> Self {
         let mut headers = HeaderMap::new();
         headers.insert(
             ACCEPT,
@@ -48,14 +75,18 @@ impl NpmClient {
                 .unwrap(),
         );
         NpmClient {
-            client: Client::new(Some(headers)),
+            client: Client::new(base_url, Some(headers)),
         }
     }
 
-    pub fn fetch_package(
+    pub async fn fetch_package(
         &self,
         package: &NpmPackage,
     ) -> Result<NpmAbbrevPackageDto, reqwest::Error> {
-        self.client.get(NpmEndpoint::Package(package))?.json()
+        self.client
+            .get(NpmEndpoint::Package(package))
+            .await?
+            .json()
+            .await
     }
 }
@@ -1,4 +1,7 @@
-use std::cmp::Ordering;
+use crate::{
+    registry::{PackageVersion, Registry},
+    versioning::semver,
+};
 
 use super::{
     client::{
@@ -9,15 +12,6 @@ use super::{
     NpmPackage,
 };
 
-fn semver_sort_desc(a: &String, b: &String) -> Ordering {
-    let a_semver = a.parse::<semver::Version>();
-    let b_semver = b.parse::<semver::Version>();
-    if let (Ok(a), Ok(b)) = (a_semver, b_semver) {
-        return b.cmp(&a);
-    }
-    Ordering::Equal
-}
-
 pub struct NpmManager {
     client: NpmClient,
 }
@@ -27,8 +21,8 @@ impl NpmManager {
         Self { client }
     }
 
-    pub fn get_package(&self, package: &NpmPackage) -> Result<NpmAbbrevPackageDto, NpmError> {
-        Ok(self.client.fetch_package(&package)?)
+    pub async fn get_package(&self, package: &NpmPackage) -> Result<NpmAbbrevPackageDto, NpmError> {
+        Ok(self.client.fetch_package(package).await?)
     }
 
     pub fn get_package_version<'a>(
@@ -42,27 +36,79 @@ impl NpmManager {
             .ok_or_else(|| NpmError::ResourceNotFound { source: None })
     }
 
+    /// Returns the version the `latest` dist-tag points to or, with `include_prerelease`, a newer
+    /// pre-release if there is one.
     pub fn get_latest_package_version<'a>(
         &self,
         package: &'a NpmAbbrevPackageDto,
+        include_prerelease: bool,
     ) -> Result<&'a NpmAbbrevPackageVersionDto, NpmError> {
         let latest_version = package
             .dist_tags
             .get(&NpmDistTag::Latest)
             .ok_or_else(|| NpmError::ResourceNotFound { source: None })?;
+        let prereleases = package
+            .versions
+            .keys()
+            .filter(|version| include_prerelease && semver::is_prerelease(version));
+        let latest_version = semver::latest(
+            prereleases.chain([latest_version]).map(String::as_str),
+            true,
+        )
+        .unwrap_or(latest_version);
         self.get_package_version(package, latest_version)
     }
 
-    /// Returns all package versions in DESCENDING order.
-    pub fn get_all_package_versions(&self, package: &NpmPackage) -> Result<Vec<String>, NpmError> {
-        let npm_package = self.get_package(package)?;
-        let mut versions: Vec<String> = npm_package.versions.into_keys().collect();
+    /// Returns all package versions in DESCENDING order. Pre-releases are included unless opted
+    /// out of.
+    pub async fn get_all_package_versions(
+        &self,
+        package: &NpmPackage,
+    ) -> Result<Vec<String>, NpmError> {
+        let npm_package = self.get_package(package).await?;
+        let include_prerelease = package.include_prerelease.unwrap_or(true);
+        let mut versions: Vec<String> = npm_package
+            .versions
+            .into_keys()
+            .filter(|version| include_prerelease || !semver::is_prerelease(version))
+            .collect();
         // https://github.com/npm/cli/blob/32336f6efe06bd52de1dc67c0f812d4705533ef2/lib/commands/view.js#L54
-        versions.sort_by(semver_sort_desc);
+        semver::sort_desc(&mut versions);
         Ok(versions)
     }
 }
 
+impl From<&NpmAbbrevPackageVersionDto> for PackageVersion {
+    fn from(dto: &NpmAbbrevPackageVersionDto) -> Self {
+        PackageVersion::new(dto.name.clone(), dto.version.clone())
+    }
+}
+
+impl Registry for NpmManager {
+    type Package = NpmPackage;
+    type Error = NpmError;
+
+    async fn get_all_versions(&self, package: &NpmPackage) -> Result<Vec<String>, NpmError> {
+        self.get_all_package_versions(package).await
+    }
+
+    async fn get_latest_version(&self, package: &NpmPackage) -> Result<PackageVersion, NpmError> {
+        let npm_package = self.get_package(package).await?;
+        Ok(self
+            .get_latest_package_version(&npm_package, package.include_prerelease.unwrap_or(false))?
+            .into())
+    }
+
+    async fn get_version(
+        &self,
+        package: &NpmPackage,
+        version: &str,
+    ) -> Result<PackageVersion, NpmError> {
+        let npm_package = self.get_package(package).await?;
+        Ok(self.get_package_version(&npm_package, version)?.into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
@@ -84,7 +130,7 @@ mod tests {
         .into_iter()
         .map(ToOwned::to_owned)
         .collect();
-        input.sort_by(semver_sort_desc);
+        semver::sort_desc(&mut input);
         assert_eq!(
             vec![
                 "3.10.0",
@@ -109,16 +155,27 @@ mod tests {
                 (NpmDistTag::Next, "14.0.0-pre.1".to_owned()),
                 (NpmDistTag::Latest, "13.3.7".to_owned()),
             ]),
-            versions: HashMap::from([(
-                "13.3.7".to_owned(),
-                NpmAbbrevPackageVersionDto {
-                    name: "foobar".to_owned(),
-                    version: "13.3.7".to_owned(),
-                },
-            )]),
+            versions: HashMap::from([
+                (
+                    "13.3.7".to_owned(),
+                    NpmAbbrevPackageVersionDto {
+                        name: "foobar".to_owned(),
+                        version: "13.3.7".to_owned(),
+                    },
+                ),
+                (
+                    "14.0.0-pre.1".to_owned(),
+                    NpmAbbrevPackageVersionDto {
+                        name: "foobar".to_owned(),
+                        version: "14.0.0-pre.1".to_owned(),
+                    },
+                ),
+            ]),
         };
-        let latest_version = manager.get_latest_package_version(&package)?;
+        let latest_version = manager.get_latest_package_version(&package, false)?;
         assert_eq!("13.3.7".to_owned(), latest_version.version);
+        let latest_version = manager.get_latest_package_version(&package, true)?;
+        assert_eq!("14.0.0-pre.1".to_owned(), latest_version.version);
         Ok(())
     }
 }
@@ -8,18 +8,34 @@ pub mod manager;
 pub struct NpmPackage {
     pub scope: Option<String>,
     pub name: String,
+    /// Whether to include pre-releases. Unless set, `versions/all` lists them but
+    /// `versions/latest` doesn't resolve to them.
+    pub include_prerelease: Option<bool>,
+}
+
+impl NpmPackage {
+    pub fn new(scope: Option<String>, name: String) -> Self {
+        Self {
+            scope,
+            name,
+            include_prerelease: None,
+        }
+    }
 }
 
 impl From<&QueryParams> for NpmPackage {
     fn from(query: &QueryParams) -> Self {
+        let include_prerelease = query.flag("include_prerelease");
         match (query.get("scope"), query.get("package")) {
             (Some(scope), Some(name)) if *scope == "_" => Self {
                 scope: None,
                 name: name.to_owned(),
+                include_prerelease,
             },
             (Some(scope), Some(name)) => Self {
                 scope: Some(scope.to_owned()),
                 name: name.to_owned(),
+                include_prerelease,
             },
             (Some(_), None) | (None, None) | (None, Some(_)) => {
                 panic!("Failed to parse npm package from URL.")
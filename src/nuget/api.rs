@@ -0,0 +1,26 @@
+use serde::Serialize;
+
+use crate::registry::PackageVersion;
+
+use super::client::spec::NuGetCatalogEntryDto;
+
+#[derive(Serialize)]
+pub struct NuGetResponse {
+    pub name: String,
+    pub version: String,
+}
+
+impl NuGetResponse {
+    pub fn from_nuget_catalog_entry_dto(dto: &NuGetCatalogEntryDto) -> Self {
+        Self {
+            name: dto.id.clone(),
+            version: dto.version.clone(),
+        }
+    }
+}
+
+impl From<NuGetResponse> for PackageVersion {
+    fn from(response: NuGetResponse) -> Self {
+        PackageVersion::new(response.name, response.version)
+    }
+}
@@ -0,0 +1,191 @@
+pub mod spec;
+
+use std::fmt::Display;
+
+use futures::{stream, StreamExt, TryStreamExt};
+
+use crate::{
+    http::client::{base_url_from_env, Client, HttpEndpoint},
+    CacheControl,
+};
+
+use self::spec::{
+    NuGetCatalogEntryDto, NuGetRegistrationIndexDto, NuGetRegistrationPageDto, NuGetServiceIndexDto,
+};
+
+use super::{errors::NuGetError, NuGetPackage};
+
+enum NuGetEndpoint<'a> {
+    ServiceIndex,
+    /// A resource URL, as advertised by the service index or linked from another resource.
+    Resource(&'a str),
+}
+
+impl<'a> HttpEndpoint for NuGetEndpoint<'a> {
+    fn as_full_url(&self, base_url: &str) -> String {
+        match self {
+            NuGetEndpoint::Resource(url) => (*url).to_owned(),
+            endpoint => format!("{}/{}", base_url, endpoint),
+        }
+    }
+
+    fn cache_control(&self) -> CacheControl {
+        match self {
+            NuGetEndpoint::ServiceIndex => CacheControl::PublicLong,
+            NuGetEndpoint::Resource(_) => CacheControl::PublicMedium,
+        }
+    }
+}
+
+impl<'a> Display for NuGetEndpoint<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NuGetEndpoint::ServiceIndex => f.write_str("v3/index.json"),
+            NuGetEndpoint::Resource(url) => f.write_str(url),
+        }
+    }
+}
+
+pub struct NuGetClient {
+    client: Client,
+}
+
+impl Default for NuGetClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NuGetClient {
+    pub const DEFAULT_BASE_URL: &'static str = "https://api.nuget.org";
+    pub const MAX_CONCURRENT_PAGES: usize = 4;
+
+    /// Registration resources in order of preference. Only `3.6.0` and up include SemVer 2.0
+    /// versions.
+    const REGISTRATION_RESOURCE_TYPES: &'static [&'static str] = &[
+        "RegistrationsBaseUrl/3.6.0",
+        "RegistrationsBaseUrl/Versioned",
+        "RegistrationsBaseUrl/3.4.0",
+        "RegistrationsBaseUrl/3.0.0-rc",
+        "RegistrationsBaseUrl",
+    ];
+
+    /// Creates a client for the NuGet v3 source configured via `NUGET_URL`, whose service index
+    /// is at `{NUGET_URL}/v3/index.json`, defaulting to nuget.org.
+    pub fn new() -> Self {
+        Self::with_base_url(base_url_from_env("NUGET_URL", Self::DEFAULT_BASE_URL))
+    }
+
+    pub fn with_base_url(base_url: String) -> Self {
+        Self {
+            client: Client::new(base_url, None),
+        }
+    }
+
+    async fn fetch_registrations_base_url(&self) -> Result<String, NuGetError> {
+        let service_index: NuGetServiceIndexDto = self
+            .client
+            .get(NuGetEndpoint::ServiceIndex)
+            .await?
+            .json()
+            .await?;
+        Self::REGISTRATION_RESOURCE_TYPES
+            .iter()
+            .find_map(|resource_type| {
+                service_index
+                    .resources
+                    .iter()
+                    .find(|resource| resource.resource_type == *resource_type)
+            })
+            .map(|resource| resource.id.trim_end_matches('/').to_owned())
+            .ok_or(NuGetError::MissingResource {
+                resource_type: "RegistrationsBaseUrl",
+            })
+    }
+
+    /// Fetches the catalog entries of all versions of the package, including unlisted ones.
+    pub async fn fetch_catalog_entries(
+        &self,
+        package: &NuGetPackage,
+    ) -> Result<Vec<NuGetCatalogEntryDto>, NuGetError> {
+        let registration_url = format!(
+            "{}/{}/index.json",
+            self.fetch_registrations_base_url().await?,
+            package.lowercase_id()
+        );
+        let registration_index: NuGetRegistrationIndexDto = self
+            .client
+            .get(NuGetEndpoint::Resource(&registration_url))
+            .await?
+            .json()
+            .await?;
+
+        let pages: Vec<NuGetRegistrationPageDto> = stream::iter(registration_index.items)
+            .map(|page| async move {
+                match page.items {
+                    Some(_) => Ok(page),
+                    None => self.fetch_registration_page(&page.id).await,
+                }
+            })
+            .buffered(Self::MAX_CONCURRENT_PAGES)
+            .try_collect()
+            .await?;
+        Ok(pages
+            .into_iter()
+            .flat_map(|page| page.items.unwrap_or_default())
+            .map(|leaf| leaf.catalog_entry)
+            .collect())
+    }
+
+    async fn fetch_registration_page(
+        &self,
+        url: &str,
+    ) -> Result<NuGetRegistrationPageDto, NuGetError> {
+        Ok(self
+            .client
+            .get(NuGetEndpoint::Resource(url))
+            .await?
+            .json()
+            .await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::spec::NuGetRegistrationIndexDto;
+
+    #[test]
+    fn should_deserialize_registration_index() {
+        let index: NuGetRegistrationIndexDto = serde_json::from_str(
+            r#"{
+                "count": 2,
+                "items": [
+                    {
+                        "@id": "https://api.nuget.org/v3/registration5-gz-semver2/csharpier/page/0.1.0/0.9.0.json",
+                        "count": 1,
+                        "lower": "0.1.0",
+                        "upper": "0.9.0"
+                    },
+                    {
+                        "@id": "https://api.nuget.org/v3/registration5-gz-semver2/csharpier/index.json#page/0.26.7/0.26.7",
+                        "count": 1,
+                        "items": [
+                            {
+                                "catalogEntry": {
+                                    "id": "CSharpier",
+                                    "version": "0.26.7",
+                                    "listed": false
+                                }
+                            }
+                        ]
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+        assert!(index.items[0].items.is_none());
+        let entries = index.items[1].items.as_ref().unwrap();
+        assert_eq!("CSharpier", entries[0].catalog_entry.id);
+        assert!(!entries[0].catalog_entry.listed);
+    }
+}
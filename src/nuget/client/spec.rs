@@ -0,0 +1,49 @@
+use serde::Deserialize;
+
+/// The service index, listing the resources provided by a NuGet v3 source.
+#[derive(Deserialize)]
+pub struct NuGetServiceIndexDto {
+    pub resources: Vec<NuGetServiceResourceDto>,
+}
+
+#[derive(Deserialize)]
+pub struct NuGetServiceResourceDto {
+    #[serde(rename = "@id")]
+    pub id: String,
+    #[serde(rename = "@type")]
+    pub resource_type: String,
+}
+
+/// The registration index of a package. Packages with many versions have their versions split
+/// into pages, which are only inlined for smaller packages.
+#[derive(Deserialize)]
+pub struct NuGetRegistrationIndexDto {
+    pub items: Vec<NuGetRegistrationPageDto>,
+}
+
+#[derive(Deserialize)]
+pub struct NuGetRegistrationPageDto {
+    #[serde(rename = "@id")]
+    pub id: String,
+    pub items: Option<Vec<NuGetRegistrationLeafDto>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NuGetRegistrationLeafDto {
+    pub catalog_entry: NuGetCatalogEntryDto,
+}
+
+fn listed_default() -> bool {
+    true
+}
+
+#[derive(Deserialize)]
+pub struct NuGetCatalogEntryDto {
+    pub id: String,
+    pub version: String,
+    /// Unlisted versions can still be installed, but are hidden from search and version
+    /// resolution. Versions are listed unless stated otherwise.
+    #[serde(default = "listed_default")]
+    pub listed: bool,
+}
@@ -0,0 +1,52 @@
+use http::StatusCode;
+use thiserror::Error;
+
+use crate::errors::ApiError;
+
+#[derive(Error, Debug)]
+pub enum NuGetError {
+    #[error("The requested resource was not found when interfacing with the NuGet API.")]
+    ResourceNotFound { source: Option<reqwest::Error> },
+    #[error("Client error. {:?}", source.status())]
+    ClientError { source: reqwest::Error },
+    #[error("NuGet API had a server error. {:?}", source.status())]
+    ServerError { source: reqwest::Error },
+    #[error("Network error. {:?}", source.status())]
+    NetworkError { source: reqwest::Error },
+    #[error("The NuGet service index doesn't provide a {resource_type} resource.")]
+    MissingResource { resource_type: &'static str },
+    #[error("Invalid NuGet package id {package:?}.")]
+    InvalidPackage { package: String },
+}
+
+impl ApiError for NuGetError {
+    fn status_code(&self) -> http::StatusCode {
+        match self {
+            NuGetError::ResourceNotFound { .. } => StatusCode::NOT_FOUND,
+            NuGetError::ClientError { .. } | NuGetError::NetworkError { .. } => {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+            NuGetError::ServerError { .. } | NuGetError::MissingResource { .. } => {
+                StatusCode::BAD_GATEWAY
+            }
+            NuGetError::InvalidPackage { .. } => StatusCode::BAD_REQUEST,
+        }
+    }
+}
+
+impl From<reqwest::Error> for NuGetError {
+    fn from(req_error: reqwest::Error) -> Self {
+        match req_error.status() {
+            Some(reqwest::StatusCode::NOT_FOUND) => Self::ResourceNotFound {
+                source: Some(req_error),
+            },
+            Some(status_code) if status_code.is_server_error() => {
+                Self::ServerError { source: req_error }
+            }
+            Some(status_code) if status_code.is_client_error() => {
+                Self::ClientError { source: req_error }
+            }
+            Some(_) | None => Self::NetworkError { source: req_error },
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use crate::{
+    registry::{PackageVersion, Registry},
+    versioning::nuget,
+};
+
+use super::{
+    api::NuGetResponse,
+    client::{spec::NuGetCatalogEntryDto, NuGetClient},
+    errors::NuGetError,
+    NuGetPackage,
+};
+
+pub struct NuGetManager {
+    client: NuGetClient,
+}
+
+impl NuGetManager {
+    pub fn new(client: NuGetClient) -> Self {
+        Self { client }
+    }
+
+    pub async fn get_catalog_entries(
+        &self,
+        package: &NuGetPackage,
+    ) -> Result<Vec<NuGetCatalogEntryDto>, NuGetError> {
+        let entries = self.client.fetch_catalog_entries(package).await?;
+        match entries.is_empty() {
+            true => Err(NuGetError::ResourceNotFound { source: None }),
+            false => Ok(entries),
+        }
+    }
+
+    /// Returns all package versions in DESCENDING order. Pre-releases and unlisted versions are
+    /// each only included if requested.
+    pub async fn get_all_package_versions(
+        &self,
+        package: &NuGetPackage,
+    ) -> Result<Vec<String>, NuGetError> {
+        let mut versions: Vec<String> = self
+            .get_catalog_entries(package)
+            .await?
+            .into_iter()
+            .filter(|entry| {
+                package.include_prerelease.unwrap_or(false) || !nuget::is_prerelease(&entry.version)
+            })
+            .filter(|entry| package.include_yanked.unwrap_or(false) || entry.listed)
+            .map(|entry| entry.version)
+            .collect();
+        nuget::sort_desc(&mut versions);
+        Ok(versions)
+    }
+
+    pub async fn get_latest_package_version(
+        &self,
+        package: &NuGetPackage,
+    ) -> Result<NuGetResponse, NuGetError> {
+        let entries = self.get_catalog_entries(package).await?;
+        let latest = nuget::latest(
+            entries
+                .iter()
+                // Like `dotnet tool install`, never resolve to an unlisted version.
+                .filter(|entry| entry.listed)
+                .map(|entry| entry.version.as_str()),
+            package.include_prerelease.unwrap_or(false),
+        )
+        .ok_or(NuGetError::ResourceNotFound { source: None })?;
+        entries
+            .iter()
+            .find(|entry| entry.version == latest)
+            .map(NuGetResponse::from_nuget_catalog_entry_dto)
+            .ok_or(NuGetError::ResourceNotFound { source: None })
+    }
+
+    /// Returns the given version, which may be unlisted. Versions are matched like NuGet does,
+    /// e.g. `1.0` matches `1.0.0`.
+    pub async fn get_package_version(
+        &self,
+        package: &NuGetPackage,
+        version: &str,
+    ) -> Result<NuGetResponse, NuGetError> {
+        self.get_catalog_entries(package)
+            .await?
+            .iter()
+            .find(|entry| nuget::eq(&entry.version, version))
+            .map(NuGetResponse::from_nuget_catalog_entry_dto)
+            .ok_or(NuGetError::ResourceNotFound { source: None })
+    }
+}
+
+impl Registry for NuGetManager {
+    type Package = NuGetPackage;
+    type Error = NuGetError;
+
+    async fn get_all_versions(&self, package: &NuGetPackage) -> Result<Vec<String>, NuGetError> {
+        self.get_all_package_versions(package).await
+    }
+
+    async fn get_latest_version(
+        &self,
+        package: &NuGetPackage,
+    ) -> Result<PackageVersion, NuGetError> {
+        Ok(self.get_latest_package_version(package).await?.into())
+    }
+
+    async fn get_version(
+        &self,
+        package: &NuGetPackage,
+        version: &str,
+    ) -> Result<PackageVersion, NuGetError> {
+        Ok(self.get_package_version(package, version).await?.into())
+    }
+}
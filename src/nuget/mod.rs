@@ -0,0 +1,91 @@
+use std::fmt::Display;
+
+use crate::{registry::is_path_safe_id, QueryParams};
+
+use self::errors::NuGetError;
+
+pub mod api;
+pub mod client;
+pub mod errors;
+pub mod manager;
+
+#[derive(Debug)]
+pub struct NuGetPackage {
+    pub id: String,
+    /// Whether to include pre-releases. Unless set, they are left out.
+    pub include_prerelease: Option<bool>,
+    /// Whether to include unlisted versions, NuGet's equivalent of yanked versions. Unless set,
+    /// they are left out.
+    pub include_yanked: Option<bool>,
+}
+
+impl NuGetPackage {
+    pub fn new(id: String) -> Self {
+        Self {
+            id,
+            include_prerelease: None,
+            include_yanked: None,
+        }
+    }
+
+    /// Whether the id is a valid package id, as it's mapped to resource paths.
+    pub fn is_valid(&self) -> bool {
+        is_path_safe_id(&self.id)
+    }
+
+    /// Package ids are case-insensitive, and lowercased in resource URLs.
+    pub fn lowercase_id(&self) -> String {
+        self.id.to_lowercase()
+    }
+}
+
+impl TryFrom<&QueryParams> for NuGetPackage {
+    type Error = NuGetError;
+
+    fn try_from(query: &QueryParams) -> Result<Self, Self::Error> {
+        let package = Self {
+            include_prerelease: query.flag("include_prerelease"),
+            include_yanked: query.flag("include_yanked"),
+            ..Self::new(
+                query
+                    .get("package")
+                    .expect("No [package] query param")
+                    .to_owned(),
+            )
+        };
+        match package.is_valid() {
+            true => Ok(package),
+            false => Err(NuGetError::InvalidPackage {
+                package: package.id,
+            }),
+        }
+    }
+}
+
+impl Display for NuGetPackage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_validate_package_ids() {
+        for valid in ["CSharpier", "Microsoft.Extensions.Logging", "dotnet-format"] {
+            assert!(NuGetPackage::new(valid.to_owned()).is_valid(), "{valid}");
+        }
+        for invalid in ["", ".hidden", "Foo..Bar", "Foo/Bar", "Foo%2FBar"] {
+            assert!(
+                !NuGetPackage::new(invalid.to_owned()).is_valid(),
+                "{invalid}"
+            );
+        }
+        assert_eq!(
+            "csharpier",
+            NuGetPackage::new("CSharpier".to_owned()).lowercase_id()
+        );
+    }
+}
@@ -0,0 +1,15 @@
+use serde::Serialize;
+
+use crate::registry::PackageVersion;
+
+#[derive(Serialize)]
+pub struct OpamResponse {
+    pub name: String,
+    pub version: String,
+}
+
+impl From<OpamResponse> for PackageVersion {
+    fn from(response: OpamResponse) -> Self {
+        PackageVersion::new(response.name, response.version)
+    }
+}
@@ -0,0 +1,176 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    fmt::Display,
+    io::Read,
+};
+
+use flate2::read::GzDecoder;
+
+use crate::{
+    http::{
+        cache::ParsedCache,
+        client::{base_url_from_env, Client, HttpEndpoint},
+    },
+    CacheControl,
+};
+
+use super::{errors::OpamError, OpamPackage};
+
+enum OpamEndpoint {
+    Index,
+}
+
+impl HttpEndpoint for OpamEndpoint {
+    fn as_full_url(&self, base_url: &str) -> String {
+        format!("{}/{}", base_url, self)
+    }
+
+    fn cache_control(&self) -> CacheControl {
+        CacheControl::PublicMedium
+    }
+}
+
+impl Display for OpamEndpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OpamEndpoint::Index => f.write_str("index.tar.gz"),
+        }
+    }
+}
+
+/// The versions of every package in a repository's index.
+type OpamIndex = HashMap<String, BTreeSet<String>>;
+
+/// Indexes are several megabytes large, so they're only read once per change.
+static INDEXES: ParsedCache<OpamIndex> = ParsedCache::new();
+
+pub struct OpamClient {
+    client: Client,
+}
+
+impl Default for OpamClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OpamClient {
+    pub const DEFAULT_BASE_URL: &'static str = "https://opam.ocaml.org";
+    /// The limit for the decompressed index, which is read in full to find the package.
+    pub const MAX_INDEX_SIZE: u64 = 512 * 1024 * 1024;
+
+    /// Creates a client for the opam repository configured via `OPAM_REPOSITORY_URL`, defaulting
+    /// to opam.ocaml.org.
+    pub fn new() -> Self {
+        Self::with_base_url(base_url_from_env(
+            "OPAM_REPOSITORY_URL",
+            Self::DEFAULT_BASE_URL,
+        ))
+    }
+
+    pub fn with_base_url(base_url: String) -> Self {
+        Self {
+            client: Client::new(base_url, None),
+        }
+    }
+
+    /// Fetches the versions of the package listed in the repository's index, in no particular
+    /// order.
+    pub async fn fetch_package_versions(
+        &self,
+        package: &OpamPackage,
+    ) -> Result<Vec<String>, OpamError> {
+        let index = self
+            .client
+            .get_parsed(OpamEndpoint::Index, &INDEXES, |index| {
+                let mut index = GzDecoder::new(index).take(Self::MAX_INDEX_SIZE);
+                let versions = read_index(&mut index)?;
+                if index.limit() == 0 {
+                    return Err(OpamError::InvalidIndex {
+                        source: std::io::Error::new(
+                            std::io::ErrorKind::InvalidData,
+                            format!("Index exceeds {} bytes.", Self::MAX_INDEX_SIZE),
+                        ),
+                    });
+                }
+                Ok(versions)
+            })
+            .await?;
+        index
+            .get(&package.name)
+            .map(|versions| versions.iter().cloned().collect())
+            .ok_or(OpamError::ResourceNotFound { source: None })
+    }
+}
+
+/// Reads the versions of every package from an index archive, which holds a
+/// `packages/<name>/<name>.<version>/opam` file for every package version.
+fn read_index<R: Read>(index: R) -> std::io::Result<OpamIndex> {
+    let mut versions = OpamIndex::new();
+    for entry in tar::Archive::new(index).entries()? {
+        let entry = entry?;
+        let path = entry.path()?;
+        let mut components = path
+            .components()
+            .filter_map(|component| component.as_os_str().to_str())
+            .filter(|component| *component != ".");
+        if let (Some("packages"), Some(package), Some(release), Some("opam"), None) = (
+            components.next(),
+            components.next(),
+            components.next(),
+            components.next(),
+            components.next(),
+        ) {
+            if let Some(version) = release
+                .strip_prefix(package)
+                .and_then(|version| version.strip_prefix('.'))
+            {
+                versions
+                    .entry(package.to_owned())
+                    .or_default()
+                    .insert(version.to_owned());
+            }
+        }
+    }
+    Ok(versions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn index(paths: &[&str]) -> Vec<u8> {
+        let mut builder = tar::Builder::new(Vec::new());
+        for path in paths {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(0);
+            builder
+                .append_data(&mut header, path, std::io::empty())
+                .unwrap();
+        }
+        builder.into_inner().unwrap()
+    }
+
+    #[test]
+    fn should_read_index() {
+        let index = index(&[
+            "repo",
+            "packages/ocamlformat/ocamlformat.0.26.2/opam",
+            "./packages/ocamlformat/ocamlformat.0.27.0/opam",
+            "packages/ocamlformat/ocamlformat.0.27.0/url",
+            "packages/ocamlformat-lib/ocamlformat-lib.0.26.2/opam",
+            "packages/ocamlformat/ocamlformat-lib.0.26.2/opam",
+        ]);
+        let versions = read_index(index.as_slice()).unwrap();
+        assert_eq!(
+            vec!["0.26.2", "0.27.0"],
+            versions["ocamlformat"].iter().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec!["0.26.2"],
+            versions["ocamlformat-lib"].iter().collect::<Vec<_>>()
+        );
+        assert_eq!(2, versions.len());
+        assert!(read_index(&b"not a tar archive"[..]).is_err());
+    }
+}
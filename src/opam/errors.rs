@@ -0,0 +1,55 @@
+use http::StatusCode;
+use thiserror::Error;
+
+use crate::errors::ApiError;
+
+#[derive(Error, Debug)]
+pub enum OpamError {
+    #[error("The requested resource was not found when interfacing with the opam repository.")]
+    ResourceNotFound { source: Option<reqwest::Error> },
+    #[error("Client error. {:?}", source.status())]
+    ClientError { source: reqwest::Error },
+    #[error("opam repository had a server error. {:?}", source.status())]
+    ServerError { source: reqwest::Error },
+    #[error("Network error. {:?}", source.status())]
+    NetworkError { source: reqwest::Error },
+    #[error("opam repository responded with an invalid index. {source}")]
+    InvalidIndex { source: std::io::Error },
+}
+
+impl ApiError for OpamError {
+    fn status_code(&self) -> http::StatusCode {
+        match self {
+            OpamError::ResourceNotFound { .. } => StatusCode::NOT_FOUND,
+            OpamError::ClientError { .. } | OpamError::NetworkError { .. } => {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+            OpamError::ServerError { .. } | OpamError::InvalidIndex { .. } => {
+                StatusCode::BAD_GATEWAY
+            }
+        }
+    }
+}
+
+impl From<reqwest::Error> for OpamError {
+    fn from(req_error: reqwest::Error) -> Self {
+        match req_error.status() {
+            Some(reqwest::StatusCode::NOT_FOUND) => Self::ResourceNotFound {
+                source: Some(req_error),
+            },
+            Some(status_code) if status_code.is_server_error() => {
+                Self::ServerError { source: req_error }
+            }
+            Some(status_code) if status_code.is_client_error() => {
+                Self::ClientError { source: req_error }
+            }
+            Some(_) | None => Self::NetworkError { source: req_error },
+        }
+    }
+}
+
+impl From<std::io::Error> for OpamError {
+    fn from(source: std::io::Error) -> Self {
+        Self::InvalidIndex { source }
+    }
+}
@@ -0,0 +1,82 @@
+use crate::{
+    registry::{PackageVersion, Registry},
+    versioning::opam,
+};
+
+use super::{api::OpamResponse, client::OpamClient, errors::OpamError, OpamPackage};
+
+pub struct OpamManager {
+    client: OpamClient,
+}
+
+impl OpamManager {
+    pub fn new(client: OpamClient) -> Self {
+        Self { client }
+    }
+
+    /// Returns all package versions in DESCENDING order. Pre-releases are only included if
+    /// requested.
+    pub async fn get_all_package_versions(
+        &self,
+        package: &OpamPackage,
+    ) -> Result<Vec<String>, OpamError> {
+        let mut versions = self.client.fetch_package_versions(package).await?;
+        if !package.include_prerelease.unwrap_or(false) {
+            versions.retain(|version| !opam::is_prerelease(version));
+        }
+        opam::sort_desc(&mut versions);
+        Ok(versions)
+    }
+
+    pub async fn get_latest_package_version(
+        &self,
+        package: &OpamPackage,
+    ) -> Result<OpamResponse, OpamError> {
+        let versions = self.client.fetch_package_versions(package).await?;
+        let latest = opam::latest(
+            versions.iter().map(String::as_str),
+            package.include_prerelease.unwrap_or(false),
+        )
+        .ok_or(OpamError::ResourceNotFound { source: None })?;
+        Ok(OpamResponse {
+            name: package.name.clone(),
+            version: latest.to_owned(),
+        })
+    }
+
+    pub async fn get_package_version(
+        &self,
+        package: &OpamPackage,
+        version: &str,
+    ) -> Result<OpamResponse, OpamError> {
+        let versions = self.client.fetch_package_versions(package).await?;
+        match versions.iter().any(|v| v == version) {
+            true => Ok(OpamResponse {
+                name: package.name.clone(),
+                version: version.to_owned(),
+            }),
+            false => Err(OpamError::ResourceNotFound { source: None }),
+        }
+    }
+}
+
+impl Registry for OpamManager {
+    type Package = OpamPackage;
+    type Error = OpamError;
+
+    async fn get_all_versions(&self, package: &OpamPackage) -> Result<Vec<String>, OpamError> {
+        self.get_all_package_versions(package).await
+    }
+
+    async fn get_latest_version(&self, package: &OpamPackage) -> Result<PackageVersion, OpamError> {
+        Ok(self.get_latest_package_version(package).await?.into())
+    }
+
+    async fn get_version(
+        &self,
+        package: &OpamPackage,
+        version: &str,
+    ) -> Result<PackageVersion, OpamError> {
+        Ok(self.get_package_version(package, version).await?.into())
+    }
+}
@@ -0,0 +1,34 @@
+use crate::QueryParams;
+
+pub mod api;
+pub mod client;
+pub mod errors;
+pub mod manager;
+
+#[derive(Debug)]
+pub struct OpamPackage {
+    pub name: String,
+    /// Whether to include pre-releases, e.g. `5.0.0~alpha1`. Unless set, they are left out.
+    pub include_prerelease: Option<bool>,
+}
+
+impl OpamPackage {
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            include_prerelease: None,
+        }
+    }
+}
+
+impl From<&QueryParams> for OpamPackage {
+    fn from(query: &QueryParams) -> Self {
+        Self {
+            name: query
+                .get("package")
+                .expect("No [package] query param")
+                .to_owned(),
+            include_prerelease: query.flag("include_prerelease"),
+        }
+    }
+}
@@ -1,11 +1,15 @@
 use serde::Serialize;
 
+use crate::registry::PackageVersion;
+
 use super::client::spec::OpenVSXExtensionDto;
 
 #[derive(Serialize)]
 pub struct OpenVSXExtensionResponse {
     pub name: String,
     pub version: String,
+    pub target_platform: Option<String>,
+    pub download_url: Option<String>,
 }
 
 impl From<OpenVSXExtensionDto> for OpenVSXExtensionResponse {
@@ -13,6 +17,21 @@ impl From<OpenVSXExtensionDto> for OpenVSXExtensionResponse {
         Self {
             name: format!("{}/{}", dto.namespace, dto.name),
             version: dto.version,
+            target_platform: dto.target_platform,
+            download_url: dto.files.and_then(|files| files.download),
+        }
+    }
+}
+
+impl From<OpenVSXExtensionResponse> for PackageVersion {
+    fn from(response: OpenVSXExtensionResponse) -> Self {
+        let mut version = PackageVersion::new(response.name, response.version);
+        if let Some(target_platform) = response.target_platform {
+            version = version.with_metadata("target_platform", target_platform);
+        }
+        if let Some(download_url) = response.download_url {
+            version = version.with_metadata("download_url", download_url);
         }
+        version
     }
 }
@@ -1,9 +1,12 @@
 pub mod spec;
 
-use self::spec::{OpenVSXExtensionVersionsDto, OpenVSXExtensionDto};
+use self::spec::{OpenVSXExtensionDto, OpenVSXQueryResultDto};
 
 use super::OpenVSXExtension;
-use crate::http::client::{Client, HttpEndpoint};
+use crate::{
+    http::client::{base_url_from_env, Client, HttpEndpoint},
+    CacheControl,
+};
 use std::fmt::Display;
 
 pub struct OpenVSXClient {
@@ -12,50 +15,150 @@ pub struct OpenVSXClient {
 
 enum OpenVSXEndpoint<'a> {
     Extension(&'a OpenVSXExtension),
-    ExtensionVersions(&'a OpenVSXExtension),
+    ExtensionVersion(&'a OpenVSXExtension, &'a str),
+    /// All versions of an extension, starting at the given offset.
+    Query(&'a OpenVSXExtension, usize),
 }
 
 impl<'a> HttpEndpoint for OpenVSXEndpoint<'a> {
-    fn as_full_url(&self) -> String {
-        format!("https://open-vsx.org/api/{}", self)
+    fn as_full_url(&self, base_url: &str) -> String {
+        format!("{}/{}", base_url, self)
+    }
+
+    fn cache_control(&self) -> CacheControl {
+        match self {
+            OpenVSXEndpoint::Extension(_) | OpenVSXEndpoint::Query(_, _) => {
+                CacheControl::PublicMedium
+            }
+            // Published versions are immutable.
+            OpenVSXEndpoint::ExtensionVersion(_, _) => CacheControl::PublicLong,
+        }
     }
 }
 
 impl<'a> Display for OpenVSXEndpoint<'a> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            OpenVSXEndpoint::Extension(ext) => {
-                f.write_fmt(format_args!("{}/{}", ext.namespace, ext.extension))
-            }
-            OpenVSXEndpoint::ExtensionVersions(ext) => {
-                f.write_fmt(format_args!("{}/{}/versions", ext.namespace, ext.extension))
+        let (ext, suffix) = match self {
+            OpenVSXEndpoint::Extension(ext) => (ext, None),
+            OpenVSXEndpoint::ExtensionVersion(ext, version) => (ext, Some(*version)),
+            OpenVSXEndpoint::Query(ext, offset) => {
+                f.write_fmt(format_args!(
+                    "api/-/query?namespaceName={}&extensionName={}&includeAllVersions=true&size={}&offset={}",
+                    ext.namespace,
+                    ext.extension,
+                    OpenVSXClient::QUERY_PAGE_SIZE,
+                    offset
+                ))?;
+                if let Some(target_platform) = &ext.target_platform {
+                    f.write_fmt(format_args!("&targetPlatform={}", target_platform))?;
+                }
+                return Ok(());
             }
+        };
+        f.write_fmt(format_args!("api/{}/{}", ext.namespace, ext.extension))?;
+        if let Some(target_platform) = &ext.target_platform {
+            f.write_fmt(format_args!("/{}", target_platform))?;
         }
+        if let Some(suffix) = suffix {
+            f.write_fmt(format_args!("/{}", suffix))?;
+        }
+        Ok(())
+    }
+}
+
+impl Default for OpenVSXClient {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
 impl OpenVSXClient {
+    pub const DEFAULT_BASE_URL: &'static str = "https://open-vsx.org";
+    const QUERY_PAGE_SIZE: usize = 100;
+
+    /// Creates a client for the registry configured via `OPENVSX_URL`, defaulting to
+    /// open-vsx.org.
     pub fn new() -> Self {
+        Self::with_base_url(base_url_from_env("OPENVSX_URL", Self::DEFAULT_BASE_URL))
+    }
+
+    pub fn with_base_url(base_url: String) -> Self {
         Self {
-            client: Client::new(None),
+            client: Client::new(base_url, None),
         }
     }
 
-    pub fn fetch_latest_extension_version(
+    pub async fn fetch_latest_extension_version(
         &self,
         extension: &OpenVSXExtension,
     ) -> Result<OpenVSXExtensionDto, reqwest::Error> {
         self.client
-            .get(OpenVSXEndpoint::Extension(extension))?
+            .get(OpenVSXEndpoint::Extension(extension))
+            .await?
             .json()
+            .await
     }
 
-    pub fn fetch_extension_versions(
+    pub async fn fetch_extension_version(
         &self,
         extension: &OpenVSXExtension,
-    ) -> Result<OpenVSXExtensionVersionsDto, reqwest::Error> {
+        version: &str,
+    ) -> Result<OpenVSXExtensionDto, reqwest::Error> {
         self.client
-            .get(OpenVSXEndpoint::ExtensionVersions(extension))?
+            .get(OpenVSXEndpoint::ExtensionVersion(extension, version))
+            .await?
             .json()
+            .await
+    }
+
+    /// Returns every build of every version of the extension. Unlike the `versions` endpoint, the
+    /// query API tells whether a version is a pre-release.
+    pub async fn fetch_extension_versions(
+        &self,
+        extension: &OpenVSXExtension,
+    ) -> Result<Vec<OpenVSXExtensionDto>, reqwest::Error> {
+        let mut extensions = Vec::new();
+        loop {
+            let mut page: OpenVSXQueryResultDto = self
+                .client
+                .get(OpenVSXEndpoint::Query(extension, extensions.len()))
+                .await?
+                .json()
+                .await?;
+            let is_last_page = page.extensions.is_empty()
+                || extensions.len() + page.extensions.len() >= page.total_size;
+            extensions.append(&mut page.extensions);
+            if is_last_page {
+                return Ok(extensions);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_include_target_platform_in_endpoints() {
+        let mut extension =
+            OpenVSXExtension::new("rust-lang".to_owned(), "rust-analyzer".to_owned());
+        assert_eq!(
+            "api/rust-lang/rust-analyzer/0.3.1549",
+            OpenVSXEndpoint::ExtensionVersion(&extension, "0.3.1549").to_string()
+        );
+        extension.target_platform = Some("linux-x64".to_owned());
+        assert_eq!(
+            "api/rust-lang/rust-analyzer/linux-x64",
+            OpenVSXEndpoint::Extension(&extension).to_string()
+        );
+        assert_eq!(
+            "api/rust-lang/rust-analyzer/linux-x64/0.3.1549",
+            OpenVSXEndpoint::ExtensionVersion(&extension, "0.3.1549").to_string()
+        );
+        assert_eq!(
+            "api/-/query?namespaceName=rust-lang&extensionName=rust-analyzer&includeAllVersions=true&size=100&offset=200&targetPlatform=linux-x64",
+            OpenVSXEndpoint::Query(&extension, 200).to_string()
+        );
     }
 }
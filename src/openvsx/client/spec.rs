@@ -1,10 +1,12 @@
-use std::collections::HashMap;
-
 use serde::{Deserialize, Serialize};
 
+use crate::versioning::semver;
+
 #[derive(Debug, Serialize, Deserialize)]
-pub struct OpenVSXExtensionVersionsDto {
-    pub versions: HashMap<String, String>,
+pub struct OpenVSXQueryResultDto {
+    #[serde(rename = "totalSize")]
+    pub total_size: usize,
+    pub extensions: Vec<OpenVSXExtensionDto>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -12,4 +14,26 @@ pub struct OpenVSXExtensionDto {
     pub namespace: String,
     pub name: String,
     pub version: String,
+    #[serde(rename = "targetPlatform")]
+    pub target_platform: Option<String>,
+    pub files: Option<OpenVSXExtensionFilesDto>,
+    /// Whether the version was published as a pre-release, which Open VSX doesn't require to be
+    /// reflected in the version itself.
+    #[serde(rename = "preRelease", default)]
+    pub pre_release: bool,
+}
+
+impl OpenVSXExtensionDto {
+    pub fn is_universal(&self) -> bool {
+        matches!(self.target_platform.as_deref(), None | Some("universal"))
+    }
+
+    pub fn is_prerelease(&self) -> bool {
+        self.pre_release || semver::is_prerelease(&self.version)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OpenVSXExtensionFilesDto {
+    pub download: Option<String>,
 }
@@ -13,6 +13,8 @@ pub enum OpenVSXError {
     ServerError { source: reqwest::Error },
     #[error("Network error. {:?}", source.status())]
     NetworkError { source: reqwest::Error },
+    #[error("Unknown target platform {target_platform:?}.")]
+    InvalidTargetPlatform { target_platform: String },
 }
 
 impl ApiError for OpenVSXError {
@@ -23,6 +25,7 @@ impl ApiError for OpenVSXError {
                 StatusCode::INTERNAL_SERVER_ERROR
             }
             OpenVSXError::ServerError { .. } => StatusCode::BAD_GATEWAY,
+            OpenVSXError::InvalidTargetPlatform { .. } => StatusCode::BAD_REQUEST,
         }
     }
 }
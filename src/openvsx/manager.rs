@@ -1,6 +1,10 @@
-use std::cmp::Ordering;
+use crate::{
+    registry::{PackageVersion, Registry},
+    versioning::semver,
+};
 
 use super::{
+    api::OpenVSXExtensionResponse,
     client::{spec::OpenVSXExtensionDto, OpenVSXClient},
     errors::OpenVSXError,
     OpenVSXExtension,
@@ -10,13 +14,17 @@ pub struct OpenVSXManager {
     client: OpenVSXClient,
 }
 
-fn semver_sort_desc(a: &String, b: &String) -> Ordering {
-    let a_semver = a.strip_prefix("v").unwrap_or(a).parse::<semver::Version>();
-    let b_semver = b.strip_prefix("v").unwrap_or(b).parse::<semver::Version>();
-    if let (Ok(a), Ok(b)) = (&a_semver, &b_semver) {
-        return b.cmp(a);
+fn is_missing_target(extension: &OpenVSXExtension, err: &reqwest::Error) -> bool {
+    extension.target_platform.is_some() && err.status() == Some(reqwest::StatusCode::NOT_FOUND)
+}
+
+/// Only universal builds can stand in for a build of a specific target platform.
+fn universal(extension: OpenVSXExtensionDto) -> Result<OpenVSXExtensionDto, OpenVSXError> {
+    if extension.is_universal() {
+        Ok(extension)
+    } else {
+        Err(OpenVSXError::ResourceNotFound { source: None })
     }
-    Ordering::Equal
 }
 
 impl OpenVSXManager {
@@ -24,26 +32,130 @@ impl OpenVSXManager {
         Self { client }
     }
 
-    pub fn get_extension(
+    /// Falls back to the extension's universal build if it has no build for the requested
+    /// target platform. With `include_prerelease`, returns a newer pre-release if there is one.
+    pub async fn get_extension(
         &self,
         extension: &OpenVSXExtension,
     ) -> Result<OpenVSXExtensionDto, OpenVSXError> {
-        Ok(self.client.fetch_latest_extension_version(extension)?)
+        let latest = match self.client.fetch_latest_extension_version(extension).await {
+            Err(err) if is_missing_target(extension, &err) => universal(
+                self.client
+                    .fetch_latest_extension_version(&extension.untargeted())
+                    .await?,
+            )?,
+            result => result?,
+        };
+        if !extension.include_prerelease.unwrap_or(false) {
+            return Ok(latest);
+        }
+        let versions = self.get_versions(extension).await?;
+        let prereleases = versions
+            .iter()
+            .filter(|version| version.is_prerelease())
+            .map(|version| &version.version);
+        let newest = semver::latest(
+            prereleases.chain([&latest.version]).map(String::as_str),
+            true,
+        )
+        .map(ToOwned::to_owned);
+        match newest {
+            Some(newest) if newest != latest.version => {
+                self.get_extension_version(extension, &newest).await
+            }
+            Some(_) | None => Ok(latest),
+        }
     }
 
-    /// Returns all extension versions in DESCENDING order.
-    pub fn get_all_versions(
+    /// Falls back to the extension's universal build if it has no build for the requested
+    /// target platform.
+    pub async fn get_extension_version(
         &self,
         extension: &OpenVSXExtension,
-    ) -> Result<Vec<String>, OpenVSXError> {
-        let mut unsorted_versions: Vec<String> = self
+        version: &str,
+    ) -> Result<OpenVSXExtensionDto, OpenVSXError> {
+        match self
             .client
-            .fetch_extension_versions(extension)?
-            .versions
-            .into_keys()
+            .fetch_extension_version(extension, version)
+            .await
+        {
+            Err(err) if is_missing_target(extension, &err) => universal(
+                self.client
+                    .fetch_extension_version(&extension.untargeted(), version)
+                    .await?,
+            ),
+            result => Ok(result?),
+        }
+    }
+
+    /// Returns the builds of all extension versions, falling back to the universal builds if
+    /// there are none for the requested target platform. Pre-releases are included unless opted
+    /// out of.
+    async fn get_versions(
+        &self,
+        extension: &OpenVSXExtension,
+    ) -> Result<Vec<OpenVSXExtensionDto>, OpenVSXError> {
+        let mut versions = self.client.fetch_extension_versions(extension).await?;
+        if versions.is_empty() && extension.target_platform.is_some() {
+            versions = self
+                .client
+                .fetch_extension_versions(&extension.untargeted())
+                .await?;
+            versions.retain(OpenVSXExtensionDto::is_universal);
+        }
+        if versions.is_empty() {
+            return Err(OpenVSXError::ResourceNotFound { source: None });
+        }
+        if !extension.include_prerelease.unwrap_or(true) {
+            versions.retain(|version| !version.is_prerelease());
+        }
+        Ok(versions)
+    }
+
+    /// Returns all extension versions in DESCENDING order. Pre-releases are included unless opted
+    /// out of.
+    pub async fn get_all_versions(
+        &self,
+        extension: &OpenVSXExtension,
+    ) -> Result<Vec<String>, OpenVSXError> {
+        let mut versions: Vec<String> = self
+            .get_versions(extension)
+            .await?
+            .into_iter()
+            .map(|version| version.version)
             .collect();
+        semver::sort_desc(&mut versions);
+        versions.dedup();
+        Ok(versions)
+    }
+}
+
+impl Registry for OpenVSXManager {
+    type Package = OpenVSXExtension;
+    type Error = OpenVSXError;
+
+    async fn get_all_versions(
+        &self,
+        extension: &OpenVSXExtension,
+    ) -> Result<Vec<String>, OpenVSXError> {
+        self.get_all_versions(extension).await
+    }
 
-        unsorted_versions.sort_by(semver_sort_desc);
-        Ok(unsorted_versions)
+    async fn get_latest_version(
+        &self,
+        extension: &OpenVSXExtension,
+    ) -> Result<PackageVersion, OpenVSXError> {
+        Ok(OpenVSXExtensionResponse::from(self.get_extension(extension).await?).into())
+    }
+
+    async fn get_version(
+        &self,
+        extension: &OpenVSXExtension,
+        version: &str,
+    ) -> Result<PackageVersion, OpenVSXError> {
+        Ok(
+            OpenVSXExtensionResponse::from(self.get_extension_version(extension, version).await?)
+                .into(),
+        )
     }
 }
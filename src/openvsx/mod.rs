@@ -1,19 +1,76 @@
 use crate::QueryParams;
 
+use self::errors::OpenVSXError;
+
 pub mod api;
 pub mod client;
 pub mod errors;
 pub mod manager;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct OpenVSXExtension {
     pub namespace: String,
     pub extension: String,
+    /// The platform to resolve builds for, e.g. `linux-x64`. Extensions that aren't
+    /// platform-specific are resolved to their universal build.
+    pub target_platform: Option<String>,
+    /// Whether to include pre-releases. Unless set, `versions/all` lists them but
+    /// `versions/latest` doesn't resolve to them.
+    pub include_prerelease: Option<bool>,
 }
 
-impl From<&QueryParams> for OpenVSXExtension {
-    fn from(query: &QueryParams) -> Self {
+impl OpenVSXExtension {
+    /// The target platforms supported by Open VSX.
+    pub const TARGET_PLATFORMS: &'static [&'static str] = &[
+        "win32-x64",
+        "win32-ia32",
+        "win32-arm64",
+        "linux-x64",
+        "linux-arm64",
+        "linux-armhf",
+        "alpine-x64",
+        "alpine-arm64",
+        "darwin-x64",
+        "darwin-arm64",
+        "web",
+        "universal",
+    ];
+
+    pub fn new(namespace: String, extension: String) -> Self {
         Self {
+            namespace,
+            extension,
+            target_platform: None,
+            include_prerelease: None,
+        }
+    }
+
+    /// Validates `target` against [`Self::TARGET_PLATFORMS`].
+    pub fn target_platform(target: Option<&str>) -> Result<Option<String>, OpenVSXError> {
+        match target {
+            Some(target) if Self::TARGET_PLATFORMS.contains(&target) => Ok(Some(target.to_owned())),
+            Some(target) => Err(OpenVSXError::InvalidTargetPlatform {
+                target_platform: target.to_owned(),
+            }),
+            None => Ok(None),
+        }
+    }
+
+    /// The same extension, without a target platform.
+    pub fn untargeted(&self) -> Self {
+        Self {
+            include_prerelease: self.include_prerelease,
+            ..Self::new(self.namespace.clone(), self.extension.clone())
+        }
+    }
+}
+
+impl TryFrom<&QueryParams> for OpenVSXExtension {
+    type Error = OpenVSXError;
+
+    fn try_from(query: &QueryParams) -> Result<Self, Self::Error> {
+        let target_platform = Self::target_platform(query.get("target").map(String::as_str))?;
+        Ok(Self {
             namespace: query
                 .get("namespace")
                 .expect("No [namespace] query param")
@@ -22,6 +79,8 @@ impl From<&QueryParams> for OpenVSXExtension {
                 .get("extension")
                 .expect("No [extension] query param")
                 .to_owned(),
-        }
+            target_platform,
+            include_prerelease: query.flag("include_prerelease"),
+        })
     }
 }
@@ -1,5 +1,7 @@
 use serde::Serialize;
 
+use crate::registry::PackageVersion;
+
 use super::client::spec::PackagistPackageDto;
 
 #[derive(Serialize)]
@@ -16,3 +18,9 @@ impl PackagistResponse {
         }
     }
 }
+
+impl From<PackagistResponse> for PackageVersion {
+    fn from(response: PackagistResponse) -> Self {
+        PackageVersion::new(response.name, response.version)
+    }
+}
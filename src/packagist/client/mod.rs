@@ -2,7 +2,10 @@ pub mod spec;
 
 use std::fmt::Display;
 
-use crate::http::client::{Client, HttpEndpoint};
+use crate::{
+    http::client::{base_url_from_env, Client, HttpEndpoint},
+    CacheControl,
+};
 
 use self::spec::PackagistPackageResponseEnvelope;
 
@@ -13,8 +16,12 @@ enum PackagistEndpoint<'a> {
 }
 
 impl<'a> HttpEndpoint for PackagistEndpoint<'a> {
-    fn as_full_url(&self) -> String {
-        format!("https://repo.packagist.org/{}", self)
+    fn as_full_url(&self, base_url: &str) -> String {
+        format!("{}/{}", base_url, self)
+    }
+
+    fn cache_control(&self) -> CacheControl {
+        CacheControl::PublicMedium
     }
 }
 
@@ -32,17 +39,35 @@ pub struct PackagistClient {
     client: Client,
 }
 
+impl Default for PackagistClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl PackagistClient {
+    pub const DEFAULT_BASE_URL: &'static str = "https://repo.packagist.org";
+
+    /// Creates a client for the repository configured via `PACKAGIST_URL`, defaulting to
+    /// Packagist.
     pub fn new() -> Self {
+        Self::with_base_url(base_url_from_env("PACKAGIST_URL", Self::DEFAULT_BASE_URL))
+    }
+
+    pub fn with_base_url(base_url: String) -> Self {
         PackagistClient {
-            client: Client::new(None),
+            client: Client::new(base_url, None),
         }
     }
 
-    pub fn fetch_package(
+    pub async fn fetch_package(
         &self,
         package: &PackagistPackage,
     ) -> Result<PackagistPackageResponseEnvelope, reqwest::Error> {
-        self.client.get(PackagistEndpoint::Package(package))?.json()
+        self.client
+            .get(PackagistEndpoint::Package(package))
+            .await?
+            .json()
+            .await
     }
 }
@@ -1,6 +1,12 @@
 use std::collections::LinkedList;
 
+use crate::{
+    registry::{PackageVersion, Registry},
+    versioning::composer,
+};
+
 use super::{
+    api::PackagistResponse,
     client::{spec::PackagistPackageDto, PackagistClient},
     errors::PackagistError,
     PackagistPackage,
@@ -15,46 +21,92 @@ impl PackagistManager {
         Self { client }
     }
 
-    fn resolve_package_versions(
+    async fn resolve_package_versions(
         &self,
         package: &PackagistPackage,
     ) -> Result<LinkedList<PackagistPackageDto>, PackagistError> {
         self.client
-            .fetch_package(package)?
+            .fetch_package(package)
+            .await?
             .packages
             .remove(&package.to_string())
             .ok_or_else(|| PackagistError::ResourceNotFound { source: None })
     }
 
-    pub fn get_package(
+    pub async fn get_package(
         &self,
         package: &PackagistPackage,
     ) -> Result<PackagistPackageDto, PackagistError> {
-        self.resolve_package_versions(package)?
-            .pop_front()
-            .ok_or_else(|| PackagistError::ResourceNotFound { source: None })
+        let mut versions = self.resolve_package_versions(package).await?;
+        let latest = composer::latest(
+            versions.iter().map(|v| v.version.as_str()),
+            package.include_prerelease.unwrap_or(false),
+        )
+        .map(ToOwned::to_owned);
+        match latest {
+            Some(latest) => versions.into_iter().find(|v| v.version == latest),
+            // Packages with only branch versions, e.g. dev-main.
+            None => versions.pop_front(),
+        }
+        .ok_or_else(|| PackagistError::ResourceNotFound { source: None })
     }
 
-    pub fn get_package_version(
+    pub async fn get_package_version(
         &self,
         package: &PackagistPackage,
         version: &str,
     ) -> Result<PackagistPackageDto, PackagistError> {
-        self.resolve_package_versions(package)?
+        self.resolve_package_versions(package)
+            .await?
             .into_iter()
             .find(|v| v.version == version)
             .ok_or_else(|| PackagistError::ResourceNotFound { source: None })
     }
 
-    /// Returns all package versions in DESCENDING order.
-    pub fn get_all_package_versions(
+    /// Returns all package versions in DESCENDING order. Versions less stable than `stable` are
+    /// included unless opted out of.
+    pub async fn get_all_package_versions(
         &self,
         package: &PackagistPackage,
     ) -> Result<Vec<String>, PackagistError> {
-        Ok(self
-            .resolve_package_versions(package)?
+        let include_prerelease = package.include_prerelease.unwrap_or(true);
+        let mut versions: Vec<String> = self
+            .resolve_package_versions(package)
+            .await?
             .into_iter()
             .map(|v| v.version)
-            .collect())
+            .filter(|version| include_prerelease || !composer::is_prerelease(version))
+            .collect();
+        composer::sort_desc(&mut versions);
+        Ok(versions)
+    }
+}
+
+impl Registry for PackagistManager {
+    type Package = PackagistPackage;
+    type Error = PackagistError;
+
+    async fn get_all_versions(
+        &self,
+        package: &PackagistPackage,
+    ) -> Result<Vec<String>, PackagistError> {
+        self.get_all_package_versions(package).await
+    }
+
+    async fn get_latest_version(
+        &self,
+        package: &PackagistPackage,
+    ) -> Result<PackageVersion, PackagistError> {
+        let dto = self.get_package(package).await?;
+        Ok(PackagistResponse::from_packagist_package_dto(package.name.clone(), dto).into())
+    }
+
+    async fn get_version(
+        &self,
+        package: &PackagistPackage,
+        version: &str,
+    ) -> Result<PackageVersion, PackagistError> {
+        let dto = self.get_package_version(package, version).await?;
+        Ok(PackagistResponse::from_packagist_package_dto(package.name.clone(), dto).into())
     }
 }
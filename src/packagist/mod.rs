@@ -11,6 +11,19 @@ pub mod manager;
 pub struct PackagistPackage {
     pub vendor: String,
     pub name: String,
+    /// Whether to include versions less stable than `stable`, e.g. `1.0.0-RC1`. Unless set,
+    /// `versions/all` lists them but `versions/latest` doesn't resolve to them.
+    pub include_prerelease: Option<bool>,
+}
+
+impl PackagistPackage {
+    pub fn new(vendor: String, name: String) -> Self {
+        Self {
+            vendor,
+            name,
+            include_prerelease: None,
+        }
+    }
 }
 
 impl From<&QueryParams> for PackagistPackage {
@@ -19,6 +32,7 @@ impl From<&QueryParams> for PackagistPackage {
             (Some(scope), Some(name)) => Self {
                 vendor: scope.to_owned(),
                 name: name.to_owned(),
+                include_prerelease: query.flag("include_prerelease"),
             },
             (Some(_), None) | (None, None) | (None, Some(_)) => {
                 panic!("Failed to parse npm package from URL.")
@@ -0,0 +1,64 @@
+use std::time::Duration;
+
+use http::StatusCode;
+use thiserror::Error;
+
+use crate::errors::ApiError;
+
+#[derive(Error, Debug)]
+pub enum PurlError {
+    #[error("Invalid package URL {purl:?}. {reason}")]
+    InvalidPurl { purl: String, reason: &'static str },
+    #[error("Package URLs of type {package_type:?} are not supported.")]
+    UnsupportedType { package_type: String },
+    #[error("Invalid {package_type} package URL {purl:?}, expected {expected}.")]
+    InvalidPackage {
+        package_type: &'static str,
+        purl: String,
+        expected: &'static str,
+    },
+    #[error("Invalid repository_url {repository_url:?}, expected an http(s) URL.")]
+    InvalidRepositoryUrl { repository_url: String },
+    #[error("The repository_url {repository_url:?} is not allowed.")]
+    RepositoryUrlNotAllowed { repository_url: String },
+    #[error("The repository_url qualifier is not supported for {package_type} package URLs.")]
+    UnsupportedRepositoryUrl { package_type: &'static str },
+    #[error("{message}")]
+    Registry {
+        status: StatusCode,
+        message: String,
+        retry_after: Option<Duration>,
+    },
+}
+
+impl PurlError {
+    /// Wraps an error from the registry the package URL resolved to, keeping its status.
+    pub fn registry<E: ApiError>(error: E) -> Self {
+        Self::Registry {
+            status: error.status_code(),
+            message: error.to_string(),
+            retry_after: error.retry_after(),
+        }
+    }
+}
+
+impl ApiError for PurlError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            PurlError::InvalidPurl { .. }
+            | PurlError::UnsupportedType { .. }
+            | PurlError::InvalidPackage { .. }
+            | PurlError::InvalidRepositoryUrl { .. }
+            | PurlError::RepositoryUrlNotAllowed { .. }
+            | PurlError::UnsupportedRepositoryUrl { .. } => StatusCode::BAD_REQUEST,
+            PurlError::Registry { status, .. } => *status,
+        }
+    }
+
+    fn retry_after(&self) -> Option<Duration> {
+        match self {
+            PurlError::Registry { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+}
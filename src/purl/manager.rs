@@ -0,0 +1,307 @@
+use crate::{
+    crates::{client::CratesClient, manager::CratesManager, Crate},
+    github::{client::GitHubClient, manager::GitHubManager, GitHubRepo},
+    golang::{client::GolangClient, manager::GolangManager, GolangPackage},
+    hackage::{client::HackageClient, manager::HackageManager, HackagePackage},
+    luarocks::{client::LuaRocksClient, manager::LuaRocksManager, LuaRocksPackage},
+    maven::{client::MavenClient, manager::MavenManager, MavenPackage},
+    npm::{client::NpmClient, manager::NpmManager, NpmPackage},
+    nuget::{client::NuGetClient, manager::NuGetManager, NuGetPackage},
+    opam::{client::OpamClient, manager::OpamManager, OpamPackage},
+    openvsx::{client::OpenVSXClient, manager::OpenVSXManager, OpenVSXExtension},
+    packagist::{client::PackagistClient, manager::PackagistManager, PackagistPackage},
+    pypi::{client::PyPiClient, manager::PyPiManager, PyPiPackage},
+    registry::{PackageVersion, Registry},
+    rubygems::{client::RubyGemsClient, manager::RubyGemsManager, RubyGemPackage},
+};
+
+use super::{errors::PurlError, PurlQuery};
+
+/// A package URL resolved to the registry it's looked up in.
+enum ResolvedPurl {
+    Cargo(CratesManager, Crate),
+    Composer(PackagistManager, PackagistPackage),
+    Gem(RubyGemsManager, RubyGemPackage),
+    GitHub(GitHubManager, GitHubRepo),
+    Golang(GolangManager, GolangPackage),
+    Hackage(HackageManager, HackagePackage),
+    LuaRocks(LuaRocksManager, LuaRocksPackage),
+    Maven(MavenManager, MavenPackage),
+    Npm(NpmManager, NpmPackage),
+    NuGet(NuGetManager, NuGetPackage),
+    Opam(OpamManager, OpamPackage),
+    OpenVSX(OpenVSXManager, OpenVSXExtension),
+    PyPi(PyPiManager, PyPiPackage),
+}
+
+impl TryFrom<&PurlQuery> for ResolvedPurl {
+    type Error = PurlError;
+
+    fn try_from(query: &PurlQuery) -> Result<Self, Self::Error> {
+        let (purl, include_prerelease, include_yanked) =
+            (&query.purl, query.include_prerelease, query.include_yanked);
+        let repository_url = purl.repository_url()?;
+        let invalid = |package_type, expected| PurlError::InvalidPackage {
+            package_type,
+            purl: purl.full_name(),
+            expected,
+        };
+        let name = purl.name.clone();
+
+        Ok(match purl.package_type.as_str() {
+            "cargo" => Self::Cargo(
+                CratesManager::new(
+                    repository_url.map_or_else(CratesClient::new, CratesClient::with_base_url),
+                ),
+                Crate {
+                    include_prerelease,
+                    include_yanked,
+                    ..Crate::new(name)
+                },
+            ),
+            "composer" => Self::Composer(
+                PackagistManager::new(
+                    repository_url
+                        .map_or_else(PackagistClient::new, PackagistClient::with_base_url),
+                ),
+                PackagistPackage {
+                    include_prerelease,
+                    ..PackagistPackage::new(
+                        purl.namespace.clone().ok_or_else(|| {
+                            invalid("composer", "pkg:composer/<vendor>/<package>")
+                        })?,
+                        name,
+                    )
+                },
+            ),
+            "gem" => Self::Gem(
+                RubyGemsManager::new(
+                    repository_url.map_or_else(RubyGemsClient::new, RubyGemsClient::with_base_url),
+                ),
+                RubyGemPackage {
+                    include_prerelease,
+                    ..RubyGemPackage::new(name)
+                },
+            ),
+            "github" => {
+                if repository_url.is_some() {
+                    return Err(PurlError::UnsupportedRepositoryUrl {
+                        package_type: "github",
+                    });
+                }
+                let owner = purl
+                    .namespace
+                    .clone()
+                    .filter(|owner| !owner.contains('/'))
+                    .ok_or_else(|| invalid("github", "pkg:github/<owner>/<name>"))?;
+                Self::GitHub(
+                    GitHubManager::new(GitHubClient::new()),
+                    GitHubRepo {
+                        include_prerelease,
+                        include_draft: query.include_draft,
+                        ..GitHubRepo::new(owner, name)
+                    },
+                )
+            }
+            "golang" => Self::Golang(
+                GolangManager::new(
+                    repository_url.map_or_else(GolangClient::new, GolangClient::with_base_url),
+                ),
+                GolangPackage {
+                    include_prerelease,
+                    ..GolangPackage::new(purl.full_name())
+                },
+            ),
+            "hackage" => {
+                let package = HackagePackage {
+                    include_deprecated: query.include_deprecated,
+                    ..HackagePackage::new(name)
+                };
+                if purl.namespace.is_some() || !package.is_valid() {
+                    return Err(invalid("hackage", "pkg:hackage/<name>"));
+                }
+                Self::Hackage(
+                    HackageManager::new(
+                        repository_url
+                            .map_or_else(HackageClient::new, HackageClient::with_base_url),
+                    ),
+                    package,
+                )
+            }
+            "luarocks" => Self::LuaRocks(
+                LuaRocksManager::new(
+                    repository_url.map_or_else(LuaRocksClient::new, LuaRocksClient::with_base_url),
+                ),
+                LuaRocksPackage {
+                    include_prerelease,
+                    ..LuaRocksPackage::new(name)
+                },
+            ),
+            "maven" => {
+                let package = MavenPackage {
+                    include_prerelease,
+                    ..MavenPackage::new(purl.namespace.clone().unwrap_or_default(), name)
+                };
+                if !package.is_valid() {
+                    return Err(invalid("maven", "pkg:maven/<groupId>/<artifactId>"));
+                }
+                Self::Maven(
+                    MavenManager::new(
+                        repository_url.map_or_else(MavenClient::new, MavenClient::with_base_url),
+                    ),
+                    package,
+                )
+            }
+            "npm" => {
+                let scope = match &purl.namespace {
+                    Some(scope) if scope.starts_with('@') && !scope.contains('/') => {
+                        Some(scope.clone())
+                    }
+                    Some(_) => {
+                        return Err(invalid(
+                            "npm",
+                            "pkg:npm/<name> or pkg:npm/%40<scope>/<name>",
+                        ))
+                    }
+                    None => None,
+                };
+                Self::Npm(
+                    NpmManager::new(
+                        repository_url.map_or_else(NpmClient::new, NpmClient::with_base_url),
+                    ),
+                    NpmPackage {
+                        include_prerelease,
+                        ..NpmPackage::new(scope, name)
+                    },
+                )
+            }
+            "nuget" => {
+                let package = NuGetPackage {
+                    include_prerelease,
+                    include_yanked,
+                    ..NuGetPackage::new(name)
+                };
+                if purl.namespace.is_some() || !package.is_valid() {
+                    return Err(invalid("nuget", "pkg:nuget/<id>"));
+                }
+                Self::NuGet(
+                    NuGetManager::new(
+                        repository_url.map_or_else(NuGetClient::new, NuGetClient::with_base_url),
+                    ),
+                    package,
+                )
+            }
+            "opam" => Self::Opam(
+                OpamManager::new(
+                    repository_url.map_or_else(OpamClient::new, OpamClient::with_base_url),
+                ),
+                OpamPackage {
+                    include_prerelease,
+                    ..OpamPackage::new(name)
+                },
+            ),
+            "openvsx" => Self::OpenVSX(
+                OpenVSXManager::new(
+                    repository_url.map_or_else(OpenVSXClient::new, OpenVSXClient::with_base_url),
+                ),
+                OpenVSXExtension {
+                    target_platform: OpenVSXExtension::target_platform(
+                        purl.qualifiers
+                            .get("target")
+                            .or(query.target.as_ref())
+                            .map(String::as_str),
+                    )
+                    .map_err(PurlError::registry)?,
+                    include_prerelease,
+                    ..OpenVSXExtension::new(
+                        purl.namespace.clone().ok_or_else(|| {
+                            invalid("openvsx", "pkg:openvsx/<namespace>/<extension>")
+                        })?,
+                        name,
+                    )
+                },
+            ),
+            "pypi" => Self::PyPi(
+                PyPiManager::new(
+                    repository_url.map_or_else(PyPiClient::new, PyPiClient::with_base_url),
+                ),
+                PyPiPackage {
+                    include_prerelease,
+                    include_yanked,
+                    ..PyPiPackage::new(name)
+                },
+            ),
+            package_type => {
+                return Err(PurlError::UnsupportedType {
+                    package_type: package_type.to_owned(),
+                })
+            }
+        })
+    }
+}
+
+/// Evaluates `$lookup` with the registry and package `$resolved` resolved to, whichever
+/// ecosystem's they are.
+macro_rules! with_registry {
+    ($resolved:expr, |$registry:ident, $package:ident| $lookup:expr) => {
+        match $resolved {
+            ResolvedPurl::Cargo($registry, $package) => $lookup,
+            ResolvedPurl::Composer($registry, $package) => $lookup,
+            ResolvedPurl::Gem($registry, $package) => $lookup,
+            ResolvedPurl::GitHub($registry, $package) => $lookup,
+            ResolvedPurl::Golang($registry, $package) => $lookup,
+            ResolvedPurl::Hackage($registry, $package) => $lookup,
+            ResolvedPurl::LuaRocks($registry, $package) => $lookup,
+            ResolvedPurl::Maven($registry, $package) => $lookup,
+            ResolvedPurl::Npm($registry, $package) => $lookup,
+            ResolvedPurl::NuGet($registry, $package) => $lookup,
+            ResolvedPurl::Opam($registry, $package) => $lookup,
+            ResolvedPurl::OpenVSX($registry, $package) => $lookup,
+            ResolvedPurl::PyPi($registry, $package) => $lookup,
+        }
+    };
+}
+
+/// Looks up packages identified by package URLs in the registry of their type, see
+/// [`ResolvedPurl`].
+#[derive(Default)]
+pub struct PurlManager;
+
+impl PurlManager {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Registry for PurlManager {
+    type Package = PurlQuery;
+    type Error = PurlError;
+
+    async fn get_all_versions(&self, query: &PurlQuery) -> Result<Vec<String>, PurlError> {
+        with_registry!(ResolvedPurl::try_from(query)?, |registry, package| {
+            Registry::get_all_versions(&registry, &package)
+                .await
+                .map_err(PurlError::registry)
+        })
+    }
+
+    async fn get_latest_version(&self, query: &PurlQuery) -> Result<PackageVersion, PurlError> {
+        with_registry!(ResolvedPurl::try_from(query)?, |registry, package| {
+            Registry::get_latest_version(&registry, &package)
+                .await
+                .map_err(PurlError::registry)
+        })
+    }
+
+    async fn get_version(
+        &self,
+        query: &PurlQuery,
+        version: &str,
+    ) -> Result<PackageVersion, PurlError> {
+        with_registry!(ResolvedPurl::try_from(query)?, |registry, package| {
+            Registry::get_version(&registry, &package, version)
+                .await
+                .map_err(PurlError::registry)
+        })
+    }
+}
@@ -0,0 +1,328 @@
+//! Package URLs (purls), e.g. `pkg:npm/%40angular/cli@17.0.0`, as specified in
+//! <https://github.com/package-url/purl-spec>.
+
+use std::{collections::BTreeMap, str::FromStr};
+
+use percent_encoding::percent_decode_str;
+
+use crate::QueryParams;
+
+use self::errors::PurlError;
+
+pub mod errors;
+pub mod manager;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Purl {
+    pub package_type: String,
+    pub namespace: Option<String>,
+    pub name: String,
+    pub version: Option<String>,
+    pub qualifiers: BTreeMap<String, String>,
+    pub subpath: Option<String>,
+}
+
+fn decode(component: &str) -> String {
+    percent_decode_str(component)
+        .decode_utf8_lossy()
+        .into_owned()
+}
+
+/// Normalizes an http(s) base URL, e.g. `registry.example.com/` to `https://registry.example.com`.
+fn normalize_repository_url(repository_url: &str) -> Option<String> {
+    let repository_url = repository_url.trim();
+    // Like the purl spec, allow the scheme to be omitted.
+    let with_scheme = match repository_url.contains("://") {
+        true => repository_url.to_owned(),
+        false => format!("https://{}", repository_url),
+    };
+    url::Url::parse(&with_scheme)
+        .ok()
+        .filter(|url| matches!(url.scheme(), "http" | "https") && url.has_host())
+        .map(|url| url.as_str().trim_end_matches('/').to_owned())
+}
+
+impl Purl {
+    /// The `repository_url` qualifier, i.e. an alternative registry to look the package up in.
+    /// Only registries allowed via `PURL_REPOSITORY_URLS` are accepted, as lookups would otherwise
+    /// send requests to arbitrary hosts.
+    pub fn repository_url(&self) -> Result<Option<String>, PurlError> {
+        self.allowed_repository_url(&std::env::var("PURL_REPOSITORY_URLS").unwrap_or_default())
+    }
+
+    /// The `repository_url` qualifier, if it's one of the comma-separated `allowed` base URLs.
+    fn allowed_repository_url(&self, allowed: &str) -> Result<Option<String>, PurlError> {
+        let Some(repository_url) = self.qualifiers.get("repository_url") else {
+            return Ok(None);
+        };
+        let repository_url = normalize_repository_url(repository_url).ok_or_else(|| {
+            PurlError::InvalidRepositoryUrl {
+                repository_url: repository_url.to_owned(),
+            }
+        })?;
+        match allowed
+            .split(',')
+            .filter_map(normalize_repository_url)
+            .any(|allowed| allowed == repository_url)
+        {
+            true => Ok(Some(repository_url)),
+            false => Err(PurlError::RepositoryUrlNotAllowed { repository_url }),
+        }
+    }
+
+    /// The namespace and name joined with `/`, e.g. `golang.org/x/tools/gopls`.
+    pub fn full_name(&self) -> String {
+        match &self.namespace {
+            Some(namespace) => format!("{}/{}", namespace, self.name),
+            None => self.name.clone(),
+        }
+    }
+}
+
+impl FromStr for Purl {
+    type Err = PurlError;
+
+    fn from_str(purl: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason| PurlError::InvalidPurl {
+            purl: purl.to_owned(),
+            reason,
+        };
+
+        let (remainder, subpath) = match purl.rsplit_once('#') {
+            Some((remainder, subpath)) => (remainder, Some(subpath)),
+            None => (purl, None),
+        };
+        let subpath = subpath
+            .map(|subpath| {
+                subpath
+                    .split('/')
+                    .filter(|segment| !matches!(*segment, "" | "." | ".."))
+                    .map(decode)
+                    .collect::<Vec<_>>()
+                    .join("/")
+            })
+            .filter(|subpath| !subpath.is_empty());
+
+        let (remainder, qualifiers) = match remainder.rsplit_once('?') {
+            Some((remainder, qualifiers)) => (remainder, Some(qualifiers)),
+            None => (remainder, None),
+        };
+        let mut parsed_qualifiers = BTreeMap::new();
+        for qualifier in qualifiers.unwrap_or_default().split('&') {
+            if qualifier.is_empty() {
+                continue;
+            }
+            let (key, value) = qualifier
+                .split_once('=')
+                .ok_or_else(|| invalid("Qualifiers must be key=value pairs."))?;
+            if !value.is_empty() {
+                parsed_qualifiers.insert(key.to_lowercase(), decode(value));
+            }
+        }
+
+        let remainder = match remainder.split_once(':') {
+            Some((scheme, remainder)) if scheme.eq_ignore_ascii_case("pkg") => {
+                remainder.trim_start_matches('/')
+            }
+            _ => return Err(invalid("The scheme must be pkg.")),
+        };
+        let (package_type, remainder) = remainder
+            .split_once('/')
+            .ok_or_else(|| invalid("A type and name are required."))?;
+        if package_type.is_empty() {
+            return Err(invalid("A type is required."));
+        }
+        let package_type = package_type.to_lowercase();
+
+        let remainder = remainder.trim_end_matches('/');
+        // Only an `@` in the last segment separates the version, so that unencoded npm scopes
+        // such as `@angular/cli` are still recognised as namespaces.
+        let last_segment = remainder.rfind('/').map_or(0, |index| index + 1);
+        let (remainder, version) = match remainder[last_segment..].rfind('@') {
+            Some(index) => (
+                &remainder[..last_segment + index],
+                Some(decode(&remainder[last_segment + index + 1..])),
+            ),
+            None => (remainder, None),
+        };
+        let version = version.filter(|version| !version.is_empty());
+
+        let mut segments = remainder
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(decode)
+            .collect::<Vec<_>>();
+        let mut name = segments
+            .pop()
+            .ok_or_else(|| invalid("A name is required."))?;
+        let mut namespace = Some(segments.join("/")).filter(|namespace| !namespace.is_empty());
+
+        match package_type.as_str() {
+            "github" => {
+                namespace = namespace.map(|namespace| namespace.to_lowercase());
+                name = name.to_lowercase();
+            }
+            "pypi" => name = name.to_lowercase().replace('_', "-"),
+            _ => {}
+        }
+
+        Ok(Self {
+            package_type,
+            namespace,
+            name,
+            version,
+            qualifiers: parsed_qualifiers,
+            subpath,
+        })
+    }
+}
+
+impl TryFrom<&QueryParams> for Purl {
+    type Error = PurlError;
+
+    fn try_from(query: &QueryParams) -> Result<Self, Self::Error> {
+        query.get("purl").expect("No [purl] query param.").parse()
+    }
+}
+
+/// A lookup of the package identified by a purl, along with the options of the request.
+#[derive(Debug)]
+pub struct PurlQuery {
+    pub purl: Purl,
+    /// Whether to include pre-releases, as in the routes of the purl's ecosystem. Unless set, the
+    /// ecosystem's default applies.
+    pub include_prerelease: Option<bool>,
+    /// Whether to include yanked (or unlisted) versions, as in the routes of the purl's ecosystem.
+    pub include_yanked: Option<bool>,
+    /// Whether to include draft releases, as in the GitHub routes.
+    pub include_draft: Option<bool>,
+    /// Whether to include deprecated versions, as in the Hackage routes.
+    pub include_deprecated: Option<bool>,
+    /// The platform to resolve builds for, as in the Open VSX routes. The purl's `target`
+    /// qualifier takes precedence.
+    pub target: Option<String>,
+}
+
+impl TryFrom<&QueryParams> for PurlQuery {
+    type Error = PurlError;
+
+    fn try_from(query: &QueryParams) -> Result<Self, Self::Error> {
+        Ok(Self {
+            purl: query.try_into()?,
+            include_prerelease: query.flag("include_prerelease"),
+            include_yanked: query.flag("include_yanked"),
+            include_draft: query.flag("include_draft"),
+            include_deprecated: query.flag("include_deprecated"),
+            target: query.get("target").cloned(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_purls() {
+        assert_eq!(
+            Purl {
+                package_type: "npm".to_owned(),
+                namespace: Some("@angular".to_owned()),
+                name: "cli".to_owned(),
+                version: Some("17.0.0".to_owned()),
+                qualifiers: BTreeMap::from([(
+                    "repository_url".to_owned(),
+                    "https://registry.example.com".to_owned()
+                )]),
+                subpath: Some("packages/cli".to_owned()),
+            },
+            "pkg:npm/%40angular/cli@17.0.0?Repository_Url=https%3A%2F%2Fregistry.example.com#/packages/./cli/"
+                .parse()
+                .unwrap()
+        );
+        assert_eq!(
+            Purl {
+                package_type: "golang".to_owned(),
+                namespace: Some("golang.org/x/tools".to_owned()),
+                name: "gopls".to_owned(),
+                version: None,
+                qualifiers: BTreeMap::new(),
+                subpath: None,
+            },
+            "pkg://Golang/golang.org/x/tools/gopls".parse().unwrap()
+        );
+    }
+
+    #[test]
+    fn should_parse_unencoded_npm_scopes() {
+        let purl: Purl = "pkg:npm/@angular/cli@17.0.0".parse().unwrap();
+        assert_eq!(Some("@angular"), purl.namespace.as_deref());
+        assert_eq!("cli", purl.name);
+        assert_eq!(Some("17.0.0"), purl.version.as_deref());
+
+        let purl: Purl = "pkg:npm/@angular/cli".parse().unwrap();
+        assert_eq!("@angular/cli", purl.full_name());
+        assert_eq!(None, purl.version);
+    }
+
+    #[test]
+    fn should_normalize_names() {
+        let purl: Purl = "pkg:pypi/Django_Allauth".parse().unwrap();
+        assert_eq!("django-allauth", purl.name);
+        let purl: Purl = "pkg:github/Sumneko/VSCode-Lua".parse().unwrap();
+        assert_eq!("sumneko/vscode-lua", purl.full_name());
+    }
+
+    #[test]
+    fn should_reject_invalid_purls() {
+        for invalid in [
+            "npm/typescript",
+            "purl:npm/typescript",
+            "pkg:npm",
+            "pkg:/typescript",
+            "pkg:npm/",
+            "pkg:npm/typescript?repository_url",
+        ] {
+            assert!(
+                matches!(invalid.parse::<Purl>(), Err(PurlError::InvalidPurl { .. })),
+                "{invalid}"
+            );
+        }
+    }
+
+    #[test]
+    fn should_validate_repository_url() {
+        let allowed = "https://registry.example.com/, http://localhost:4873";
+        let repository_url = |purl: &str| {
+            purl.parse::<Purl>()
+                .unwrap()
+                .allowed_repository_url(allowed)
+        };
+        assert_eq!(None, repository_url("pkg:npm/typescript").unwrap());
+        assert_eq!(
+            Some("https://registry.example.com".to_owned()),
+            repository_url("pkg:npm/typescript?repository_url=registry.example.com/").unwrap()
+        );
+        assert_eq!(
+            Some("http://localhost:4873".to_owned()),
+            repository_url("pkg:npm/typescript?repository_url=http://LOCALHOST:4873").unwrap()
+        );
+        assert!(matches!(
+            repository_url("pkg:npm/typescript?repository_url=file:///etc/passwd"),
+            Err(PurlError::InvalidRepositoryUrl { .. })
+        ));
+        for not_allowed in [
+            "http://169.254.169.254",
+            "https://registry.example.com/private",
+            "https://registry.example.com.evil.com",
+        ] {
+            assert!(
+                matches!(
+                    repository_url(&format!("pkg:npm/typescript?repository_url={not_allowed}")),
+                    Err(PurlError::RepositoryUrlNotAllowed { .. })
+                ),
+                "{not_allowed}"
+            );
+        }
+    }
+}
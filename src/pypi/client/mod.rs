@@ -1,6 +1,9 @@
 use std::fmt::Display;
 
-use crate::http::client::{Client, HttpEndpoint};
+use crate::{
+    http::client::{base_url_from_env, Client, HttpEndpoint},
+    CacheControl,
+};
 
 use self::spec::{PyPiProjectDto, PyPiProjectVersionedDto};
 
@@ -14,17 +17,27 @@ pub enum PyPiEndpoint<'a> {
 }
 
 impl<'a> HttpEndpoint for PyPiEndpoint<'a> {
-    fn as_full_url(&self) -> String {
-        format!("https://pypi.org/pypi/{}", self)
+    fn as_full_url(&self, base_url: &str) -> String {
+        format!("{}/{}", base_url, self)
+    }
+
+    fn cache_control(&self) -> CacheControl {
+        match self {
+            PyPiEndpoint::Project(_) => CacheControl::PublicMedium,
+            // Published releases are immutable.
+            PyPiEndpoint::ProjectVersion(_, _) => CacheControl::PublicLong,
+        }
     }
 }
 
 impl<'a> Display for PyPiEndpoint<'a> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            PyPiEndpoint::Project(project) => f.write_fmt(format_args!("{}/json", project.name)),
+            PyPiEndpoint::Project(project) => {
+                f.write_fmt(format_args!("pypi/{}/json", project.name))
+            }
             PyPiEndpoint::ProjectVersion(project, version) => {
-                f.write_fmt(format_args!("{}/{}/json", project.name, version))
+                f.write_fmt(format_args!("pypi/{}/{}/json", project.name, version))
             }
         }
     }
@@ -34,24 +47,46 @@ pub struct PyPiClient {
     client: Client,
 }
 
+impl Default for PyPiClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl PyPiClient {
+    pub const DEFAULT_BASE_URL: &'static str = "https://pypi.org";
+
+    /// Creates a client for the index configured via `PYPI_URL`, defaulting to PyPI.
     pub fn new() -> Self {
+        Self::with_base_url(base_url_from_env("PYPI_URL", Self::DEFAULT_BASE_URL))
+    }
+
+    pub fn with_base_url(base_url: String) -> Self {
         Self {
-            client: Client::new(None),
+            client: Client::new(base_url, None),
         }
     }
 
-    pub fn fetch_project(&self, project: &PyPiPackage) -> Result<PyPiProjectDto, reqwest::Error> {
-        self.client.get(PyPiEndpoint::Project(project))?.json()
+    pub async fn fetch_project(
+        &self,
+        project: &PyPiPackage,
+    ) -> Result<PyPiProjectDto, reqwest::Error> {
+        self.client
+            .get(PyPiEndpoint::Project(project))
+            .await?
+            .json()
+            .await
     }
 
-    pub fn fetch_project_version(
+    pub async fn fetch_project_version(
         &self,
         project: &PyPiPackage,
         version: &str,
     ) -> Result<PyPiProjectVersionedDto, reqwest::Error> {
         self.client
-            .get(PyPiEndpoint::ProjectVersion(project, version))?
+            .get(PyPiEndpoint::ProjectVersion(project, version))
+            .await?
             .json()
+            .await
     }
 }
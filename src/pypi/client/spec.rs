@@ -1,7 +1,6 @@
 use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
 
 #[derive(Deserialize, Serialize)]
 pub struct PyPiProjectInfoDto {
@@ -14,7 +13,13 @@ pub struct PyPiProjectInfoDto {
 #[derive(Deserialize)]
 pub struct PyPiProjectDto {
     pub info: PyPiProjectInfoDto,
-    pub releases: HashMap<String, Value>,
+    pub releases: HashMap<String, Vec<PyPiReleaseFileDto>>,
+}
+
+#[derive(Deserialize)]
+pub struct PyPiReleaseFileDto {
+    #[serde(default)]
+    pub yanked: bool,
 }
 
 #[derive(Deserialize)]
@@ -1,8 +1,11 @@
-use std::cmp::Ordering;
+use crate::{
+    registry::{PackageVersion, Registry},
+    versioning::pep440,
+};
 
 use super::{
     client::{
-        spec::{PyPiProjectDto, PyPiProjectVersionedDto},
+        spec::{PyPiProjectDto, PyPiProjectInfoDto, PyPiProjectVersionedDto, PyPiReleaseFileDto},
         PyPiClient,
     },
     errors::PyPiError,
@@ -13,14 +16,9 @@ pub struct PyPiManager {
     client: PyPiClient,
 }
 
-fn maybe_semver_sort_desc(a: &String, b: &String) -> Ordering {
-    let a_semver = a.parse::<semver::Version>();
-    let b_semver = b.parse::<semver::Version>();
-    if let (Ok(a), Ok(b)) = (a_semver, b_semver) {
-        b.cmp(&a)
-    } else {
-        b.cmp(a)
-    }
+/// Whether every file of a release has been yanked, in which case pip no longer considers it.
+fn is_yanked(files: &[PyPiReleaseFileDto]) -> bool {
+    !files.is_empty() && files.iter().all(|file| file.yanked)
 }
 
 impl PyPiManager {
@@ -28,29 +26,91 @@ impl PyPiManager {
         Self { client }
     }
 
-    pub fn get_project(&self, package: &PyPiPackage) -> Result<PyPiProjectDto, PyPiError> {
-        Ok(self.client.fetch_project(package)?)
+    pub async fn get_project(&self, package: &PyPiPackage) -> Result<PyPiProjectDto, PyPiError> {
+        Ok(self.client.fetch_project(package).await?)
     }
 
-    pub fn get_project_version(
+    pub async fn get_project_version(
         &self,
         package: &PyPiPackage,
         version: &str,
     ) -> Result<PyPiProjectVersionedDto, PyPiError> {
-        Ok(self.client.fetch_project_version(&package, version)?)
+        Ok(self.client.fetch_project_version(package, version).await?)
     }
 
-    /// Returns all package versions in DESCENDING order.
-    /// Ordering should not be relied upon as it does not strictly follow pip's version ordering.
-    pub fn get_all_package_versions(
+    /// Returns all package versions in DESCENDING order, as ordered by PEP 440. Pre-releases and
+    /// yanked releases are each included unless opted out of.
+    pub async fn get_all_package_versions(
         &self,
         package: &PyPiPackage,
     ) -> Result<Vec<String>, PyPiError> {
-        let project = self.client.fetch_project(package)?;
-        let mut versions: Vec<String> = project.releases.into_keys().into_iter().collect();
-        // This is not at all according to pip's version sorting [1], but it makes the vector nicer to the eye.
-        // [1]: https://github.com/pypa/pip/blob/d6e333fb636424d7dca15f4e8aa61cdaab9cdd31/src/pip/_vendor/packaging/version.py#L223-L288
-        versions.sort_by(maybe_semver_sort_desc);
+        let project = self.client.fetch_project(package).await?;
+        let include_prerelease = package.include_prerelease.unwrap_or(true);
+        let include_yanked = package.include_yanked.unwrap_or(true);
+        let mut versions: Vec<String> = project
+            .releases
+            .into_iter()
+            .filter(|(version, _)| include_prerelease || !pep440::is_prerelease(version))
+            .filter(|(_, files)| include_yanked || !is_yanked(files))
+            .map(|(version, _)| version)
+            .collect();
+        pep440::sort_desc(&mut versions);
         Ok(versions)
     }
+
+    /// Returns the latest version pip would install, i.e. the greatest release that is neither a
+    /// pre-release (unless requested, like `pip install --pre`) nor yanked.
+    pub async fn get_latest_package_version(
+        &self,
+        package: &PyPiPackage,
+    ) -> Result<PyPiProjectInfoDto, PyPiError> {
+        let project = self.client.fetch_project(package).await?;
+        let latest = pep440::latest(
+            project
+                .releases
+                .iter()
+                .filter(|(_, files)| !is_yanked(files))
+                .map(|(version, _)| version.as_str()),
+            package.include_prerelease.unwrap_or(false),
+        );
+        match latest {
+            Some(latest) if latest != project.info.version => {
+                Ok(self.get_project_version(package, latest).await?.info)
+            }
+            Some(_) | None => Ok(project.info),
+        }
+    }
+}
+
+impl From<PyPiProjectInfoDto> for PackageVersion {
+    fn from(info: PyPiProjectInfoDto) -> Self {
+        PackageVersion::new(info.name, info.version)
+            .with_metadata("license", info.license)
+            .with_metadata("requires_python", info.requires_python)
+    }
+}
+
+impl Registry for PyPiManager {
+    type Package = PyPiPackage;
+    type Error = PyPiError;
+
+    async fn get_all_versions(&self, package: &PyPiPackage) -> Result<Vec<String>, PyPiError> {
+        self.get_all_package_versions(package).await
+    }
+
+    async fn get_latest_version(&self, package: &PyPiPackage) -> Result<PackageVersion, PyPiError> {
+        Ok(self.get_latest_package_version(package).await?.into())
+    }
+
+    async fn get_version(
+        &self,
+        package: &PyPiPackage,
+        version: &str,
+    ) -> Result<PackageVersion, PyPiError> {
+        Ok(self
+            .get_project_version(package, version)
+            .await?
+            .info
+            .into())
+    }
 }
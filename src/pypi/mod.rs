@@ -6,7 +6,23 @@ pub mod manager;
 
 #[derive(Debug)]
 pub struct PyPiPackage {
-    name: String,
+    pub name: String,
+    /// Whether to include pre-releases and development releases. Unless set, `versions/all` lists
+    /// them but `versions/latest` doesn't resolve to them.
+    pub include_prerelease: Option<bool>,
+    /// Whether to list yanked releases in `versions/all`, which they are unless opted out of.
+    /// Like pip, yanked releases are never resolved as the latest version.
+    pub include_yanked: Option<bool>,
+}
+
+impl PyPiPackage {
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            include_prerelease: None,
+            include_yanked: None,
+        }
+    }
 }
 
 impl From<&QueryParams> for PyPiPackage {
@@ -16,6 +32,8 @@ impl From<&QueryParams> for PyPiPackage {
                 .get("package")
                 .expect("No [package] query param.")
                 .to_owned(),
+            include_prerelease: query.flag("include_prerelease"),
+            include_yanked: query.flag("include_yanked"),
         }
     }
 }
@@ -0,0 +1,105 @@
+use std::future::Future;
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::errors::ApiError;
+
+/// Common response type for a single version of a package, regardless of ecosystem.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct PackageVersion {
+    pub name: String,
+    pub version: String,
+    /// Ecosystem-specific metadata, flattened into the response.
+    #[serde(flatten)]
+    pub metadata: Map<String, Value>,
+}
+
+impl PackageVersion {
+    pub fn new(name: String, version: String) -> Self {
+        Self {
+            name,
+            version,
+            metadata: Map::new(),
+        }
+    }
+
+    pub fn with_metadata<V: Serialize>(mut self, key: &str, value: V) -> Self {
+        if let Ok(value) = serde_json::to_value(value) {
+            self.metadata.insert(key.to_owned(), value);
+        }
+        self
+    }
+}
+
+/// A package registry able to answer the version queries exposed by the API.
+pub trait Registry {
+    type Package;
+    type Error: ApiError;
+
+    /// Returns all package versions in DESCENDING order.
+    fn get_all_versions(
+        &self,
+        package: &Self::Package,
+    ) -> impl Future<Output = Result<Vec<String>, Self::Error>> + Send;
+
+    fn get_latest_version(
+        &self,
+        package: &Self::Package,
+    ) -> impl Future<Output = Result<PackageVersion, Self::Error>> + Send;
+
+    fn get_version(
+        &self,
+        package: &Self::Package,
+        version: &str,
+    ) -> impl Future<Output = Result<PackageVersion, Self::Error>> + Send;
+}
+
+/// Whether `id`, e.g. a package id, can safely be interpolated into upstream URL paths, i.e. is
+/// made up of ASCII alphanumerics, `.`, `-` and `_`, and can't be mistaken for `.` or `..`.
+pub fn is_path_safe_id(id: &str) -> bool {
+    !id.is_empty()
+        && !id.starts_with('.')
+        && !id.contains("..")
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_flatten_metadata() {
+        let version = PackageVersion::new("foobar".to_owned(), "13.3.7".to_owned())
+            .with_metadata("licenses", vec!["MIT"]);
+        assert_eq!(
+            serde_json::json!({
+                "name": "foobar",
+                "version": "13.3.7",
+                "licenses": ["MIT"],
+            }),
+            serde_json::to_value(version).unwrap()
+        );
+    }
+
+    #[test]
+    fn should_only_accept_path_safe_ids() {
+        for valid in ["CSharpier", "org.eclipse.jdt", "dotnet-format", "foo_bar"] {
+            assert!(is_path_safe_id(valid), "{valid}");
+        }
+        for invalid in [
+            "",
+            ".",
+            "..",
+            ".hidden",
+            "Foo..Bar",
+            "Foo/Bar",
+            "Foo%2FBar",
+            "Foo Bar",
+        ] {
+            assert!(!is_path_safe_id(invalid), "{invalid}");
+        }
+    }
+}
@@ -6,7 +6,7 @@ use http::{header::AUTHORIZATION, HeaderMap};
 
 use crate::{
     github::GitHubRepo,
-    http::client::{Client, HttpEndpoint},
+    http::client::{base_url_from_env, Client, HttpEndpoint},
 };
 
 use self::spec::JobsResponse;
@@ -30,26 +30,44 @@ impl<'a> Display for RenovateEndpoint<'a> {
 }
 
 impl<'a> HttpEndpoint for RenovateEndpoint<'a> {
-    fn as_full_url(&self) -> String {
-        format!("https://v1.renovateapi.com/{}", self)
+    fn as_full_url(&self, base_url: &str) -> String {
+        format!("{}/{}", base_url, self)
     }
 }
 
 impl RenovateClient {
+    pub const DEFAULT_BASE_URL: &'static str = "https://v1.renovateapi.com";
+
+    /// Creates a client for the API configured via `RENOVATE_API_URL`, defaulting to the hosted
+    /// Renovate API.
     pub fn new(api_key: String) -> Self {
+        Self::with_base_url(
+            api_key,
+            base_url_from_env("RENOVATE_API_URL", Self::DEFAULT_BASE_URL),
+        )
+    }
+
+    pub fn with_base_url(api_key: String, base_url: String) -> Self {
         let mut headers = HeaderMap::new();
         headers.insert(
             AUTHORIZATION,
             format!("Bearer {}", api_key).parse().unwrap(),
         );
         Self {
-            client: Client::new(Some(headers)),
+            client: Client::new(base_url, Some(headers)),
         }
     }
 
     /// Returns jobs in ASCENDING order.
-    pub fn fetch_github_jobs(&self, repo: &GitHubRepo) -> Result<JobsResponse, reqwest::Error> {
+    pub async fn fetch_github_jobs(
+        &self,
+        repo: &GitHubRepo,
+    ) -> Result<JobsResponse, reqwest::Error> {
         tracing::debug!("Fetching GitHub jobs for repo: {repo}");
-        self.client.get(RenovateEndpoint::GitHubJobs(repo))?.json()
+        self.client
+            .get(RenovateEndpoint::GitHubJobs(repo))
+            .await?
+            .json()
+            .await
     }
 }
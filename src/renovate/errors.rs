@@ -15,6 +15,8 @@ pub enum RenovateError {
     NetworkError { source: reqwest::Error },
     #[error("API had internal error.")]
     InternalError,
+    #[error("No GitHub API key is configured.")]
+    MissingApiKey,
 }
 
 impl ApiError for RenovateError {
@@ -22,6 +24,7 @@ impl ApiError for RenovateError {
         match self {
             RenovateError::ResourceNotFound { .. } => StatusCode::NOT_FOUND,
             RenovateError::InternalError
+            | RenovateError::MissingApiKey
             | RenovateError::ClientError { .. }
             | RenovateError::NetworkError { .. } => StatusCode::INTERNAL_SERVER_ERROR,
             RenovateError::ServerError { .. } => StatusCode::BAD_GATEWAY,
@@ -1,5 +1,7 @@
 use serde::Serialize;
 
+use crate::registry::PackageVersion;
+
 use super::client::spec::{RubyGemDto, RubyGemVersionDto};
 
 #[derive(Serialize)]
@@ -14,7 +16,7 @@ impl From<RubyGemDto> for RubyGemResponse {
         Self {
             name: gem_dto.name,
             version: gem_dto.version,
-            licenses: gem_dto.licenses.unwrap_or_else(|| vec![]),
+            licenses: gem_dto.licenses.unwrap_or_default(),
         }
     }
 }
@@ -27,7 +29,14 @@ impl RubyGemResponse {
         RubyGemResponse {
             name: gem_name,
             version: gem_version_dto.version,
-            licenses: gem_version_dto.licenses.unwrap_or_else(|| vec![]),
+            licenses: gem_version_dto.licenses.unwrap_or_default(),
         }
     }
 }
+
+impl From<RubyGemResponse> for PackageVersion {
+    fn from(response: RubyGemResponse) -> Self {
+        PackageVersion::new(response.name, response.version)
+            .with_metadata("licenses", response.licenses)
+    }
+}
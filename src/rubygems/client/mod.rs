@@ -1,6 +1,9 @@
 use std::fmt::Display;
 
-use crate::http::client::{Client, HttpEndpoint};
+use crate::{
+    http::client::{base_url_from_env, Client, HttpEndpoint},
+    CacheControl,
+};
 
 use self::spec::{RubyGemDto, RubyGemVersionDto};
 
@@ -14,17 +17,23 @@ pub enum RubyGemsEndpoint<'a> {
 }
 
 impl<'a> HttpEndpoint for RubyGemsEndpoint<'a> {
-    fn as_full_url(&self) -> String {
-        format!("https://rubygems.org/api/{}", self)
+    fn as_full_url(&self, base_url: &str) -> String {
+        format!("{}/{}", base_url, self)
+    }
+
+    fn cache_control(&self) -> CacheControl {
+        CacheControl::PublicMedium
     }
 }
 
 impl<'a> Display for RubyGemsEndpoint<'a> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            RubyGemsEndpoint::Gem(gem) => f.write_fmt(format_args!("v1/gems/{}.json", gem.name)),
+            RubyGemsEndpoint::Gem(gem) => {
+                f.write_fmt(format_args!("api/v1/gems/{}.json", gem.name))
+            }
             RubyGemsEndpoint::GemVersions(gem) => {
-                f.write_fmt(format_args!("v1/versions/{}.json", gem.name))
+                f.write_fmt(format_args!("api/v1/versions/{}.json", gem.name))
             }
         }
     }
@@ -34,21 +43,42 @@ pub struct RubyGemsClient {
     client: Client,
 }
 
+impl Default for RubyGemsClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl RubyGemsClient {
+    pub const DEFAULT_BASE_URL: &'static str = "https://rubygems.org";
+
+    /// Creates a client for the host configured via `RUBYGEMS_URL`, defaulting to rubygems.org.
     pub fn new() -> Self {
+        Self::with_base_url(base_url_from_env("RUBYGEMS_URL", Self::DEFAULT_BASE_URL))
+    }
+
+    pub fn with_base_url(base_url: String) -> Self {
         Self {
-            client: Client::new(None),
+            client: Client::new(base_url, None),
         }
     }
 
-    pub fn fetch_gem(&self, gem: &RubyGemPackage) -> Result<RubyGemDto, reqwest::Error> {
-        self.client.get(RubyGemsEndpoint::Gem(gem))?.json()
+    pub async fn fetch_gem(&self, gem: &RubyGemPackage) -> Result<RubyGemDto, reqwest::Error> {
+        self.client
+            .get(RubyGemsEndpoint::Gem(gem))
+            .await?
+            .json()
+            .await
     }
 
-    pub fn fetch_gem_versions(
+    pub async fn fetch_gem_versions(
         &self,
         gem: &RubyGemPackage,
     ) -> Result<Vec<RubyGemVersionDto>, reqwest::Error> {
-        self.client.get(RubyGemsEndpoint::GemVersions(gem))?.json()
+        self.client
+            .get(RubyGemsEndpoint::GemVersions(gem))
+            .await?
+            .json()
+            .await
     }
 }
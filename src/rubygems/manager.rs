@@ -1,4 +1,10 @@
+use crate::{
+    registry::{PackageVersion, Registry},
+    versioning::rubygems,
+};
+
 use super::{
+    api::RubyGemResponse,
     client::{
         spec::{RubyGemDto, RubyGemVersionDto},
         RubyGemsClient,
@@ -16,35 +22,103 @@ impl RubyGemsManager {
         Self { client }
     }
 
-    pub fn get_gem(&self, gem: &RubyGemPackage) -> Result<RubyGemDto, RubyGemsError> {
-        Ok(self.client.fetch_gem(gem)?)
+    pub async fn get_gem(&self, gem: &RubyGemPackage) -> Result<RubyGemDto, RubyGemsError> {
+        Ok(self.client.fetch_gem(gem).await?)
     }
 
-    pub fn get_gem_version(
+    pub async fn get_gem_version(
         &self,
         gem: &RubyGemPackage,
         version: &str,
     ) -> Result<RubyGemVersionDto, RubyGemsError> {
-        let gem_versions = self.client.fetch_gem_versions(gem)?;
+        let gem_versions = self.client.fetch_gem_versions(gem).await?;
         gem_versions
             .into_iter()
             .find(|gem| gem.version == version)
             .ok_or_else(|| RubyGemsError::ResourceNotFound { source: None })
     }
 
-    /// Returns all package versions in DESCENDING order.
-    pub fn get_all_gem_versions(&self, gem: &RubyGemPackage) -> Result<Vec<String>, RubyGemsError> {
-        Ok(self
+    /// Returns the latest stable version or, with `include_prerelease`, a newer pre-release if
+    /// there is one.
+    pub async fn get_latest_gem_version(
+        &self,
+        gem: &RubyGemPackage,
+    ) -> Result<RubyGemResponse, RubyGemsError> {
+        let latest = self.get_gem(gem).await?;
+        if !gem.include_prerelease.unwrap_or(false) {
+            return Ok(latest.into());
+        }
+        let mut gem_versions = self.client.fetch_gem_versions(gem).await?;
+        let prereleases = gem_versions
+            .iter()
+            .filter(|gem_version| gem_version.prerelease)
+            .map(|gem_version| &gem_version.version);
+        let newest = rubygems::latest(
+            prereleases.chain([&latest.version]).map(String::as_str),
+            true,
+        )
+        .map(ToOwned::to_owned);
+        match newest {
+            Some(newest) if newest != latest.version => {
+                let index = gem_versions
+                    .iter()
+                    .position(|gem_version| gem_version.version == newest)
+                    .ok_or_else(|| RubyGemsError::ResourceNotFound { source: None })?;
+                Ok(RubyGemResponse::from_versioned_dto(
+                    gem.name.clone(),
+                    gem_versions.swap_remove(index),
+                ))
+            }
+            Some(_) | None => Ok(latest.into()),
+        }
+    }
+
+    /// Returns all package versions in DESCENDING order. Pre-releases are only included if
+    /// requested.
+    pub async fn get_all_gem_versions(
+        &self,
+        gem: &RubyGemPackage,
+    ) -> Result<Vec<String>, RubyGemsError> {
+        let include_prerelease = gem.include_prerelease.unwrap_or(false);
+        let mut versions: Vec<String> = self
             .client
-            .fetch_gem_versions(gem)?
+            .fetch_gem_versions(gem)
+            .await?
             .into_iter()
-            .filter_map(|gem| {
-                if !gem.prerelease {
-                    Some(gem.version)
+            .filter_map(|gem_version| {
+                if include_prerelease || !gem_version.prerelease {
+                    Some(gem_version.version)
                 } else {
                     None
                 }
             })
-            .collect())
+            .collect();
+        rubygems::sort_desc(&mut versions);
+        Ok(versions)
+    }
+}
+
+impl Registry for RubyGemsManager {
+    type Package = RubyGemPackage;
+    type Error = RubyGemsError;
+
+    async fn get_all_versions(&self, gem: &RubyGemPackage) -> Result<Vec<String>, RubyGemsError> {
+        self.get_all_gem_versions(gem).await
+    }
+
+    async fn get_latest_version(
+        &self,
+        gem: &RubyGemPackage,
+    ) -> Result<PackageVersion, RubyGemsError> {
+        Ok(self.get_latest_gem_version(gem).await?.into())
+    }
+
+    async fn get_version(
+        &self,
+        gem: &RubyGemPackage,
+        version: &str,
+    ) -> Result<PackageVersion, RubyGemsError> {
+        let gem_version = self.get_gem_version(gem, version).await?;
+        Ok(RubyGemResponse::from_versioned_dto(gem.name.clone(), gem_version).into())
     }
 }
@@ -8,12 +8,24 @@ pub mod manager;
 #[derive(Debug)]
 pub struct RubyGemPackage {
     pub name: String,
+    /// Whether to include pre-releases. Unless set, they are left out.
+    pub include_prerelease: Option<bool>,
+}
+
+impl RubyGemPackage {
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            include_prerelease: None,
+        }
+    }
 }
 
 impl From<&QueryParams> for RubyGemPackage {
     fn from(query: &QueryParams) -> Self {
         RubyGemPackage {
             name: query.get("gem").expect("No [gem] query param.").to_owned(),
+            include_prerelease: query.flag("include_prerelease"),
         }
     }
 }
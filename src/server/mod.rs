@@ -0,0 +1,127 @@
+//! Standalone HTTP server mounting every API route on a single listener, for deployments outside
+//! of Vercel.
+
+use std::{convert::Infallible, sync::Arc};
+
+use http::{header::HOST, Method, StatusCode};
+use http_body_util::{BodyExt, Full, LengthLimitError, Limited};
+use hyper::{
+    body::{Bytes, Incoming},
+    server::conn::http1,
+    service::service_fn,
+};
+use hyper_util::rt::TokioIo;
+use tokio::net::TcpListener;
+use vercel_runtime::{Body, Error, Request, Response};
+
+use crate::{
+    errors::CoreError,
+    vercel::{conditional, err_json, redirect},
+    CacheControl,
+};
+
+use self::router::Router;
+
+pub mod router;
+
+const ROOT_REDIRECT: &str = "https://github.com/mason-org/mason-registry-api";
+
+/// The largest request body accepted, well above the few KiB the API's JSON requests take up.
+pub const MAX_BODY_SIZE: usize = 64 * 1024;
+
+pub async fn serve(listener: TcpListener) -> Result<(), Error> {
+    let router = Arc::new(Router::default());
+    tracing::info!("Listening on http://{}", listener.local_addr()?);
+
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let router = router.clone();
+        tokio::spawn(async move {
+            let service = service_fn(move |request| handle(router.clone(), request));
+            if let Err(err) = http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), service)
+                .await
+            {
+                tracing::debug!("Failed to serve connection: {err:?}");
+            }
+        });
+    }
+}
+
+async fn handle(
+    router: Arc<Router>,
+    request: http::Request<Incoming>,
+) -> Result<http::Response<Full<Bytes>>, Infallible> {
+    let response = match dispatch(&router, request).await {
+        Ok(response) => response,
+        Err(err) => {
+            tracing::error!("Unhandled error: {err:?}");
+            Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::Empty)
+                .expect("Failed to build response.")
+        }
+    };
+    let (parts, body) = response.into_parts();
+    let body = match body {
+        Body::Empty => Bytes::new(),
+        Body::Text(text) => Bytes::from(text),
+        Body::Binary(binary) => Bytes::from(binary),
+    };
+    Ok(http::Response::from_parts(parts, Full::new(body)))
+}
+
+async fn dispatch(
+    router: &Router,
+    request: http::Request<Incoming>,
+) -> Result<Response<Body>, Error> {
+    let path = request.uri().path().to_owned();
+    if path == "/" && request.method() == Method::GET {
+        return redirect(ROOT_REDIRECT, CacheControl::PublicLong);
+    }
+
+    let Some((handler, route)) = router.resolve(&path) else {
+        tracing::debug!("No route for {path}");
+        return Ok(Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Body::Empty)?);
+    };
+
+    // Handlers expect an absolute URL with dynamic path segments as query params, like Vercel
+    // provides.
+    let host = request
+        .headers()
+        .get(HOST)
+        .and_then(|host| host.to_str().ok())
+        .unwrap_or("localhost")
+        .to_owned();
+    let mut url = match url::Url::parse(&format!("http://{host}{}", request.uri())) {
+        Ok(url) => url,
+        Err(err) => return err_json(CoreError::from(err)),
+    };
+    for (key, value) in &route.params {
+        url.query_pairs_mut().append_pair(key, value);
+    }
+
+    let (mut parts, body) = request.into_parts();
+    parts.uri = url.as_str().parse()?;
+    let body = match Limited::new(body, MAX_BODY_SIZE).collect().await {
+        Ok(body) => body.to_bytes(),
+        Err(err) if err.is::<LengthLimitError>() => {
+            tracing::debug!("Request body for {path} exceeds {MAX_BODY_SIZE} bytes");
+            return Ok(Response::builder()
+                .status(StatusCode::PAYLOAD_TOO_LARGE)
+                .body(Body::Empty)?);
+        }
+        Err(err) => return Err(err),
+    };
+    let body = match String::from_utf8(body.to_vec()) {
+        Ok(text) if text.is_empty() => Body::Empty,
+        Ok(text) => Body::Text(text),
+        Err(err) => Body::Binary(err.into_bytes()),
+    };
+    let request = Request::from_parts(parts, body);
+
+    tracing::info!("{} {} -> {}", request.method(), path, route.pattern);
+    conditional(request, handler).await
+}
@@ -0,0 +1,441 @@
+use std::{future::Future, pin::Pin};
+
+use percent_encoding::percent_decode_str;
+use vercel_runtime::{Body, Error, Request, Response};
+
+use crate::handlers::{
+    batch, crates, github, golang, hackage, luarocks, mason, maven, npm, nuget, opam, openvsx,
+    packagist, purl, pypi, rubygems,
+};
+
+pub type HandlerFuture = Pin<Box<dyn Future<Output = Result<Response<Body>, Error>> + Send>>;
+pub type Handler = fn(Request) -> HandlerFuture;
+
+/// A route mirroring a Vercel function in `api/`, e.g. `/api/npm/[scope]/[package]/versions/all`.
+/// Dynamic `[segments]` are passed to the handler as query params, like Vercel does.
+pub struct Route {
+    pattern: &'static str,
+    handler: Handler,
+}
+
+/// A rewrite mirroring an entry in `vercel.json`, e.g. `/api/repo/:path*` -> `/api/github/:path*`.
+/// `:param` matches a single segment and `:param*` matches all remaining segments.
+pub struct Rewrite {
+    source: &'static str,
+    destination: &'static str,
+    /// Skip the rewrite if the first captured segment starts with this prefix.
+    unless_prefix: Option<&'static str>,
+}
+
+pub struct Router {
+    routes: Vec<Route>,
+    rewrites: Vec<Rewrite>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Match {
+    pub pattern: &'static str,
+    pub params: Vec<(String, String)>,
+}
+
+impl Route {
+    pub const fn new(pattern: &'static str, handler: Handler) -> Self {
+        Self { pattern, handler }
+    }
+
+    fn matches(&self, path: &str) -> Option<Vec<(String, String)>> {
+        let mut params = vec![];
+        let mut segments = path.trim_matches('/').split('/');
+        for pattern_segment in self.pattern.trim_matches('/').split('/') {
+            let segment = segments.next()?;
+            match pattern_segment
+                .strip_prefix('[')
+                .and_then(|s| s.strip_suffix(']'))
+            {
+                Some(_) if segment.is_empty() => return None,
+                Some(param) => params.push((
+                    param.to_owned(),
+                    percent_decode_str(segment).decode_utf8_lossy().into_owned(),
+                )),
+                None if pattern_segment != segment => return None,
+                None => {}
+            }
+        }
+        match segments.next() {
+            Some(_) => None,
+            None => Some(params),
+        }
+    }
+}
+
+impl Rewrite {
+    pub const fn new(source: &'static str, destination: &'static str) -> Self {
+        Self {
+            source,
+            destination,
+            unless_prefix: None,
+        }
+    }
+
+    pub const fn unless_prefix(mut self, prefix: &'static str) -> Self {
+        self.unless_prefix = Some(prefix);
+        self
+    }
+
+    fn apply(&self, path: &str) -> Option<String> {
+        let mut captures: Vec<(&str, String)> = vec![];
+        let mut segments = path.trim_matches('/').split('/').peekable();
+        for pattern_segment in self.source.trim_matches('/').split('/') {
+            if let Some(param) = pattern_segment
+                .strip_prefix(':')
+                .and_then(|s| s.strip_suffix('*'))
+            {
+                segments.peek().filter(|s| !s.is_empty())?;
+                captures.push((param, segments.by_ref().collect::<Vec<_>>().join("/")));
+            } else if let Some(param) = pattern_segment.strip_prefix(':') {
+                let segment = segments.next().filter(|s| !s.is_empty())?;
+                captures.push((param, segment.to_owned()));
+            } else if segments.next()? != pattern_segment {
+                return None;
+            }
+        }
+        if segments.next().is_some() {
+            return None;
+        }
+        if let (Some(prefix), Some((_, first))) = (self.unless_prefix, captures.first()) {
+            if first.starts_with(prefix) {
+                return None;
+            }
+        }
+
+        let destination = self
+            .destination
+            .split('/')
+            .map(|segment| {
+                let param = segment.trim_start_matches(':').trim_end_matches('*');
+                match captures
+                    .iter()
+                    .find(|(name, _)| segment.starts_with(':') && *name == param)
+                {
+                    Some((_, value)) => value.as_str(),
+                    None => segment,
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("/");
+        Some(destination)
+    }
+}
+
+impl Router {
+    /// Resolves the route for a request path, applying rewrites in order until a route matches.
+    pub fn resolve(&self, path: &str) -> Option<(Handler, Match)> {
+        let mut path = path.to_owned();
+        if let Some(resolved) = self.find_route(&path) {
+            return Some(resolved);
+        }
+        for rewrite in &self.rewrites {
+            if let Some(rewritten) = rewrite.apply(&path) {
+                tracing::debug!("Rewrote {path} to {rewritten}");
+                path = rewritten;
+                if let Some(resolved) = self.find_route(&path) {
+                    return Some(resolved);
+                }
+            }
+        }
+        None
+    }
+
+    fn find_route(&self, path: &str) -> Option<(Handler, Match)> {
+        self.routes.iter().find_map(|route| {
+            route.matches(path).map(|params| {
+                (
+                    route.handler,
+                    Match {
+                        pattern: route.pattern,
+                        params,
+                    },
+                )
+            })
+        })
+    }
+}
+
+impl Default for Router {
+    fn default() -> Self {
+        Self {
+            routes: vec![
+                Route::new("/api/batch", |req| Box::pin(batch::latest_versions(req))),
+                Route::new("/api/crate/[crate]/versions/all", |req| {
+                    Box::pin(crates::all_versions(req))
+                }),
+                Route::new("/api/crate/[crate]/versions/latest", |req| {
+                    Box::pin(crates::latest_version(req))
+                }),
+                Route::new("/api/crate/[crate]/versions/[version]", |req| {
+                    Box::pin(crates::version(req))
+                }),
+                Route::new("/api/github/[owner]/[name]/releases/all", |req| {
+                    Box::pin(github::all_releases(req))
+                }),
+                Route::new("/api/github/[owner]/[name]/releases/latest", |req| {
+                    Box::pin(github::latest_release(req))
+                }),
+                Route::new("/api/github/[owner]/[name]/releases/[release]", |req| {
+                    Box::pin(github::release(req))
+                }),
+                Route::new("/api/github/[owner]/[name]/tags/all", |req| {
+                    Box::pin(github::all_tags(req))
+                }),
+                Route::new("/api/github/[owner]/[name]/tags/latest", |req| {
+                    Box::pin(github::latest_tag(req))
+                }),
+                Route::new("/api/github/[owner]/[name]/tags/[tag]", |req| {
+                    Box::pin(github::tag(req))
+                }),
+                Route::new("/api/golang/[package]/versions/all", |req| {
+                    Box::pin(golang::all_versions(req))
+                }),
+                Route::new("/api/golang/[package]/versions/latest", |req| {
+                    Box::pin(golang::latest_version(req))
+                }),
+                Route::new("/api/golang/[package]/versions/[version]", |req| {
+                    Box::pin(golang::version(req))
+                }),
+                Route::new("/api/hackage/[package]/versions/all", |req| {
+                    Box::pin(hackage::all_versions(req))
+                }),
+                Route::new("/api/hackage/[package]/versions/latest", |req| {
+                    Box::pin(hackage::latest_version(req))
+                }),
+                Route::new("/api/hackage/[package]/versions/[version]", |req| {
+                    Box::pin(hackage::version(req))
+                }),
+                Route::new("/api/luarocks/[package]/versions/all", |req| {
+                    Box::pin(luarocks::all_versions(req))
+                }),
+                Route::new("/api/luarocks/[package]/versions/latest", |req| {
+                    Box::pin(luarocks::latest_version(req))
+                }),
+                Route::new("/api/luarocks/[package]/versions/[version]", |req| {
+                    Box::pin(luarocks::version(req))
+                }),
+                Route::new("/api/mason/sponsors", |req| Box::pin(mason::sponsors(req))),
+                Route::new("/api/mason/renovate/badge", |req| {
+                    Box::pin(mason::renovate_badge(req))
+                }),
+                Route::new("/api/maven/[group]/[artifact]/versions/all", |req| {
+                    Box::pin(maven::all_versions(req))
+                }),
+                Route::new("/api/maven/[group]/[artifact]/versions/latest", |req| {
+                    Box::pin(maven::latest_version(req))
+                }),
+                Route::new("/api/maven/[group]/[artifact]/versions/[version]", |req| {
+                    Box::pin(maven::version(req))
+                }),
+                Route::new("/api/npm/[scope]/[package]/versions/all", |req| {
+                    Box::pin(npm::all_versions(req))
+                }),
+                Route::new("/api/npm/[scope]/[package]/versions/latest", |req| {
+                    Box::pin(npm::latest_version(req))
+                }),
+                Route::new("/api/npm/[scope]/[package]/versions/[version]", |req| {
+                    Box::pin(npm::version(req))
+                }),
+                Route::new("/api/nuget/[package]/versions/all", |req| {
+                    Box::pin(nuget::all_versions(req))
+                }),
+                Route::new("/api/nuget/[package]/versions/latest", |req| {
+                    Box::pin(nuget::latest_version(req))
+                }),
+                Route::new("/api/nuget/[package]/versions/[version]", |req| {
+                    Box::pin(nuget::version(req))
+                }),
+                Route::new("/api/opam/[package]/versions/all", |req| {
+                    Box::pin(opam::all_versions(req))
+                }),
+                Route::new("/api/opam/[package]/versions/latest", |req| {
+                    Box::pin(opam::latest_version(req))
+                }),
+                Route::new("/api/opam/[package]/versions/[version]", |req| {
+                    Box::pin(opam::version(req))
+                }),
+                Route::new("/api/openvsx/[namespace]/[extension]/versions/all", |req| {
+                    Box::pin(openvsx::all_versions(req))
+                }),
+                Route::new(
+                    "/api/openvsx/[namespace]/[extension]/versions/latest",
+                    |req| Box::pin(openvsx::latest_version(req)),
+                ),
+                Route::new(
+                    "/api/openvsx/[namespace]/[extension]/versions/[version]",
+                    |req| Box::pin(openvsx::version(req)),
+                ),
+                Route::new("/api/packagist/[vendor]/[package]/versions/all", |req| {
+                    Box::pin(packagist::all_versions(req))
+                }),
+                Route::new("/api/packagist/[vendor]/[package]/versions/latest", |req| {
+                    Box::pin(packagist::latest_version(req))
+                }),
+                Route::new(
+                    "/api/packagist/[vendor]/[package]/versions/[version]",
+                    |req| Box::pin(packagist::version(req)),
+                ),
+                Route::new("/api/purl/[purl]/versions/all", |req| {
+                    Box::pin(purl::all_versions(req))
+                }),
+                Route::new("/api/purl/[purl]/versions/latest", |req| {
+                    Box::pin(purl::latest_version(req))
+                }),
+                Route::new("/api/purl/[purl]/versions/[version]", |req| {
+                    Box::pin(purl::version(req))
+                }),
+                Route::new("/api/pypi/[package]/versions/all", |req| {
+                    Box::pin(pypi::all_versions(req))
+                }),
+                Route::new("/api/pypi/[package]/versions/latest", |req| {
+                    Box::pin(pypi::latest_version(req))
+                }),
+                Route::new("/api/pypi/[package]/versions/[version]", |req| {
+                    Box::pin(pypi::version(req))
+                }),
+                Route::new("/api/rubygems/[gem]/versions/all", |req| {
+                    Box::pin(rubygems::all_versions(req))
+                }),
+                Route::new("/api/rubygems/[gem]/versions/latest", |req| {
+                    Box::pin(rubygems::latest_version(req))
+                }),
+                Route::new("/api/rubygems/[gem]/versions/[version]", |req| {
+                    Box::pin(rubygems::version(req))
+                }),
+            ],
+            // Keep in sync with the rewrites in vercel.json. The `latest-release` and `latest-tag`
+            // aliases come first here, as each rewrite is applied to the result of the previous
+            // ones, and `/api/repo/:path*` would have already moved them out of `/api/repo`.
+            rewrites: vec![
+                Rewrite::new(
+                    "/api/repo/:owner/:name/latest-release",
+                    "/api/github/:owner/:name/releases/latest",
+                ),
+                Rewrite::new(
+                    "/api/repo/:owner/:name/latest-tag",
+                    "/api/github/:owner/:name/tags/latest",
+                ),
+                Rewrite::new("/api/repo/:path*", "/api/github/:path*"),
+                Rewrite::new("/api/npm/:path*", "/api/npm/_/:path*").unless_prefix("@"),
+                Rewrite::new(
+                    "/api/npm/:scope/:package/latest-version",
+                    "/api/npm/:scope/:package/versions/latest",
+                ),
+            ],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolve(path: &str) -> Option<Match> {
+        Router::default().resolve(path).map(|(_, m)| m)
+    }
+
+    #[test]
+    fn should_match_dynamic_segments() {
+        assert_eq!(
+            Some(Match {
+                pattern: "/api/npm/[scope]/[package]/versions/[version]",
+                params: vec![
+                    ("scope".to_owned(), "@ansible".to_owned()),
+                    ("package".to_owned(), "ansible-language-server".to_owned()),
+                    ("version".to_owned(), "1.0.0".to_owned()),
+                ]
+            }),
+            resolve("/api/npm/@ansible/ansible-language-server/versions/1.0.0")
+        );
+        assert_eq!(
+            Some(Match {
+                pattern: "/api/golang/[package]/versions/all",
+                params: vec![("package".to_owned(), "golang.org/x/tools/gopls".to_owned())]
+            }),
+            resolve("/api/golang/golang.org%2Fx%2Ftools%2Fgopls/versions/all")
+        );
+    }
+
+    #[test]
+    fn should_prefer_static_segments() {
+        assert_eq!(
+            "/api/crate/[crate]/versions/latest",
+            resolve("/api/crate/crates_io_api/versions/latest")
+                .unwrap()
+                .pattern
+        );
+        assert_eq!(None, resolve("/api/crate/crates_io_api/versions"));
+        assert_eq!(
+            None,
+            resolve("/api/crate/crates_io_api/versions/latest/foo")
+        );
+    }
+
+    #[test]
+    fn should_apply_rewrites() {
+        assert_eq!(
+            Some(Match {
+                pattern: "/api/npm/[scope]/[package]/versions/all",
+                params: vec![
+                    ("scope".to_owned(), "_".to_owned()),
+                    (
+                        "package".to_owned(),
+                        "typescript-language-server".to_owned()
+                    ),
+                ]
+            }),
+            resolve("/api/npm/typescript-language-server/versions/all")
+        );
+        assert_eq!(
+            Some(Match {
+                pattern: "/api/npm/[scope]/[package]/versions/latest",
+                params: vec![
+                    ("scope".to_owned(), "_".to_owned()),
+                    (
+                        "package".to_owned(),
+                        "typescript-language-server".to_owned()
+                    ),
+                ]
+            }),
+            resolve("/api/npm/typescript-language-server/latest-version")
+        );
+        assert_eq!(
+            Some(Match {
+                pattern: "/api/github/[owner]/[name]/tags/[tag]",
+                params: vec![
+                    ("owner".to_owned(), "sumneko".to_owned()),
+                    ("name".to_owned(), "vscode-lua".to_owned()),
+                    ("tag".to_owned(), "v3.6.4".to_owned()),
+                ]
+            }),
+            resolve("/api/repo/sumneko/vscode-lua/tags/v3.6.4")
+        );
+        assert_eq!(
+            Some(Match {
+                pattern: "/api/github/[owner]/[name]/releases/latest",
+                params: vec![
+                    ("owner".to_owned(), "sumneko".to_owned()),
+                    ("name".to_owned(), "vscode-lua".to_owned()),
+                ]
+            }),
+            resolve("/api/repo/sumneko/vscode-lua/latest-release")
+        );
+        assert_eq!(
+            Some(Match {
+                pattern: "/api/github/[owner]/[name]/tags/latest",
+                params: vec![
+                    ("owner".to_owned(), "sumneko".to_owned()),
+                    ("name".to_owned(), "vscode-lua".to_owned()),
+                ]
+            }),
+            resolve("/api/repo/sumneko/vscode-lua/latest-tag")
+        );
+        assert_eq!(None, resolve("/api/npm/@scope/versions/all"));
+    }
+}
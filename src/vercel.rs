@@ -1,21 +1,37 @@
+use std::future::Future;
+
 use http::{
-    header::{CACHE_CONTROL, CONTENT_TYPE, LOCATION},
-    HeaderValue, Response, StatusCode,
+    header::{
+        CACHE_CONTROL, CONTENT_TYPE, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED,
+        LOCATION, RETRY_AFTER,
+    },
+    HeaderValue, Method, Response, StatusCode,
 };
 use serde::Serialize;
 use vercel_runtime::{Body, Error as VercelError, Request};
 
-use crate::{errors::ApiError, CacheControl, ErrResponse};
+use crate::{
+    errors::ApiError,
+    http::{stable_hash, validators::track_last_modified},
+    registry::Registry,
+    CacheControl, ErrResponse, QueryParams,
+};
 
 pub fn err_json<T: ApiError>(error: T) -> Result<Response<Body>, VercelError> {
     tracing::error!(%error, "API error");
-    json_response(
+    let mut response = json_response(
         error.status_code(),
         CacheControl::NoStore,
         &ErrResponse {
             message: error.to_string(),
         },
-    )
+    )?;
+    if let Some(retry_after) = error.retry_after() {
+        // Round up, so clients don't retry right before the limit resets.
+        let seconds = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+        response.headers_mut().insert(RETRY_AFTER, seconds.into());
+    }
+    Ok(response)
 }
 
 pub fn ok_json<T: Serialize>(data: T, cache: CacheControl) -> Result<Response<Body>, VercelError> {
@@ -30,18 +46,194 @@ pub fn redirect<S: AsRef<str>>(to: S, cache: CacheControl) -> Result<Response<Bo
         .body(Body::Empty)?)
 }
 
+pub fn method_not_allowed() -> Result<Response<Body>, VercelError> {
+    Ok(Response::builder()
+        .status(StatusCode::METHOD_NOT_ALLOWED)
+        .body(Body::Empty)?)
+}
+
 pub fn json_response<T: Serialize>(
     status: StatusCode,
     cache: CacheControl,
     data: &T,
 ) -> Result<Response<Body>, VercelError> {
+    let body = serde_json::to_string_pretty(data)?;
     Ok(Response::builder()
         .status(status)
         .header(CONTENT_TYPE, "application/json")
         .header(CACHE_CONTROL, cache.get_header())
-        .body(Body::Text(serde_json::to_string_pretty(data)?))?)
+        .header(ETAG, format!("\"{:016x}\"", stable_hash(body.as_bytes())))
+        .body(Body::Text(body))?)
+}
+
+/// Whether an `If-None-Match` header value matches `etag`, using the weak comparison function.
+fn etag_matches(if_none_match: &str, etag: &str) -> bool {
+    let opaque_tag = |tag: &str| tag.trim().trim_start_matches("W/").to_owned();
+    if_none_match.trim() == "*"
+        || if_none_match
+            .split(',')
+            .any(|candidate| opaque_tag(candidate) == opaque_tag(etag))
+}
+
+/// Runs `handler`, adding the most recent upstream `Last-Modified` date to successful responses
+/// and answering requests whose `If-None-Match` (or, failing that, `If-Modified-Since`) validator
+/// still matches with `304 Not Modified`.
+pub async fn conditional<H, F>(request: Request, handler: H) -> Result<Response<Body>, VercelError>
+where
+    H: FnOnce(Request) -> F,
+    F: Future<Output = Result<Response<Body>, VercelError>>,
+{
+    let is_get = request.method() == Method::GET;
+    let if_none_match = request
+        .headers()
+        .get(IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .map(ToOwned::to_owned);
+    let if_modified_since = request
+        .headers()
+        .get(IF_MODIFIED_SINCE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| httpdate::parse_http_date(value).ok());
+
+    let (response, last_modified) = track_last_modified(handler(request)).await;
+    let mut response = response?;
+    if response.status() != StatusCode::OK {
+        return Ok(response);
+    }
+    if let Some(last_modified) = last_modified {
+        response.headers_mut().insert(
+            LAST_MODIFIED,
+            HeaderValue::from_str(&httpdate::fmt_http_date(last_modified))?,
+        );
+    }
+
+    let etag = response
+        .headers()
+        .get(ETAG)
+        .and_then(|value| value.to_str().ok());
+    let is_not_modified = match (if_none_match, etag) {
+        (Some(if_none_match), Some(etag)) => etag_matches(&if_none_match, etag),
+        (Some(_), None) => false,
+        (None, _) => matches!(
+            (if_modified_since, last_modified),
+            (Some(since), Some(last_modified)) if last_modified <= since
+        ),
+    };
+    if !is_get || !is_not_modified {
+        return Ok(response);
+    }
+
+    let mut not_modified = Response::builder().status(StatusCode::NOT_MODIFIED);
+    for header in [CACHE_CONTROL, ETAG, LAST_MODIFIED] {
+        if let Some(value) = response.headers().get(&header) {
+            not_modified = not_modified.header(header, value);
+        }
+    }
+    Ok(not_modified.body(Body::Empty)?)
 }
 
 pub fn parse_url(request: &Request) -> Result<url::Url, crate::errors::CoreError> {
     Ok(url::Url::parse(&request.uri().to_string())?)
 }
+
+/// Handles a `versions/all` request for any [`Registry`].
+pub async fn registry_all_versions<R>(
+    request: &Request,
+    registry: &R,
+    cache: CacheControl,
+) -> Result<Response<Body>, VercelError>
+where
+    R: Registry,
+    for<'a> R::Package: TryFrom<&'a QueryParams>,
+    for<'a> <R::Package as TryFrom<&'a QueryParams>>::Error: ApiError,
+{
+    if request.method() != Method::GET {
+        return method_not_allowed();
+    }
+
+    let url = parse_url(request)?;
+    let query_params: QueryParams = (&url).into();
+    let package = match R::Package::try_from(&query_params) {
+        Ok(package) => package,
+        Err(err) => return err_json(err),
+    };
+
+    match registry.get_all_versions(&package).await {
+        Ok(versions) => ok_json(versions, cache),
+        Err(err) => err_json(err),
+    }
+}
+
+/// Handles a `versions/latest` request for any [`Registry`].
+pub async fn registry_latest_version<R>(
+    request: &Request,
+    registry: &R,
+    cache: CacheControl,
+) -> Result<Response<Body>, VercelError>
+where
+    R: Registry,
+    for<'a> R::Package: TryFrom<&'a QueryParams>,
+    for<'a> <R::Package as TryFrom<&'a QueryParams>>::Error: ApiError,
+{
+    if request.method() != Method::GET {
+        return method_not_allowed();
+    }
+
+    let url = parse_url(request)?;
+    let query_params: QueryParams = (&url).into();
+    let package = match R::Package::try_from(&query_params) {
+        Ok(package) => package,
+        Err(err) => return err_json(err),
+    };
+
+    match registry.get_latest_version(&package).await {
+        Ok(version) => ok_json(version, cache),
+        Err(err) => err_json(err),
+    }
+}
+
+/// Handles a `versions/[version]` request for any [`Registry`].
+pub async fn registry_version<R>(
+    request: &Request,
+    registry: &R,
+    cache: CacheControl,
+) -> Result<Response<Body>, VercelError>
+where
+    R: Registry,
+    for<'a> R::Package: TryFrom<&'a QueryParams>,
+    for<'a> <R::Package as TryFrom<&'a QueryParams>>::Error: ApiError,
+{
+    if request.method() != Method::GET {
+        return method_not_allowed();
+    }
+
+    let url = parse_url(request)?;
+    let query_params: QueryParams = (&url).into();
+    let package = match R::Package::try_from(&query_params) {
+        Ok(package) => package,
+        Err(err) => return err_json(err),
+    };
+    let version = query_params
+        .get("version")
+        .expect("No [version] query param");
+
+    match registry.get_version(&package, version).await {
+        Ok(version) => ok_json(version, cache),
+        Err(err) => err_json(err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_match_etags() {
+        assert!(etag_matches("\"abc\"", "\"abc\""));
+        assert!(etag_matches("W/\"abc\"", "\"abc\""));
+        assert!(etag_matches("\"xyz\", \"abc\"", "\"abc\""));
+        assert!(etag_matches("*", "\"abc\""));
+        assert!(!etag_matches("\"xyz\"", "\"abc\""));
+        assert!(!etag_matches("", "\"abc\""));
+    }
+}
@@ -0,0 +1,251 @@
+//! Version parsing and ordering as done by [Composer], which normalizes versions to four numeric
+//! components followed by a stability modifier, e.g. `v1.2-beta.3` to `1.2.0.0-beta3`.
+//!
+//! [Composer]: https://getcomposer.org/doc/articles/versions.md
+
+use std::{cmp::Ordering, str::FromStr};
+
+use thiserror::Error;
+
+use super::Version;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Stability {
+    Dev,
+    Alpha,
+    Beta,
+    ReleaseCandidate,
+    Stable,
+    Patch,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComposerVersion {
+    pub release: [u64; 4],
+    pub stability: Stability,
+    pub stability_number: Vec<u64>,
+    /// Whether a stability modifier is followed by `-dev`, e.g. `1.0.0-beta2-dev`, which sorts
+    /// before the modifier itself. Plain development versions such as `1.0.0-dev` have the
+    /// [`Stability::Dev`] stability instead.
+    pub dev: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("Invalid Composer version {0:?}.")]
+pub struct InvalidVersion(String);
+
+/// Longer keywords come first, so that e.g. `beta` isn't parsed as `b` followed by `eta`.
+const STABILITIES: &[(&str, Stability)] = &[
+    ("stable", Stability::Stable),
+    ("beta", Stability::Beta),
+    ("b", Stability::Beta),
+    ("rc", Stability::ReleaseCandidate),
+    ("alpha", Stability::Alpha),
+    ("a", Stability::Alpha),
+    ("patch", Stability::Patch),
+    ("pl", Stability::Patch),
+    ("p", Stability::Patch),
+];
+
+impl FromStr for ComposerVersion {
+    type Err = InvalidVersion;
+
+    fn from_str(version: &str) -> Result<Self, Self::Err> {
+        let invalid = || InvalidVersion(version.to_owned());
+        let lowercase = version.trim().to_lowercase();
+        // Build metadata doesn't affect precedence.
+        let normalized = lowercase
+            .split_once('+')
+            .map_or(lowercase.as_str(), |(version, _)| version);
+        let normalized = normalized.strip_prefix('v').unwrap_or(normalized);
+
+        let numeric_len = normalized
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(normalized.len());
+        let (numeric, modifier) = normalized.split_at(numeric_len);
+        // A trailing `.` separates the modifier, as in `1.0.0.beta1`.
+        let numeric = numeric.strip_suffix('.').unwrap_or(numeric);
+        let components = numeric
+            .split('.')
+            .map(|component| component.parse().ok())
+            .collect::<Option<Vec<u64>>>()
+            .filter(|components| components.len() <= 4)
+            .ok_or_else(invalid)?;
+        let mut release = [0; 4];
+        release[..components.len()].copy_from_slice(&components);
+
+        let modifier = modifier.trim_start_matches(['.', '-', '_']);
+        let (modifier, dev) = match modifier.strip_suffix("dev") {
+            Some(modifier) => (modifier.strip_suffix(['.', '-']).unwrap_or(modifier), true),
+            None => (modifier, false),
+        };
+        if modifier.is_empty() {
+            return Ok(Self {
+                release,
+                stability: if dev {
+                    Stability::Dev
+                } else {
+                    Stability::Stable
+                },
+                stability_number: Vec::new(),
+                dev: false,
+            });
+        }
+
+        let (keyword, stability) = STABILITIES
+            .iter()
+            .find(|(keyword, _)| modifier.starts_with(keyword))
+            .ok_or_else(invalid)?;
+        let mut rest = &modifier[keyword.len()..];
+        let mut stability_number = Vec::new();
+        while !rest.is_empty() {
+            rest = rest.strip_prefix(['.', '-']).unwrap_or(rest);
+            let len = rest
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(rest.len());
+            stability_number.push(rest[..len].parse().map_err(|_| invalid())?);
+            rest = &rest[len..];
+        }
+
+        Ok(Self {
+            release,
+            stability: *stability,
+            stability_number,
+            dev,
+        })
+    }
+}
+
+impl Ord for ComposerVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.release
+            .cmp(&other.release)
+            .then_with(|| self.stability.cmp(&other.stability))
+            .then_with(|| self.stability_number.cmp(&other.stability_number))
+            .then_with(|| other.dev.cmp(&self.dev))
+    }
+}
+
+impl PartialOrd for ComposerVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Version for ComposerVersion {
+    fn is_prerelease(&self) -> bool {
+        self.stability < Stability::Stable || self.dev
+    }
+}
+
+/// Sorts `versions` in DESCENDING order. Versions that Composer can't normalize, such as
+/// `dev-main` branches, are sorted last, in reverse lexical order.
+pub fn sort_desc(versions: &mut [String]) {
+    super::sort_desc::<ComposerVersion>(versions);
+}
+
+/// Returns the greatest stable version, or the greatest pre-release if there are only
+/// pre-releases. With `include_prerelease`, returns the greatest version.
+pub fn latest<'a, I: IntoIterator<Item = &'a str>>(
+    versions: I,
+    include_prerelease: bool,
+) -> Option<&'a str> {
+    super::latest::<ComposerVersion, _>(versions, include_prerelease)
+}
+
+/// Whether `version` is less stable than `stable`. Like Composer, branches such as `dev-main` and
+/// `1.x-dev` are considered development versions.
+pub fn is_prerelease(version: &str) -> bool {
+    let lowercase = version.trim().to_lowercase();
+    lowercase.starts_with("dev-")
+        || lowercase.ends_with("-dev")
+        || super::is_prerelease::<ComposerVersion>(version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(version: &str) -> ComposerVersion {
+        version
+            .parse()
+            .unwrap_or_else(|err| panic!("{version}: {err}"))
+    }
+
+    /// In ascending order.
+    const ORDERED: &[&str] = &[
+        "1.0.0-dev",
+        "1.0.0-alpha1",
+        "1.0.0-alpha.2",
+        "1.0.0-beta2-dev",
+        "1.0.0-beta2",
+        "1.0.0-RC1",
+        "1.0.0-rc2",
+        "v1.0.0",
+        "1.0.0-patch1",
+        "1.0.0-pl2",
+        "1.0.1",
+        "1.1",
+        "1.10.0",
+        "2.0.0.1",
+    ];
+
+    #[test]
+    fn should_order_versions() {
+        for pair in ORDERED.windows(2) {
+            assert!(parse(pair[0]) < parse(pair[1]), "{} < {}", pair[0], pair[1]);
+        }
+    }
+
+    #[test]
+    fn should_normalize_versions() {
+        assert_eq!(parse("1.0.0.0"), parse("v1.0"));
+        assert_eq!(parse("1.0.0-beta.1"), parse("1.0.0b1"));
+        assert_eq!(parse("1.0.0"), parse("1.0.0-stable"));
+        assert_eq!(parse("1.0.0"), parse("1.0.0+20230101"));
+        assert_eq!(parse("1.0.0-rc1-dev"), parse("1.0.0RC1.dev"));
+    }
+
+    #[test]
+    fn should_reject_invalid_versions() {
+        for invalid in [
+            "",
+            "dev-main",
+            "1.0.x-dev",
+            "1.0.0.0.0",
+            "1.0-pre1",
+            "latest",
+        ] {
+            assert!(invalid.parse::<ComposerVersion>().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn should_detect_prereleases() {
+        assert!(parse("1.0.0-dev").is_prerelease());
+        assert!(parse("1.0.0-RC1").is_prerelease());
+        assert!(parse("1.0.0-patch1-dev").is_prerelease());
+        assert!(!parse("v1.0.0").is_prerelease());
+        assert!(!parse("1.0.0-p1").is_prerelease());
+        assert!(is_prerelease("dev-main"));
+        assert!(is_prerelease("1.x-dev"));
+        assert!(!is_prerelease("v1.0.0"));
+    }
+
+    #[test]
+    fn should_sort_versions_desc() {
+        let mut versions: Vec<String> = ["v1.3.0", "dev-main", "v1.10.0", "v1.10.0-RC1", "v1.9.0"]
+            .into_iter()
+            .map(ToOwned::to_owned)
+            .collect();
+        sort_desc(&mut versions);
+        assert_eq!(
+            vec!["v1.10.0", "v1.10.0-RC1", "v1.9.0", "v1.3.0", "dev-main"],
+            versions
+        );
+        assert_eq!(
+            Some("v1.10.0"),
+            latest(versions.iter().map(String::as_str), false)
+        );
+    }
+}
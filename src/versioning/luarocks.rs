@@ -0,0 +1,224 @@
+//! Version parsing and ordering as done by LuaRocks' [`vers`] module, where versions are
+//! followed by a rockspec revision, e.g. `1.0rc1-1` < `1.0-1` < `1.0-2` < `1.0.1-1` < `scm-1`.
+//!
+//! [`vers`]: https://github.com/luarocks/luarocks/blob/main/src/luarocks/core/vers.lua
+
+use std::{cmp::Ordering, str::FromStr};
+
+use thiserror::Error;
+
+use super::Version;
+
+/// The weights of well-known words, which replace the version number at their position.
+fn delta(word: &str) -> Option<f64> {
+    match word {
+        "dev" => Some(120000000.0),
+        "scm" => Some(110000000.0),
+        "cvs" => Some(100000000.0),
+        "rc" => Some(-1000.0),
+        "pre" => Some(-10000.0),
+        "beta" => Some(-100000.0),
+        "alpha" => Some(-1000000.0),
+        _ => None,
+    }
+}
+
+fn is_separator(c: char) -> bool {
+    matches!(c, '.' | '-' | '_')
+}
+
+#[derive(Debug, Clone)]
+pub struct LuaRocksVersion {
+    components: Vec<f64>,
+    /// The rockspec revision, e.g. `1` in `1.0-1`.
+    revision: Option<u64>,
+    /// Whether the version is a pre-release, e.g. `1.0rc1`, or a development version, e.g. `scm`.
+    is_prerelease: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("Invalid LuaRocks version {0:?}.")]
+pub struct InvalidVersion(String);
+
+impl FromStr for LuaRocksVersion {
+    type Err = InvalidVersion;
+
+    fn from_str(version: &str) -> Result<Self, Self::Err> {
+        let invalid = || InvalidVersion(version.to_owned());
+        let trimmed = version.trim();
+        if trimmed.is_empty() {
+            return Err(invalid());
+        }
+        let (mut rest, revision) = match trimmed.rsplit_once('-') {
+            Some((main, revision))
+                if !revision.is_empty() && revision.bytes().all(|b| b.is_ascii_digit()) =>
+            {
+                (main, Some(revision.parse().map_err(|_| invalid())?))
+            }
+            Some(_) | None => (trimmed, None),
+        };
+
+        let mut components: Vec<f64> = Vec::new();
+        let mut is_prerelease = false;
+        // Words replace the component at their position, and a number following a word is added
+        // to it as a fraction, e.g. `rc2` is `-1000 + 2/100000`.
+        let mut after_word = false;
+        while !rest.is_empty() {
+            let number_len = rest
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(rest.len());
+            let token_len = match number_len {
+                0 => rest
+                    .find(|c: char| !c.is_ascii_alphabetic())
+                    .unwrap_or(rest.len()),
+                number_len => number_len,
+            };
+            if token_len == 0 {
+                return Err(invalid());
+            }
+            let (token, remaining) = rest.split_at(token_len);
+            match number_len {
+                0 => {
+                    let weight = match delta(token) {
+                        Some(weight) => {
+                            is_prerelease = true;
+                            weight
+                        }
+                        None => f64::from(token.as_bytes()[0]) / 1000.0,
+                    };
+                    match after_word {
+                        true => *components.last_mut().expect("Words push a component.") = weight,
+                        false => components.push(weight),
+                    }
+                    after_word = true;
+                }
+                _ => {
+                    let number: f64 = token.parse().map_err(|_| invalid())?;
+                    match after_word {
+                        true => {
+                            *components.last_mut().expect("Words push a component.") +=
+                                number / 100000.0
+                        }
+                        false => components.push(number),
+                    }
+                    after_word = false;
+                }
+            }
+            rest = remaining.trim_start_matches(is_separator);
+        }
+
+        Ok(Self {
+            components,
+            revision,
+            is_prerelease,
+        })
+    }
+}
+
+impl Ord for LuaRocksVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (0..self.components.len().max(other.components.len()))
+            .map(|index| {
+                let lhs = self.components.get(index).copied().unwrap_or_default();
+                let rhs = other.components.get(index).copied().unwrap_or_default();
+                lhs.total_cmp(&rhs)
+            })
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(Ordering::Equal)
+            // Versions without a revision sort before those with one.
+            .then_with(|| self.revision.cmp(&other.revision))
+    }
+}
+
+impl PartialOrd for LuaRocksVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for LuaRocksVersion {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for LuaRocksVersion {}
+
+impl Version for LuaRocksVersion {
+    fn is_prerelease(&self) -> bool {
+        self.is_prerelease
+    }
+}
+
+/// Sorts `versions` in DESCENDING order.
+pub fn sort_desc(versions: &mut [String]) {
+    super::sort_desc::<LuaRocksVersion>(versions);
+}
+
+/// Returns the greatest version that isn't a pre-release or development version, or the greatest
+/// of those if there are no others. With `include_prerelease`, returns the greatest version.
+pub fn latest<'a, I: IntoIterator<Item = &'a str>>(
+    versions: I,
+    include_prerelease: bool,
+) -> Option<&'a str> {
+    super::latest::<LuaRocksVersion, _>(versions, include_prerelease)
+}
+
+pub fn is_prerelease(version: &str) -> bool {
+    super::is_prerelease::<LuaRocksVersion>(version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(version: &str) -> LuaRocksVersion {
+        version
+            .parse()
+            .unwrap_or_else(|err| panic!("{version}: {err}"))
+    }
+
+    #[test]
+    fn should_order_versions() {
+        // In ascending order, partly from LuaRocks' `vers_spec`.
+        let versions = [
+            "0.9-1",
+            "1.0alpha-1",
+            "1.0beta-1",
+            "1.0pre-1",
+            "1.0rc1-1",
+            "1.0rc2-1",
+            "1.0",
+            "1.0-1",
+            "1.0-2",
+            "1.0.1-1",
+            "1.2.3-1",
+            "1.10-1",
+            "cvs-1",
+            "scm-1",
+            "dev-1",
+        ];
+        for pair in versions.windows(2) {
+            assert!(parse(pair[0]) < parse(pair[1]), "{} < {}", pair[0], pair[1]);
+        }
+    }
+
+    #[test]
+    fn should_normalize_versions() {
+        assert_eq!(parse("1.0-1"), parse("1.0.0-1"));
+        assert_eq!(parse("1_0-1"), parse("1.0-1"));
+        assert_eq!(parse(" 1.0-1 "), parse("1.0-1"));
+        assert!("".parse::<LuaRocksVersion>().is_err());
+        assert!("1.0+1-1".parse::<LuaRocksVersion>().is_err());
+    }
+
+    #[test]
+    fn should_prefer_stable_latest_version() {
+        let versions = ["0.26.0-1", "0.26.1-1", "1.0.0rc1-1", "scm-1"];
+        assert_eq!(Some("0.26.1-1"), latest(versions, false));
+        assert_eq!(Some("scm-1"), latest(versions, true));
+        assert!(is_prerelease("1.0.0rc1-1"));
+        assert!(is_prerelease("dev-1"));
+        assert!(!is_prerelease("1.0b-1"));
+    }
+}
@@ -0,0 +1,390 @@
+//! Version parsing and ordering as done by Maven's [`ComparableVersion`], which splits versions
+//! into numbers and qualifiers, e.g. `1.0-alpha-1` < `1.0-beta` < `1.0-RC1` < `1.0` < `1.0-sp1`.
+//!
+//! [`ComparableVersion`]: https://maven.apache.org/ref/current/maven-artifact/apidocs/org/apache/maven/artifact/versioning/ComparableVersion.html
+
+use std::{cmp::Ordering, str::FromStr};
+
+use thiserror::Error;
+
+use super::Version;
+
+/// Well-known qualifiers in ascending order. The empty qualifier is a release, and unknown
+/// qualifiers sort after all of these, in lexical order.
+const QUALIFIERS: &[&str] = &["alpha", "beta", "milestone", "rc", "snapshot", "", "sp"];
+
+fn qualifier_rank(qualifier: &str) -> (usize, &str) {
+    match QUALIFIERS.iter().position(|known| *known == qualifier) {
+        Some(index) => (index, ""),
+        None => (QUALIFIERS.len(), qualifier),
+    }
+}
+
+fn release_rank() -> (usize, &'static str) {
+    qualifier_rank("")
+}
+
+fn normalize_qualifier(qualifier: &str, followed_by_digit: bool) -> String {
+    let qualifier = match (followed_by_digit, qualifier) {
+        (true, "a") => "alpha",
+        (true, "b") => "beta",
+        (true, "m") => "milestone",
+        (_, "ga" | "final" | "release") => "",
+        (_, "cr") => "rc",
+        (_, qualifier) => qualifier,
+    };
+    qualifier.to_owned()
+}
+
+/// Numbers are kept as digits without leading zeros, as Maven doesn't bound them.
+fn digits(number: &str) -> String {
+    number.trim_start_matches('0').to_owned()
+}
+
+fn cmp_numbers(lhs: &str, rhs: &str) -> Ordering {
+    lhs.len().cmp(&rhs.len()).then_with(|| lhs.cmp(rhs))
+}
+
+#[derive(Debug, Clone)]
+enum Item {
+    Number(String),
+    Qualifier(String),
+    /// A qualifier immediately followed by a number, e.g. `rc1` or `beta-2`.
+    Combination(String, String),
+    /// The items following a `-`, or a transition between digits and letters.
+    List(Vec<Item>),
+}
+
+impl Item {
+    fn parse(buf: &str, is_digit: bool, is_combination: bool) -> Self {
+        if is_combination {
+            let buf = buf.replace('-', "");
+            let (qualifier, number) =
+                buf.split_at(buf.find(|c: char| c.is_ascii_digit()).unwrap_or(buf.len()));
+            return Self::Combination(normalize_qualifier(qualifier, true), digits(number));
+        }
+        match is_digit {
+            true => Self::Number(digits(buf)),
+            false => Self::Qualifier(normalize_qualifier(buf, false)),
+        }
+    }
+
+    /// Whether the item is equivalent to its absence, e.g. the trailing `0` in `1.0`.
+    fn is_null(&self) -> bool {
+        match self {
+            Self::Number(digits) => digits.is_empty(),
+            Self::Qualifier(qualifier) => qualifier.is_empty(),
+            Self::Combination(..) => false,
+            Self::List(items) => items.is_empty(),
+        }
+    }
+
+    fn is_prerelease(&self) -> bool {
+        match self {
+            Self::Number(_) => false,
+            Self::Qualifier(qualifier) | Self::Combination(qualifier, _) => {
+                qualifier_rank(qualifier) < release_rank()
+            }
+            Self::List(items) => items.iter().any(Item::is_prerelease),
+        }
+    }
+
+    /// Compares against another item, where `None` stands for a missing item, e.g. when
+    /// comparing `1.0` to `1.0.1`.
+    fn cmp(&self, other: Option<&Item>) -> Ordering {
+        use Item::*;
+        match (self, other) {
+            (Number(digits), None) => cmp_numbers(digits, ""),
+            (Qualifier(qualifier), None) | (Combination(qualifier, _), None) => {
+                qualifier_rank(qualifier).cmp(&release_rank())
+            }
+            (List(items), None) => items.first().map_or(Ordering::Equal, |item| item.cmp(None)),
+
+            (Number(lhs), Some(Number(rhs))) => cmp_numbers(lhs, rhs),
+            (Number(_), Some(_)) => Ordering::Greater,
+
+            (Qualifier(_), Some(Number(_) | List(_))) => Ordering::Less,
+            (Qualifier(lhs), Some(Qualifier(rhs))) => qualifier_rank(lhs).cmp(&qualifier_rank(rhs)),
+            (Qualifier(lhs), Some(Combination(rhs, _))) => qualifier_rank(lhs)
+                .cmp(&qualifier_rank(rhs))
+                .then(Ordering::Less),
+
+            (Combination(..), Some(Number(_) | List(_))) => Ordering::Less,
+            (Combination(lhs, _), Some(Qualifier(rhs))) => qualifier_rank(lhs)
+                .cmp(&qualifier_rank(rhs))
+                .then(Ordering::Greater),
+            (Combination(lhs, lhs_number), Some(Combination(rhs, rhs_number))) => {
+                qualifier_rank(lhs)
+                    .cmp(&qualifier_rank(rhs))
+                    .then_with(|| cmp_numbers(lhs_number, rhs_number))
+            }
+
+            (List(_), Some(Number(_))) => Ordering::Less,
+            (List(_), Some(Qualifier(_) | Combination(..))) => Ordering::Greater,
+            (List(lhs), Some(List(rhs))) => cmp_lists(lhs, rhs),
+        }
+    }
+}
+
+fn cmp_lists(lhs: &[Item], rhs: &[Item]) -> Ordering {
+    (0..lhs.len().max(rhs.len()))
+        .map(|index| match (lhs.get(index), rhs.get(index)) {
+            (Some(lhs), rhs) => lhs.cmp(rhs),
+            (None, Some(rhs)) => rhs.cmp(None).reverse(),
+            (None, None) => Ordering::Equal,
+        })
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or(Ordering::Equal)
+}
+
+/// Removes trailing null items, which don't affect the ordering, e.g. `1.0.0.Final` to `1`.
+fn normalize(items: &mut Vec<Item>) {
+    for index in (0..items.len()).rev() {
+        if items[index].is_null() {
+            items.remove(index);
+        } else if !matches!(items[index], Item::List(_)) {
+            break;
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MavenVersion {
+    items: Vec<Item>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("Invalid Maven version {0:?}.")]
+pub struct InvalidVersion(String);
+
+impl FromStr for MavenVersion {
+    type Err = InvalidVersion;
+
+    fn from_str(version: &str) -> Result<Self, Self::Err> {
+        let lowercase = version.trim().to_lowercase();
+        if lowercase.is_empty() {
+            return Err(InvalidVersion(version.to_owned()));
+        }
+
+        // Every `-`, and every transition from digits to letters, starts a nested list. As lists
+        // only ever nest in their parent's last position, they're collected as levels first.
+        let mut levels: Vec<Vec<Item>> = vec![Vec::new()];
+        let mut is_digit = false;
+        let mut is_combination = false;
+        let mut start = 0;
+        for (index, c) in lowercase.char_indices() {
+            let list = levels.last_mut().expect("There's always a list.");
+            match c {
+                '.' => {
+                    list.push(match index == start {
+                        true => Item::Number(String::new()),
+                        false => Item::parse(&lowercase[start..index], is_digit, is_combination),
+                    });
+                    is_combination = false;
+                    start = index + 1;
+                }
+                '-' => {
+                    if index == start {
+                        list.push(Item::Number(String::new()));
+                    } else {
+                        // `X-1` is treated as `X1`.
+                        let next_is_digit =
+                            lowercase[index + 1..].starts_with(|c: char| c.is_ascii_digit());
+                        if !is_digit && next_is_digit {
+                            is_combination = true;
+                            continue;
+                        }
+                        list.push(Item::parse(
+                            &lowercase[start..index],
+                            is_digit,
+                            is_combination,
+                        ));
+                    }
+                    start = index + 1;
+                    if !list.is_empty() {
+                        levels.push(Vec::new());
+                    }
+                    is_combination = false;
+                }
+                '0'..='9' => {
+                    if !is_digit && index > start {
+                        is_combination = true;
+                        if !list.is_empty() {
+                            levels.push(Vec::new());
+                        }
+                    }
+                    is_digit = true;
+                }
+                _ => {
+                    if is_digit && index > start {
+                        list.push(Item::parse(&lowercase[start..index], true, is_combination));
+                        start = index;
+                        levels.push(Vec::new());
+                        is_combination = false;
+                    }
+                    is_digit = false;
+                }
+            }
+        }
+        if lowercase.len() > start {
+            // Like `-X`, `.X` starts a nested list for any qualifier `X`.
+            if !is_digit && levels.last().is_some_and(|list| !list.is_empty()) {
+                levels.push(Vec::new());
+            }
+            levels
+                .last_mut()
+                .expect("There's always a list.")
+                .push(Item::parse(&lowercase[start..], is_digit, is_combination));
+        }
+
+        let mut items = levels.pop().unwrap_or_default();
+        normalize(&mut items);
+        while let Some(mut parent) = levels.pop() {
+            parent.push(Item::List(items));
+            normalize(&mut parent);
+            items = parent;
+        }
+        Ok(Self { items })
+    }
+}
+
+impl Ord for MavenVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        cmp_lists(&self.items, &other.items)
+    }
+}
+
+impl PartialOrd for MavenVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for MavenVersion {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for MavenVersion {}
+
+impl Version for MavenVersion {
+    /// Whether the version has a qualifier that sorts before a release, e.g. `-M1` or
+    /// `-SNAPSHOT`. Other qualifiers, such as Guava's `-jre`, are not considered pre-releases.
+    fn is_prerelease(&self) -> bool {
+        self.items.iter().any(Item::is_prerelease)
+    }
+}
+
+/// Sorts `versions` in DESCENDING order.
+pub fn sort_desc(versions: &mut [String]) {
+    super::sort_desc::<MavenVersion>(versions);
+}
+
+/// Returns the greatest version that isn't a pre-release, or the greatest pre-release if there are
+/// only pre-releases. With `include_prerelease`, returns the greatest version.
+pub fn latest<'a, I: IntoIterator<Item = &'a str>>(
+    versions: I,
+    include_prerelease: bool,
+) -> Option<&'a str> {
+    super::latest::<MavenVersion, _>(versions, include_prerelease)
+}
+
+pub fn is_prerelease(version: &str) -> bool {
+    super::is_prerelease::<MavenVersion>(version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(version: &str) -> MavenVersion {
+        version
+            .parse()
+            .unwrap_or_else(|err| panic!("{version}: {err}"))
+    }
+
+    fn assert_ordered(versions: &[&str]) {
+        for pair in versions.windows(2) {
+            assert!(parse(pair[0]) < parse(pair[1]), "{} < {}", pair[0], pair[1]);
+        }
+    }
+
+    #[test]
+    fn should_order_qualifiers() {
+        // In ascending order, from Maven's `ComparableVersionTest`.
+        assert_ordered(&[
+            "1-alpha2snapshot",
+            "1-alpha2",
+            "1-alpha-123",
+            "1-beta-2",
+            "1-beta123",
+            "1-m2",
+            "1-m11",
+            "1-rc",
+            "1-cr2",
+            "1-rc123",
+            "1-SNAPSHOT",
+            "1",
+            "1-sp",
+            "1-sp2",
+            "1-sp123",
+            "1-abc",
+            "1-def",
+            "1-pom-1",
+            "1-1-snapshot",
+            "1-1",
+            "1-2",
+            "1-123",
+        ]);
+    }
+
+    #[test]
+    fn should_order_numbers() {
+        // In ascending order, from Maven's `ComparableVersionTest`.
+        assert_ordered(&[
+            "2.0", "2.0.a", "2-1", "2.0.2", "2.0.123", "2.1.0", "2.1-a", "2.1b", "2.1-c", "2.1-1",
+            "2.1.0.1", "2.2", "2.123", "11.a2", "11.a11", "11.b2", "11.b11", "11.m2", "11.m11",
+            "11", "11.a", "11b", "11c", "11m",
+        ]);
+    }
+
+    #[test]
+    fn should_normalize_versions() {
+        assert_eq!(parse("1"), parse("1.0.0"));
+        assert_eq!(parse("1"), parse("1.0.0.Final"));
+        assert_eq!(parse("1"), parse("1-ga"));
+        assert_eq!(parse("1.0-rc1"), parse("1.0-CR1"));
+        assert_eq!(parse("1.0-alpha1"), parse("1.0-a1"));
+        assert_eq!(parse("1.0-alpha-1"), parse("1.0-alpha1"));
+        assert_eq!(
+            parse("18446744073709551616"),
+            parse("018446744073709551616")
+        );
+        assert!("".parse::<MavenVersion>().is_err());
+    }
+
+    #[test]
+    fn should_detect_prereleases() {
+        assert!(parse("1.0-SNAPSHOT").is_prerelease());
+        assert!(parse("2.0.0-M1").is_prerelease());
+        assert!(parse("1.0-beta-2").is_prerelease());
+        assert!(!parse("33.0.0-jre").is_prerelease());
+        assert!(!parse("5.3.0.Final").is_prerelease());
+        assert!(!parse("1.0-sp1").is_prerelease());
+    }
+
+    #[test]
+    fn should_sort_versions_desc() {
+        let mut versions: Vec<String> = ["1.10.0", "1.9.0", "1.10.0-RC1", "1.10.0-SNAPSHOT"]
+            .into_iter()
+            .map(ToOwned::to_owned)
+            .collect();
+        sort_desc(&mut versions);
+        assert_eq!(
+            vec!["1.10.0", "1.10.0-SNAPSHOT", "1.10.0-RC1", "1.9.0"],
+            versions
+        );
+        assert_eq!(Some("1.9.0"), latest(["1.9.0", "1.10.0-RC1"], false));
+    }
+}
@@ -0,0 +1,56 @@
+//! Version ordering for registries that don't list versions in order, with one comparator per
+//! ecosystem's versioning scheme.
+
+use std::{cmp::Reverse, str::FromStr};
+
+pub mod composer;
+pub mod luarocks;
+pub mod maven;
+pub mod nuget;
+pub mod opam;
+pub mod pep440;
+pub mod pvp;
+pub mod rubygems;
+pub mod semver;
+
+pub trait Version: Ord + FromStr {
+    fn is_prerelease(&self) -> bool;
+}
+
+/// Sorts `versions` in DESCENDING order. Versions that can't be parsed as `V` are sorted last, in
+/// reverse lexical order, so that the order is total.
+pub fn sort_desc<V: Version>(versions: &mut [String]) {
+    versions.sort_by_cached_key(|version| {
+        (Reverse(version.parse::<V>().ok()), Reverse(version.clone()))
+    });
+}
+
+/// Whether `version` parses as a pre-release `V`. Versions that can't be parsed aren't considered
+/// pre-releases.
+pub fn is_prerelease<V: Version>(version: &str) -> bool {
+    version
+        .parse::<V>()
+        .is_ok_and(|version| version.is_prerelease())
+}
+
+/// Returns the greatest version. Unless `include_prerelease` is set, versions that aren't
+/// pre-releases are preferred, and a pre-release is only returned if there are only pre-releases.
+/// Versions that can't be parsed as `V` are ignored.
+pub fn latest<'a, V: Version, I: IntoIterator<Item = &'a str>>(
+    versions: I,
+    include_prerelease: bool,
+) -> Option<&'a str> {
+    versions
+        .into_iter()
+        .filter_map(|version| Some((version.parse::<V>().ok()?, version)))
+        .max_by(|(a, a_raw), (b, b_raw)| {
+            let stability = match include_prerelease {
+                true => std::cmp::Ordering::Equal,
+                false => b.is_prerelease().cmp(&a.is_prerelease()),
+            };
+            stability
+                .then_with(|| a.cmp(b))
+                .then_with(|| a_raw.cmp(b_raw))
+        })
+        .map(|(_, version)| version)
+}
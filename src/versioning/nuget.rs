@@ -0,0 +1,213 @@
+//! Version parsing and ordering as done by NuGet's [`NuGetVersion`], i.e. SemVer 2.0 with an
+//! optional fourth version number and case-insensitive release labels, e.g. `1.0.0-beta.2` <
+//! `1.0.0-RC.1` < `1.0.0` == `1.0.0.0` < `1.0.0.1`.
+//!
+//! [`NuGetVersion`]: https://learn.microsoft.com/en-us/nuget/concepts/package-versioning
+
+use std::{cmp::Ordering, str::FromStr};
+
+use thiserror::Error;
+
+use super::Version;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Label {
+    Numeric(u64),
+    /// Compared case-insensitively, so kept in lowercase.
+    Alphanumeric(String),
+}
+
+impl Ord for Label {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Label::Numeric(lhs), Label::Numeric(rhs)) => lhs.cmp(rhs),
+            (Label::Numeric(_), Label::Alphanumeric(_)) => Ordering::Less,
+            (Label::Alphanumeric(_), Label::Numeric(_)) => Ordering::Greater,
+            (Label::Alphanumeric(lhs), Label::Alphanumeric(rhs)) => lhs.cmp(rhs),
+        }
+    }
+}
+
+impl PartialOrd for Label {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// A NuGet version. Build metadata is ignored, as it doesn't affect the ordering.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NuGetVersion {
+    /// Major, minor, patch and revision. Omitted numbers are 0.
+    numbers: [u64; 4],
+    release_labels: Vec<Label>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("Invalid NuGet version {0:?}.")]
+pub struct InvalidVersion(String);
+
+impl FromStr for NuGetVersion {
+    type Err = InvalidVersion;
+
+    fn from_str(version: &str) -> Result<Self, Self::Err> {
+        let invalid = || InvalidVersion(version.to_owned());
+        let without_metadata = version
+            .trim()
+            .split_once('+')
+            .map_or(version.trim(), |(version, _)| version);
+        let (numbers, release) = match without_metadata.split_once('-') {
+            Some((numbers, release)) => (numbers, Some(release)),
+            None => (without_metadata, None),
+        };
+
+        let mut parsed_numbers = [0; 4];
+        for (index, number) in numbers.split('.').enumerate() {
+            if index == parsed_numbers.len()
+                || number.is_empty()
+                || !number.bytes().all(|b| b.is_ascii_digit())
+            {
+                return Err(invalid());
+            }
+            parsed_numbers[index] = number.parse().map_err(|_| invalid())?;
+        }
+
+        let release_labels = match release {
+            Some(release) => release
+                .split('.')
+                .map(|label| {
+                    if label.is_empty()
+                        || !label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+                    {
+                        return Err(invalid());
+                    }
+                    Ok(match label.parse() {
+                        Ok(number) if label.bytes().all(|b| b.is_ascii_digit()) => {
+                            Label::Numeric(number)
+                        }
+                        Ok(_) | Err(_) => Label::Alphanumeric(label.to_lowercase()),
+                    })
+                })
+                .collect::<Result<_, _>>()?,
+            None => Vec::new(),
+        };
+
+        Ok(Self {
+            numbers: parsed_numbers,
+            release_labels,
+        })
+    }
+}
+
+impl Ord for NuGetVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.numbers.cmp(&other.numbers).then_with(|| {
+            match (
+                self.release_labels.is_empty(),
+                other.release_labels.is_empty(),
+            ) {
+                (true, true) => Ordering::Equal,
+                // Releases sort after their pre-releases.
+                (true, false) => Ordering::Greater,
+                (false, true) => Ordering::Less,
+                (false, false) => self.release_labels.cmp(&other.release_labels),
+            }
+        })
+    }
+}
+
+impl PartialOrd for NuGetVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Version for NuGetVersion {
+    fn is_prerelease(&self) -> bool {
+        !self.release_labels.is_empty()
+    }
+}
+
+/// Sorts `versions` in DESCENDING order.
+pub fn sort_desc(versions: &mut [String]) {
+    super::sort_desc::<NuGetVersion>(versions);
+}
+
+/// Returns the greatest version that isn't a pre-release, or the greatest pre-release if there are
+/// only pre-releases. With `include_prerelease`, returns the greatest version.
+pub fn latest<'a, I: IntoIterator<Item = &'a str>>(
+    versions: I,
+    include_prerelease: bool,
+) -> Option<&'a str> {
+    super::latest::<NuGetVersion, _>(versions, include_prerelease)
+}
+
+pub fn is_prerelease(version: &str) -> bool {
+    super::is_prerelease::<NuGetVersion>(version)
+}
+
+/// Whether `lhs` and `rhs` are the same version, e.g. `1.0` and `1.0.0+build.1`. Versions that
+/// can't be parsed are only equal to themselves.
+pub fn eq(lhs: &str, rhs: &str) -> bool {
+    match (lhs.parse::<NuGetVersion>(), rhs.parse::<NuGetVersion>()) {
+        (Ok(lhs), Ok(rhs)) => lhs == rhs,
+        (_, _) => lhs == rhs,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(version: &str) -> NuGetVersion {
+        version
+            .parse()
+            .unwrap_or_else(|err| panic!("{version}: {err}"))
+    }
+
+    #[test]
+    fn should_order_versions() {
+        let versions = [
+            "0.9.9",
+            "1.0.0-alpha",
+            "1.0.0-alpha.1",
+            "1.0.0-alpha.beta",
+            "1.0.0-Beta",
+            "1.0.0-beta.2",
+            "1.0.0-beta.11",
+            "1.0.0-rc.1",
+            "1.0.0",
+            "1.0.0.1",
+            "1.0.1",
+            "1.10.0",
+        ];
+        for pair in versions.windows(2) {
+            assert!(parse(pair[0]) < parse(pair[1]), "{} < {}", pair[0], pair[1]);
+        }
+    }
+
+    #[test]
+    fn should_normalize_versions() {
+        assert_eq!(parse("1.0"), parse("1.0.0.0"));
+        assert_eq!(parse("1.0.0+build.1"), parse("1.0.0"));
+        assert_eq!(parse("1.0.0-BETA"), parse("1.0.0-beta"));
+        assert_eq!(parse("01.0.0"), parse("1.0.0"));
+        assert!(eq("1.0", "1.0.0+build.1"));
+        assert!(!eq("1.0.0", "1.0.0-beta"));
+    }
+
+    #[test]
+    fn should_reject_invalid_versions() {
+        for invalid in ["", "1.0.0.0.0", "1..0", "a.b.c", "1.0.0-", "1.0.0-beta..1"] {
+            assert!(invalid.parse::<NuGetVersion>().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn should_prefer_stable_latest_version() {
+        let versions = ["0.26.7", "0.27.0-beta.1", "0.26.10"];
+        assert_eq!(Some("0.26.10"), latest(versions, false));
+        assert_eq!(Some("0.27.0-beta.1"), latest(versions, true));
+        assert!(is_prerelease("0.27.0-beta.1"));
+        assert!(!is_prerelease("0.26.7+sha.1234"));
+    }
+}
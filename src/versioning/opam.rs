@@ -0,0 +1,165 @@
+//! Version ordering as done by opam, which compares versions like Debian does: alternating
+//! non-digit and digit parts, where `~` sorts before anything, even the end of the version, e.g.
+//! `1.0~beta1` < `1.0` < `1.0a` < `1.0.1`, as letters sort before other characters.
+
+use std::{cmp::Ordering, str::FromStr};
+
+use thiserror::Error;
+
+use super::Version;
+
+#[derive(Debug, Clone)]
+pub struct OpamVersion(String);
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("Invalid opam version {0:?}.")]
+pub struct InvalidVersion(String);
+
+impl FromStr for OpamVersion {
+    type Err = InvalidVersion;
+
+    fn from_str(version: &str) -> Result<Self, Self::Err> {
+        match !version.is_empty()
+            && version
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '+' | '.' | '~'))
+        {
+            true => Ok(Self(version.to_owned())),
+            false => Err(InvalidVersion(version.to_owned())),
+        }
+    }
+}
+
+/// The weight of a character in a non-digit part, where `None` is the end of the part.
+fn weight(c: Option<u8>) -> i32 {
+    match c {
+        Some(b'~') => -1,
+        None => 0,
+        Some(c) if c.is_ascii_alphabetic() => i32::from(c),
+        Some(c) => i32::from(c) + 256,
+    }
+}
+
+fn cmp_non_digits(lhs: &[u8], rhs: &[u8]) -> Ordering {
+    (0..lhs.len().max(rhs.len()))
+        .map(|index| weight(lhs.get(index).copied()).cmp(&weight(rhs.get(index).copied())))
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or(Ordering::Equal)
+}
+
+fn cmp_digits(lhs: &[u8], rhs: &[u8]) -> Ordering {
+    let trim = |digits: &[u8]| -> usize { digits.iter().take_while(|d| **d == b'0').count() };
+    let (lhs, rhs) = (&lhs[trim(lhs)..], &rhs[trim(rhs)..]);
+    lhs.len().cmp(&rhs.len()).then_with(|| lhs.cmp(rhs))
+}
+
+/// Splits `bytes` after the longest prefix of bytes which are (not) digits.
+fn split_digits(bytes: &[u8], digits: bool) -> (&[u8], &[u8]) {
+    let index = bytes
+        .iter()
+        .position(|b| b.is_ascii_digit() != digits)
+        .unwrap_or(bytes.len());
+    bytes.split_at(index)
+}
+
+impl Ord for OpamVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (mut lhs, mut rhs) = (self.0.as_bytes(), other.0.as_bytes());
+        while !lhs.is_empty() || !rhs.is_empty() {
+            let ((lhs_part, lhs_rest), (rhs_part, rhs_rest)) =
+                (split_digits(lhs, false), split_digits(rhs, false));
+            let ordering = cmp_non_digits(lhs_part, rhs_part);
+            if ordering.is_ne() {
+                return ordering;
+            }
+            let ((lhs_part, lhs_rest), (rhs_part, rhs_rest)) =
+                (split_digits(lhs_rest, true), split_digits(rhs_rest, true));
+            let ordering = cmp_digits(lhs_part, rhs_part);
+            if ordering.is_ne() {
+                return ordering;
+            }
+            (lhs, rhs) = (lhs_rest, rhs_rest);
+        }
+        Ordering::Equal
+    }
+}
+
+impl PartialOrd for OpamVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for OpamVersion {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for OpamVersion {}
+
+impl Version for OpamVersion {
+    /// By convention, pre-releases are marked with a `~`, e.g. `5.0.0~alpha1`.
+    fn is_prerelease(&self) -> bool {
+        self.0.contains('~')
+    }
+}
+
+/// Sorts `versions` in DESCENDING order.
+pub fn sort_desc(versions: &mut [String]) {
+    super::sort_desc::<OpamVersion>(versions);
+}
+
+/// Returns the greatest version that isn't a pre-release, or the greatest pre-release if there are
+/// only pre-releases. With `include_prerelease`, returns the greatest version.
+pub fn latest<'a, I: IntoIterator<Item = &'a str>>(
+    versions: I,
+    include_prerelease: bool,
+) -> Option<&'a str> {
+    super::latest::<OpamVersion, _>(versions, include_prerelease)
+}
+
+pub fn is_prerelease(version: &str) -> bool {
+    super::is_prerelease::<OpamVersion>(version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(version: &str) -> OpamVersion {
+        version
+            .parse()
+            .unwrap_or_else(|err| panic!("{version}: {err}"))
+    }
+
+    #[test]
+    fn should_order_versions() {
+        // In ascending order, partly from opam's `OpamVersionCompare` documentation.
+        let versions = [
+            "~~", "~~a", "~", "~beta2", "~beta10", "0.1", "1.0~beta", "1.0", "1.0a", "1.0-test",
+            "1.0.1", "1.0.10", "1.1", "v1.0",
+        ];
+        for pair in versions.windows(2) {
+            assert!(parse(pair[0]) < parse(pair[1]), "{} < {}", pair[0], pair[1]);
+        }
+    }
+
+    #[test]
+    fn should_compare_numbers_numerically() {
+        assert_eq!(parse("1.00"), parse("1.0"));
+        assert_eq!(parse("1.010"), parse("1.10"));
+        assert!(parse("1.9") < parse("1.10"));
+        assert!("".parse::<OpamVersion>().is_err());
+        assert!("1.0/1".parse::<OpamVersion>().is_err());
+    }
+
+    #[test]
+    fn should_prefer_stable_latest_version() {
+        let versions = ["1.17.0", "1.18.0~5.2preview", "1.9.0"];
+        assert_eq!(Some("1.17.0"), latest(versions, false));
+        assert_eq!(Some("1.18.0~5.2preview"), latest(versions, true));
+        assert!(is_prerelease("5.0.0~alpha1"));
+        assert!(!is_prerelease("0.26.2"));
+    }
+}
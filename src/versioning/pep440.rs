@@ -0,0 +1,469 @@
+//! Version parsing and ordering as specified by [PEP 440], which is what pip uses to pick the
+//! latest version of a package.
+//!
+//! [PEP 440]: https://peps.python.org/pep-0440/
+
+use std::{cmp::Ordering, str::FromStr};
+
+use thiserror::Error;
+
+use super::Version;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PreReleaseKind {
+    Alpha,
+    Beta,
+    ReleaseCandidate,
+}
+
+/// A segment of a local version label, e.g. `ubuntu` and `1` in `1.0+ubuntu.1`. Numeric segments
+/// sort after alphanumeric ones.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LocalSegment {
+    String(String),
+    Number(u64),
+}
+
+#[derive(Debug, Clone)]
+pub struct Pep440Version {
+    pub epoch: u64,
+    pub release: Vec<u64>,
+    pub pre: Option<(PreReleaseKind, u64)>,
+    pub post: Option<u64>,
+    pub dev: Option<u64>,
+    pub local: Option<Vec<LocalSegment>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("Invalid PEP 440 version {0:?}.")]
+pub struct InvalidVersion(String);
+
+/// Where the pre-release segment sorts, with development releases of a final release (`1.0.dev0`)
+/// sorting before its pre-releases (`1.0a0`).
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+enum PreReleaseKey {
+    DevelopmentOnly,
+    PreRelease(PreReleaseKind, u64),
+    Final,
+}
+
+impl Pep440Version {
+    fn release_key(&self) -> &[u64] {
+        // Trailing zeros are insignificant, i.e. 1.0 == 1.0.0.
+        let len = self
+            .release
+            .iter()
+            .rposition(|segment| *segment != 0)
+            .map_or(0, |index| index + 1);
+        &self.release[..len]
+    }
+
+    fn pre_release_key(&self) -> PreReleaseKey {
+        match (self.pre, self.post, self.dev) {
+            (Some((kind, number)), _, _) => PreReleaseKey::PreRelease(kind, number),
+            (None, None, Some(_)) => PreReleaseKey::DevelopmentOnly,
+            (None, _, _) => PreReleaseKey::Final,
+        }
+    }
+
+    fn dev_key(&self) -> (bool, u64) {
+        // Releases without a development segment sort after those with one.
+        match self.dev {
+            Some(dev) => (false, dev),
+            None => (true, 0),
+        }
+    }
+}
+
+impl Ord for Pep440Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.epoch
+            .cmp(&other.epoch)
+            .then_with(|| self.release_key().cmp(other.release_key()))
+            .then_with(|| self.pre_release_key().cmp(&other.pre_release_key()))
+            .then_with(|| self.post.cmp(&other.post))
+            .then_with(|| self.dev_key().cmp(&other.dev_key()))
+            .then_with(|| self.local.cmp(&other.local))
+    }
+}
+
+impl PartialOrd for Pep440Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Pep440Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Pep440Version {}
+
+struct Scanner<'a> {
+    input: &'a str,
+    position: usize,
+}
+
+impl<'a> Scanner<'a> {
+    fn rest(&self) -> &'a str {
+        &self.input[self.position..]
+    }
+
+    fn is_done(&self) -> bool {
+        self.position == self.input.len()
+    }
+
+    fn char(&mut self, expected: char) -> bool {
+        if self.rest().starts_with(expected) {
+            self.position += expected.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn separator(&mut self) -> bool {
+        self.char('.') || self.char('-') || self.char('_')
+    }
+
+    fn number(&mut self) -> Option<u64> {
+        let len = self
+            .rest()
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(self.rest().len());
+        let number = self.rest()[..len].parse().ok()?;
+        self.position += len;
+        Some(number)
+    }
+
+    /// Consumes the first of `keywords` the input continues with.
+    fn keyword<T: Copy>(&mut self, keywords: &[(&str, T)]) -> Option<T> {
+        let (keyword, value) = keywords
+            .iter()
+            .find(|(keyword, _)| self.rest().starts_with(keyword))?;
+        self.position += keyword.len();
+        Some(*value)
+    }
+
+    /// Runs `parse`, rewinding if it doesn't match.
+    fn attempt<T>(&mut self, parse: impl FnOnce(&mut Self) -> Option<T>) -> Option<T> {
+        let position = self.position;
+        let result = parse(self);
+        if result.is_none() {
+            self.position = position;
+        }
+        result
+    }
+
+    /// An optionally separated number following a pre-release, post-release or development
+    /// keyword, which is implicitly 0.
+    fn implicit_number(&mut self) -> u64 {
+        self.attempt(|scanner| {
+            scanner.separator();
+            scanner.number()
+        })
+        .unwrap_or(0)
+    }
+
+    /// A keyword segment such as `.post1`, `-rc.2` or `b`.
+    fn labelled<T: Copy>(&mut self, keywords: &[(&str, T)]) -> Option<(T, u64)> {
+        self.attempt(|scanner| {
+            scanner.separator();
+            let label = scanner.keyword(keywords)?;
+            Some((label, scanner.implicit_number()))
+        })
+    }
+}
+
+impl FromStr for Pep440Version {
+    type Err = InvalidVersion;
+
+    fn from_str(version: &str) -> Result<Self, Self::Err> {
+        let invalid = || InvalidVersion(version.to_owned());
+        let normalized = version.trim().to_lowercase();
+        let mut scanner = Scanner {
+            input: normalized.strip_prefix('v').unwrap_or(&normalized),
+            position: 0,
+        };
+
+        let epoch = scanner
+            .attempt(|scanner| {
+                let epoch = scanner.number()?;
+                scanner.char('!').then_some(epoch)
+            })
+            .unwrap_or(0);
+
+        let mut release = vec![scanner.number().ok_or_else(invalid)?];
+        while let Some(segment) = scanner.attempt(|scanner| {
+            scanner.char('.').then_some(())?;
+            scanner.number()
+        }) {
+            release.push(segment);
+        }
+
+        let pre = scanner.labelled(&[
+            ("alpha", PreReleaseKind::Alpha),
+            ("a", PreReleaseKind::Alpha),
+            ("beta", PreReleaseKind::Beta),
+            ("b", PreReleaseKind::Beta),
+            ("preview", PreReleaseKind::ReleaseCandidate),
+            ("pre", PreReleaseKind::ReleaseCandidate),
+            ("rc", PreReleaseKind::ReleaseCandidate),
+            ("c", PreReleaseKind::ReleaseCandidate),
+        ]);
+
+        // `1.0-1` is an implicit post-release.
+        let post = scanner
+            .attempt(|scanner| {
+                scanner.char('-').then_some(())?;
+                scanner.number()
+            })
+            .or_else(|| {
+                scanner
+                    .labelled(&[("post", ()), ("rev", ()), ("r", ())])
+                    .map(|(_, number)| number)
+            });
+
+        let dev = scanner.labelled(&[("dev", ())]).map(|(_, number)| number);
+
+        let local = match scanner.char('+') {
+            true => {
+                let local = scanner
+                    .rest()
+                    .split(['.', '-', '_'])
+                    .map(|segment| match segment.parse() {
+                        Ok(number) => Some(LocalSegment::Number(number)),
+                        Err(_)
+                            if !segment.is_empty()
+                                && segment.chars().all(|c| c.is_ascii_alphanumeric()) =>
+                        {
+                            Some(LocalSegment::String(segment.to_owned()))
+                        }
+                        Err(_) => None,
+                    })
+                    .collect::<Option<Vec<_>>>()
+                    .ok_or_else(invalid)?;
+                scanner.position = scanner.input.len();
+                Some(local)
+            }
+            false => None,
+        };
+
+        if !scanner.is_done() {
+            return Err(invalid());
+        }
+        Ok(Self {
+            epoch,
+            release,
+            pre,
+            post,
+            dev,
+            local,
+        })
+    }
+}
+
+impl Version for Pep440Version {
+    /// Whether this is a pre-release or development release, which pip skips unless asked for.
+    fn is_prerelease(&self) -> bool {
+        self.pre.is_some() || self.dev.is_some()
+    }
+}
+
+/// Sorts `versions` in DESCENDING order. Versions that aren't valid PEP 440 versions are sorted
+/// last, in reverse lexical order.
+pub fn sort_desc(versions: &mut [String]) {
+    super::sort_desc::<Pep440Version>(versions);
+}
+
+/// Returns the greatest version that isn't a pre-release, or the greatest pre-release if there are
+/// only pre-releases, like pip does. With `include_prerelease`, like `pip install --pre` does,
+/// returns the greatest version.
+pub fn latest<'a, I: IntoIterator<Item = &'a str>>(
+    versions: I,
+    include_prerelease: bool,
+) -> Option<&'a str> {
+    super::latest::<Pep440Version, _>(versions, include_prerelease)
+}
+
+pub fn is_prerelease(version: &str) -> bool {
+    super::is_prerelease::<Pep440Version>(version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(version: &str) -> Pep440Version {
+        version
+            .parse()
+            .unwrap_or_else(|err| panic!("{version}: {err}"))
+    }
+
+    /// In ascending order, from the `packaging` test suite.
+    const ORDERED: &[&str] = &[
+        "1.0.dev456",
+        "1.0a1",
+        "1.0a2.dev456",
+        "1.0a12.dev456",
+        "1.0a12",
+        "1.0b1.dev456",
+        "1.0b2",
+        "1.0b2.post345.dev456",
+        "1.0b2.post345",
+        "1.0b2-346",
+        "1.0c1.dev456",
+        "1.0c1",
+        "1.0rc2",
+        "1.0c3",
+        "1.0",
+        "1.0.post456.dev34",
+        "1.0.post456",
+        "1.1.dev1",
+        "1.2+123abc",
+        "1.2+123abc456",
+        "1.2+abc",
+        "1.2+abc123",
+        "1.2+abc123def",
+        "1.2+1234.abc",
+        "1.2+123456",
+        "1.2.r32+123456",
+        "1.2.rev33+123456",
+        "1!1.0.dev456",
+        "1!1.0a1",
+        "1!1.0a2.dev456",
+        "1!1.0a12.dev456",
+        "1!1.0a12",
+        "1!1.0b1.dev456",
+        "1!1.0b2",
+        "1!1.0b2.post345.dev456",
+        "1!1.0b2.post345",
+        "1!1.0b2-346",
+        "1!1.0c1.dev456",
+        "1!1.0c1",
+        "1!1.0rc2",
+        "1!1.0c3",
+        "1!1.0",
+        "1!1.0.post456.dev34",
+        "1!1.0.post456",
+        "1!1.1.dev1",
+        "1!1.2+123abc",
+        "1!1.2+123abc456",
+        "1!1.2+abc",
+        "1!1.2+abc123",
+        "1!1.2+abc123def",
+        "1!1.2+1234.abc",
+        "1!1.2+123456",
+        "1!1.2.r32+123456",
+        "1!1.2.rev33+123456",
+    ];
+
+    #[test]
+    fn should_order_versions() {
+        for (index, lower) in ORDERED.iter().enumerate() {
+            for higher in &ORDERED[index + 1..] {
+                assert!(parse(lower) < parse(higher), "expected {lower} < {higher}");
+            }
+        }
+    }
+
+    #[test]
+    fn should_normalize_versions() {
+        for (version, normalized) in [
+            ("1.0.0", "1.0"),
+            ("1.0.0.0", "1"),
+            ("v1.0", "1.0"),
+            ("0!1.0", "1.0"),
+            ("1.0ALPHA1", "1.0a1"),
+            ("1.0-beta.2", "1.0b2"),
+            ("1.0c1", "1.0rc1"),
+            ("1.0pre1", "1.0rc1"),
+            ("1.0preview", "1.0rc0"),
+            ("1.0.rc", "1.0rc0"),
+            ("1.0-1", "1.0.post1"),
+            ("1.0.rev1", "1.0.post1"),
+            ("1.0r", "1.0.post0"),
+            ("1.0-post_2", "1.0.post2"),
+            ("1.0-dev", "1.0.dev0"),
+            ("1.0.DEV3", "1.0.dev3"),
+            ("1.0+Ubuntu-1", "1.0+ubuntu.1"),
+            (" 1.0 ", "1.0"),
+        ] {
+            assert_eq!(parse(normalized), parse(version), "{version}");
+        }
+    }
+
+    #[test]
+    fn should_reject_invalid_versions() {
+        for version in [
+            "",
+            "french toast",
+            "1.0+",
+            "1.0+a..b",
+            "1.0-",
+            "1.0.",
+            "1.0a1a2",
+            "1.0.post1.post2",
+            "1.0+abc+def",
+            "1!2!3",
+        ] {
+            assert!(version.parse::<Pep440Version>().is_err(), "{version}");
+        }
+    }
+
+    #[test]
+    fn should_detect_prereleases() {
+        assert!(parse("1.0rc1").is_prerelease());
+        assert!(parse("1.0.dev3").is_prerelease());
+        assert!(parse("1.0.post1.dev1").is_prerelease());
+        assert!(!parse("1.0.post1").is_prerelease());
+        assert!(!parse("2!1.0+local").is_prerelease());
+    }
+
+    #[test]
+    fn should_sort_descending() {
+        let mut versions = [
+            "1.0.dev3",
+            "not-a-version",
+            "1.0",
+            "2!1.0",
+            "1.0rc1",
+            "1.0.post1",
+            "0.9",
+            "10.0",
+            "1.0.0",
+        ]
+        .map(ToOwned::to_owned);
+        sort_desc(&mut versions);
+        assert_eq!(
+            [
+                "2!1.0",
+                "10.0",
+                "1.0.post1",
+                "1.0.0",
+                "1.0",
+                "1.0rc1",
+                "1.0.dev3",
+                "0.9",
+                "not-a-version"
+            ],
+            versions
+        );
+    }
+
+    #[test]
+    fn should_find_latest_stable_version() {
+        assert_eq!(
+            Some("1.0.post1"),
+            latest(["1.0", "1.1rc1", "1.0.post1", "1.1.dev0", "garbage"], false)
+        );
+        assert_eq!(Some("2.0b1"), latest(["1.0a1", "2.0b1", "2.0.dev1"], false));
+        assert_eq!(None, latest(["garbage"], false));
+        assert_eq!(
+            Some("1.1rc1"),
+            latest(["1.0", "1.1rc1", "1.0.post1", "1.1.dev0"], true)
+        );
+    }
+}
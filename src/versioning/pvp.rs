@@ -0,0 +1,88 @@
+//! Versions as defined by the Haskell [Package Versioning Policy](https://pvp.haskell.org), i.e.
+//! any number of dot-separated numbers compared in order, e.g. `1.2` < `1.2.0` < `1.2.0.1`.
+
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use super::Version;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PvpVersion(Vec<u64>);
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("Invalid PVP version {0:?}.")]
+pub struct InvalidVersion(String);
+
+impl FromStr for PvpVersion {
+    type Err = InvalidVersion;
+
+    fn from_str(version: &str) -> Result<Self, Self::Err> {
+        version
+            .split('.')
+            .map(|number| match number.bytes().all(|b| b.is_ascii_digit()) {
+                true => number.parse().ok(),
+                false => None,
+            })
+            .collect::<Option<_>>()
+            .map(Self)
+            .ok_or_else(|| InvalidVersion(version.to_owned()))
+    }
+}
+
+impl Version for PvpVersion {
+    /// The PVP has no notion of pre-releases.
+    fn is_prerelease(&self) -> bool {
+        false
+    }
+}
+
+/// Sorts `versions` in DESCENDING order.
+pub fn sort_desc(versions: &mut [String]) {
+    super::sort_desc::<PvpVersion>(versions);
+}
+
+/// Returns the greatest version.
+pub fn latest<'a, I: IntoIterator<Item = &'a str>>(versions: I) -> Option<&'a str> {
+    super::latest::<PvpVersion, _>(versions, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(version: &str) -> PvpVersion {
+        version
+            .parse()
+            .unwrap_or_else(|err| panic!("{version}: {err}"))
+    }
+
+    #[test]
+    fn should_order_versions() {
+        let versions = [
+            "0.9", "1", "1.2", "1.2.0", "1.2.0.1", "1.2.3.4", "1.10", "2",
+        ];
+        for pair in versions.windows(2) {
+            assert!(parse(pair[0]) < parse(pair[1]), "{} < {}", pair[0], pair[1]);
+        }
+        assert_eq!(parse("01.2"), parse("1.2"));
+    }
+
+    #[test]
+    fn should_reject_invalid_versions() {
+        for invalid in ["", "1.", ".1", "1..2", "1.2-rc1", "v1.2"] {
+            assert!(invalid.parse::<PvpVersion>().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn should_sort_versions_desc() {
+        let mut versions: Vec<String> = ["3.6.1", "3.8", "3.10", "3.6"]
+            .into_iter()
+            .map(ToOwned::to_owned)
+            .collect();
+        sort_desc(&mut versions);
+        assert_eq!(vec!["3.10", "3.8", "3.6.1", "3.6"], versions);
+        assert_eq!(Some("3.10"), latest(["3.8", "3.10"]));
+    }
+}
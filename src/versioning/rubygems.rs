@@ -0,0 +1,213 @@
+//! Version parsing and ordering as done by RubyGems' [`Gem::Version`], which compares versions
+//! segment by segment, with letters marking pre-releases, e.g. `1.0.0.pre` and `1.0.0-rc.1`.
+//!
+//! [`Gem::Version`]: https://github.com/rubygems/rubygems/blob/master/lib/rubygems/version.rb
+
+use std::{cmp::Ordering, str::FromStr};
+
+use thiserror::Error;
+
+use super::Version;
+
+/// String segments sort before numeric ones, so that `1.0.a` < `1.0`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Segment {
+    String(String),
+    Number(u64),
+}
+
+#[derive(Debug, Clone)]
+pub struct GemVersion {
+    pub segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("Invalid gem version {0:?}.")]
+pub struct InvalidVersion(String);
+
+impl GemVersion {
+    /// The segments with trailing zeros of the release and pre-release parts removed, which is
+    /// what `Gem::Version` compares, so that `1.0` == `1`.
+    fn canonical_segments(&self) -> Vec<&Segment> {
+        let prerelease_start = self
+            .segments
+            .iter()
+            .position(|segment| matches!(segment, Segment::String(_)))
+            .unwrap_or(self.segments.len());
+        let (release, prerelease) = self.segments.split_at(prerelease_start);
+        trim_zeros(release)
+            .iter()
+            .chain(trim_zeros(prerelease))
+            .collect()
+    }
+}
+
+fn trim_zeros(segments: &[Segment]) -> &[Segment] {
+    let len = segments
+        .iter()
+        .rposition(|segment| *segment != Segment::Number(0))
+        .map_or(0, |index| index + 1);
+    &segments[..len]
+}
+
+impl FromStr for GemVersion {
+    type Err = InvalidVersion;
+
+    fn from_str(version: &str) -> Result<Self, Self::Err> {
+        let invalid = || InvalidVersion(version.to_owned());
+        let version = version.trim();
+        // `Gem::Version::ANCHORED_VERSION_PATTERN`: a number, followed by `.`-separated
+        // alphanumeric segments and an optional `-`-separated pre-release.
+        let (release, prerelease) = match version.split_once('-') {
+            Some((release, prerelease)) => (release, Some(prerelease)),
+            None => (version, None),
+        };
+        let is_valid = release.starts_with(|c: char| c.is_ascii_digit())
+            && release
+                .split('.')
+                .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric()))
+            && prerelease.is_none_or(|prerelease| {
+                prerelease.split('.').all(|part| {
+                    !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+                })
+            });
+        if !is_valid {
+            return Err(invalid());
+        }
+
+        // Like `Gem::Version`, treat `-` as a `.pre.` segment and split digits from letters.
+        let mut segments = Vec::new();
+        let mut rest = &*version.replace('-', ".pre.");
+        while let Some(start) = rest.find(|c: char| c.is_ascii_alphanumeric()) {
+            rest = &rest[start..];
+            let is_digit = rest.starts_with(|c: char| c.is_ascii_digit());
+            let len = rest
+                .find(|c: char| !c.is_ascii_alphanumeric() || c.is_ascii_digit() != is_digit)
+                .unwrap_or(rest.len());
+            segments.push(match is_digit {
+                true => Segment::Number(rest[..len].parse().map_err(|_| invalid())?),
+                false => Segment::String(rest[..len].to_owned()),
+            });
+            rest = &rest[len..];
+        }
+        Ok(Self { segments })
+    }
+}
+
+impl Ord for GemVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (lhs, rhs) = (self.canonical_segments(), other.canonical_segments());
+        let zero = Segment::Number(0);
+        (0..lhs.len().max(rhs.len()))
+            .map(|index| {
+                let lhs = lhs.get(index).copied().unwrap_or(&zero);
+                let rhs = rhs.get(index).copied().unwrap_or(&zero);
+                lhs.cmp(rhs)
+            })
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for GemVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for GemVersion {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for GemVersion {}
+
+impl Version for GemVersion {
+    fn is_prerelease(&self) -> bool {
+        self.segments
+            .iter()
+            .any(|segment| matches!(segment, Segment::String(_)))
+    }
+}
+
+/// Sorts `versions` in DESCENDING order. Versions that aren't valid gem versions are sorted last,
+/// in reverse lexical order.
+pub fn sort_desc(versions: &mut [String]) {
+    super::sort_desc::<GemVersion>(versions);
+}
+
+/// Returns the greatest version that isn't a pre-release, or the greatest pre-release if there are
+/// only pre-releases. With `include_prerelease`, returns the greatest version.
+pub fn latest<'a, I: IntoIterator<Item = &'a str>>(
+    versions: I,
+    include_prerelease: bool,
+) -> Option<&'a str> {
+    super::latest::<GemVersion, _>(versions, include_prerelease)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(version: &str) -> GemVersion {
+        version
+            .parse()
+            .unwrap_or_else(|err| panic!("{version}: {err}"))
+    }
+
+    /// In ascending order, from the RubyGems test suite.
+    const ORDERED: &[&str] = &[
+        "0.9", "1.0.a", "1.0.a.2", "1.0.b1", "1.0.rc.1", "1.0", "1.0.0.1", "1.8.2", "1.9.3.a",
+        "1.10", "5.a.10", "5.b", "5.b.1",
+    ];
+
+    #[test]
+    fn should_order_versions() {
+        for pair in ORDERED.windows(2) {
+            assert!(parse(pair[0]) < parse(pair[1]), "{} < {}", pair[0], pair[1]);
+        }
+    }
+
+    #[test]
+    fn should_compare_canonical_segments() {
+        assert_eq!(parse("1"), parse("1.0.0"));
+        assert_eq!(parse("1.0.a"), parse("1.0.0.a"));
+        assert_eq!(parse("1.0.0-rc1"), parse("1.0.0.pre.rc1"));
+        assert_eq!(parse("1.0.b1"), parse("1.0.b.1"));
+    }
+
+    #[test]
+    fn should_reject_invalid_versions() {
+        for invalid in [
+            "",
+            "junk",
+            "1.0\n2.0",
+            "1..2",
+            "1.2 3.4",
+            "2.3422222.222.222222222.22222.ads0as.dasd0.ddd2222.2.qd3e.",
+        ] {
+            assert!(invalid.parse::<GemVersion>().is_err(), "{invalid:?}");
+        }
+    }
+
+    #[test]
+    fn should_detect_prereleases() {
+        assert!(parse("0.49.0.pre").is_prerelease());
+        assert!(parse("1.0.0-rc1").is_prerelease());
+        assert!(!parse("0.49.0").is_prerelease());
+    }
+
+    #[test]
+    fn should_sort_versions_desc() {
+        let mut versions: Vec<String> = ["0.47.2", "0.49.0", "junk", "0.49.0.pre", "0.100.0"]
+            .into_iter()
+            .map(ToOwned::to_owned)
+            .collect();
+        sort_desc(&mut versions);
+        assert_eq!(
+            vec!["0.100.0", "0.49.0", "0.49.0.pre", "0.47.2", "junk"],
+            versions
+        );
+    }
+}
@@ -0,0 +1,74 @@
+//! [Semantic versions](https://semver.org), as used by Cargo, npm, Go modules and Open VSX.
+
+use std::str::FromStr;
+
+use super::Version;
+
+/// A semantic version, optionally prefixed with `v` like Go module versions are.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SemVer(pub semver::Version);
+
+impl FromStr for SemVer {
+    type Err = semver::Error;
+
+    fn from_str(version: &str) -> Result<Self, Self::Err> {
+        version
+            .strip_prefix('v')
+            .unwrap_or(version)
+            .parse()
+            .map(Self)
+    }
+}
+
+impl Version for SemVer {
+    fn is_prerelease(&self) -> bool {
+        !self.0.pre.is_empty()
+    }
+}
+
+/// Sorts `versions` in DESCENDING order. Versions that aren't valid semantic versions are sorted
+/// last, in reverse lexical order.
+pub fn sort_desc(versions: &mut [String]) {
+    super::sort_desc::<SemVer>(versions);
+}
+
+/// Returns the greatest version that isn't a pre-release, or the greatest pre-release if there are
+/// only pre-releases. With `include_prerelease`, returns the greatest version.
+pub fn latest<'a, I: IntoIterator<Item = &'a str>>(
+    versions: I,
+    include_prerelease: bool,
+) -> Option<&'a str> {
+    super::latest::<SemVer, _>(versions, include_prerelease)
+}
+
+pub fn is_prerelease(version: &str) -> bool {
+    super::is_prerelease::<SemVer>(version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_sort_invalid_versions_last() {
+        let mut versions: Vec<String> = ["nightly", "v1.0.0", "latest", "v1.10.0", "1.2.0"]
+            .into_iter()
+            .map(ToOwned::to_owned)
+            .collect();
+        sort_desc(&mut versions);
+        assert_eq!(
+            vec!["v1.10.0", "1.2.0", "v1.0.0", "nightly", "latest"],
+            versions
+        );
+    }
+
+    #[test]
+    fn should_prefer_stable_latest_version() {
+        let versions = ["1.0.0", "1.1.0-rc.1", "nightly"];
+        assert_eq!(Some("1.0.0"), latest(versions, false));
+        assert_eq!(Some("1.1.0-rc.1"), latest(versions, true));
+        assert_eq!(Some("1.1.0-rc.1"), latest(["1.1.0-rc.1", "nightly"], false));
+        assert!(is_prerelease("v0.0.0-20230101120000-abcdef123456"));
+        assert!(!is_prerelease("nightly"));
+    }
+}
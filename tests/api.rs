@@ -0,0 +1,983 @@
+mod common;
+
+use common::{
+    assert_not_found_json, assert_ok_json, assert_ok_json_post, get, get_with_headers, post_json,
+};
+use http::{
+    header::{ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED, RETRY_AFTER},
+    HeaderMap,
+};
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use serde_json::json;
+
+const ALL_VERSIONS: &str = "schemas/versions/all.json";
+const VERSION: &str = "schemas/versions/version.json";
+const RELEASE: &str = "schemas/github/releases/release.json";
+const TAG: &str = "schemas/github/tags/tag.json";
+const BATCH: &str = "schemas/batch.json";
+
+fn encode(component: &str) -> String {
+    utf8_percent_encode(component, NON_ALPHANUMERIC).to_string()
+}
+
+#[test]
+fn should_redirect_root() {
+    let response = get("/");
+    assert_eq!(307, response.status().as_u16());
+    assert_eq!(
+        "https://github.com/mason-org/mason-registry-api",
+        response.headers()["location"]
+    );
+}
+
+#[test]
+fn should_respond_not_found_for_unknown_route() {
+    assert_eq!(404, get("/api/unknown").status().as_u16());
+}
+
+#[test]
+fn should_answer_conditional_requests() {
+    let path = "/api/npm/typescript-language-server/versions/latest";
+    let response = get(path);
+    assert_eq!(200, response.status().as_u16());
+    assert_eq!(
+        "Tue, 11 Apr 2023 10:00:00 GMT",
+        response.headers()[LAST_MODIFIED]
+    );
+    let etag = response.headers()[ETAG].clone();
+
+    let mut headers = HeaderMap::new();
+    headers.insert(IF_NONE_MATCH, etag.clone());
+    let response = get_with_headers(path, headers);
+    assert_eq!(304, response.status().as_u16());
+    assert_eq!(etag, response.headers()[ETAG]);
+    assert!(response.text().unwrap().is_empty());
+
+    let mut headers = HeaderMap::new();
+    headers.insert(IF_NONE_MATCH, "\"outdated\"".parse().unwrap());
+    assert_eq!(200, get_with_headers(path, headers).status().as_u16());
+
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        IF_MODIFIED_SINCE,
+        "Tue, 11 Apr 2023 10:00:00 GMT".parse().unwrap(),
+    );
+    assert_eq!(304, get_with_headers(path, headers).status().as_u16());
+
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        IF_MODIFIED_SINCE,
+        "Mon, 10 Apr 2023 10:00:00 GMT".parse().unwrap(),
+    );
+    assert_eq!(200, get_with_headers(path, headers).status().as_u16());
+}
+
+#[test]
+fn npm() {
+    assert_eq!(
+        json!(["3.3.2", "3.0.0", "2.0.0", "0.1.0"]),
+        assert_ok_json(
+            "/api/npm/typescript-language-server/versions/all?include_prerelease=false",
+            ALL_VERSIONS
+        )
+    );
+    assert_eq!(
+        json!([
+            "4.0.0-rc.1",
+            "3.3.2",
+            "3.0.0",
+            "3.0.0-rc.1",
+            "2.0.0",
+            "0.1.0"
+        ]),
+        assert_ok_json(
+            "/api/npm/typescript-language-server/versions/all",
+            ALL_VERSIONS
+        )
+    );
+    assert_eq!(
+        "3.3.2",
+        assert_ok_json(
+            "/api/npm/typescript-language-server/versions/latest",
+            VERSION
+        )["version"]
+    );
+    assert_eq!(
+        "4.0.0-rc.1",
+        assert_ok_json(
+            "/api/npm/typescript-language-server/versions/latest?include_prerelease",
+            VERSION
+        )["version"]
+    );
+    assert_eq!(
+        "3.0.0",
+        assert_ok_json(
+            "/api/npm/typescript-language-server/versions/3.0.0",
+            VERSION
+        )["version"]
+    );
+    assert_eq!(
+        json!({"name": "@ansible/ansible-language-server", "version": "1.0.4"}),
+        assert_ok_json(
+            "/api/npm/@ansible/ansible-language-server/versions/latest",
+            VERSION
+        )
+    );
+    assert_not_found_json("/api/npm/typescript-language-server/versions/17287138");
+    assert_not_found_json("/api/npm/does-not-exist/versions/latest");
+}
+
+#[test]
+fn npm_legacy_latest_version() {
+    assert_eq!(
+        "3.3.2",
+        assert_ok_json(
+            "/api/npm/typescript-language-server/latest-version",
+            VERSION
+        )["version"]
+    );
+}
+
+#[test]
+fn pypi() {
+    assert_eq!(
+        json!(["0.2.0rc1", "0.1.10", "0.1.7", "0.1.6", "0.1.1", "0.1.0"]),
+        assert_ok_json("/api/pypi/cmake-language-server/versions/all", ALL_VERSIONS)
+    );
+    assert_eq!(
+        json!(["0.1.7", "0.1.6", "0.1.1", "0.1.0"]),
+        assert_ok_json(
+            "/api/pypi/cmake-language-server/versions/all?include_prerelease=0&include_yanked=0",
+            ALL_VERSIONS
+        )
+    );
+    assert_eq!(
+        json!(["0.2.0rc1", "0.1.7", "0.1.6", "0.1.1", "0.1.0"]),
+        assert_ok_json(
+            "/api/pypi/cmake-language-server/versions/all?include_yanked=0",
+            ALL_VERSIONS
+        )
+    );
+    // 0.1.10 is yanked.
+    assert_eq!(
+        json!(["0.1.10", "0.1.7", "0.1.6", "0.1.1", "0.1.0"]),
+        assert_ok_json(
+            "/api/pypi/cmake-language-server/versions/all?include_prerelease=0",
+            ALL_VERSIONS
+        )
+    );
+    assert_eq!(
+        "0.1.7",
+        assert_ok_json("/api/pypi/cmake-language-server/versions/latest", VERSION)["version"]
+    );
+    assert_eq!(
+        "0.2.0rc1",
+        assert_ok_json(
+            "/api/pypi/cmake-language-server/versions/latest?include_prerelease=1",
+            VERSION
+        )["version"]
+    );
+    assert_eq!(
+        "0.1.6",
+        assert_ok_json("/api/pypi/cmake-language-server/versions/0.1.6", VERSION)["version"]
+    );
+    assert_not_found_json("/api/pypi/cmake-language-server/versions/17287138");
+}
+
+#[test]
+fn crates() {
+    assert_eq!(
+        json!(["0.8.1", "0.8.0", "0.1.0"]),
+        assert_ok_json("/api/crate/crates_io_api/versions/all", ALL_VERSIONS)
+    );
+    // 0.1.0 is yanked.
+    assert_eq!(
+        json!(["0.8.1", "0.8.0"]),
+        assert_ok_json(
+            "/api/crate/crates_io_api/versions/all?include_yanked=false",
+            ALL_VERSIONS
+        )
+    );
+    assert_eq!(
+        json!(["0.8.1", "0.8.0", "0.1.0"]),
+        assert_ok_json(
+            "/api/crate/crates_io_api/versions/all?include_prerelease=false",
+            ALL_VERSIONS
+        )
+    );
+    assert_eq!(
+        "0.8.1",
+        assert_ok_json("/api/crate/crates_io_api/versions/latest", VERSION)["version"]
+    );
+    assert_eq!(
+        "0.8.1",
+        assert_ok_json("/api/crate/crates_io_api/versions/0.8.1", VERSION)["version"]
+    );
+    assert_not_found_json("/api/crate/crates_io_api/versions/17287138");
+}
+
+#[test]
+fn rubygems() {
+    assert_eq!(
+        json!(["0.49.0", "0.48.0", "0.47.2"]),
+        assert_ok_json("/api/rubygems/solargraph/versions/all", ALL_VERSIONS)
+    );
+    assert_eq!(
+        json!(["0.49.0", "0.49.0.pre", "0.48.0", "0.47.2"]),
+        assert_ok_json(
+            "/api/rubygems/solargraph/versions/all?include_prerelease=true",
+            ALL_VERSIONS
+        )
+    );
+    assert_eq!(
+        "0.49.0",
+        assert_ok_json(
+            "/api/rubygems/solargraph/versions/latest?include_prerelease=true",
+            VERSION
+        )["version"]
+    );
+    assert_eq!(
+        "0.49.0",
+        assert_ok_json("/api/rubygems/solargraph/versions/latest", VERSION)["version"]
+    );
+    assert_eq!(
+        "0.48.0",
+        assert_ok_json("/api/rubygems/solargraph/versions/0.48.0", VERSION)["version"]
+    );
+    assert_not_found_json("/api/rubygems/solargraph/versions/17287138");
+}
+
+#[test]
+fn packagist() {
+    assert_eq!(
+        json!(["v1.9.0", "v1.3.0", "v1.0.0", "v0.1.0"]),
+        assert_ok_json(
+            "/api/packagist/laravel/pint/versions/all?include_prerelease=false",
+            ALL_VERSIONS
+        )
+    );
+    assert_eq!(
+        json!([
+            "v1.10.0-RC1",
+            "v1.9.0",
+            "v1.3.0",
+            "v1.0.0",
+            "v0.1.0",
+            "dev-main"
+        ]),
+        assert_ok_json("/api/packagist/laravel/pint/versions/all", ALL_VERSIONS)
+    );
+    assert_eq!(
+        "v1.9.0",
+        assert_ok_json("/api/packagist/laravel/pint/versions/latest", VERSION)["version"]
+    );
+    assert_eq!(
+        "v1.10.0-RC1",
+        assert_ok_json(
+            "/api/packagist/laravel/pint/versions/latest?include_prerelease=true",
+            VERSION
+        )["version"]
+    );
+    assert_eq!(
+        "v1.3.0",
+        assert_ok_json("/api/packagist/laravel/pint/versions/v1.3.0", VERSION)["version"]
+    );
+    assert_not_found_json("/api/packagist/laravel/pint/versions/17287138");
+}
+
+#[test]
+fn golang() {
+    assert_eq!(
+        json!(["v0.12.0", "v0.11.0", "v0.9.5"]),
+        assert_ok_json(
+            "/api/golang/golang.org%2Fx%2Ftools%2Fgopls/versions/all?include_prerelease=false",
+            ALL_VERSIONS
+        )
+    );
+    assert_eq!(
+        json!([
+            "v0.12.0",
+            "v0.12.0-pre.1",
+            "v0.11.0",
+            "v0.11.0-pre.3",
+            "v0.9.5"
+        ]),
+        assert_ok_json(
+            "/api/golang/golang.org%2Fx%2Ftools%2Fgopls/versions/all",
+            ALL_VERSIONS
+        )
+    );
+    // No pre-release is newer than the latest release.
+    assert_eq!(
+        "v0.12.0",
+        assert_ok_json(
+            "/api/golang/golang.org%2Fx%2Ftools%2Fgopls/versions/latest?include_prerelease=true",
+            VERSION
+        )["version"]
+    );
+    assert_eq!(
+        json!({"name": "golang.org/x/tools/gopls", "version": "v0.12.0", "time": "2023-05-23T20:05:50Z"}),
+        assert_ok_json(
+            "/api/golang/golang.org%2Fx%2Ftools%2Fgopls/versions/latest",
+            VERSION
+        )
+    );
+    assert_eq!(
+        "v0.11.0",
+        assert_ok_json(
+            "/api/golang/golang.org%2Fx%2Ftools%2Fgopls/versions/v0.11.0",
+            VERSION
+        )["version"]
+    );
+    assert_eq!(
+        "v0.0.0-20230523195222-d15757fd1a8e",
+        assert_ok_json(
+            "/api/golang/golang.org%2Fx%2Ftools%2Fgopls/versions/v0.0.0-20230523195222-d15757fd1a8e",
+            VERSION
+        )["version"]
+    );
+    assert_not_found_json("/api/golang/golang.org%2Fx%2Ftools%2Fgopls/versions/v0.10.0");
+    assert_not_found_json("/api/golang/golang.org%2Fx%2Ftools%2Fgopls/versions/v2.0.0");
+}
+
+#[test]
+fn golang_case_encoding() {
+    assert_eq!(
+        json!(["v1.3.0", "v1.2.1", "v0.4.1"]),
+        assert_ok_json(
+            "/api/golang/github.com%2FBurntSushi%2Ftoml/versions/all",
+            ALL_VERSIONS
+        )
+    );
+}
+
+#[test]
+fn golang_all_majors() {
+    assert_eq!(
+        json!(["v4.1.2+incompatible", "v4.0.0+incompatible", "v1.5.4"]),
+        assert_ok_json(
+            "/api/golang/github.com%2Fgo-chi%2Fchi/versions/all",
+            ALL_VERSIONS
+        )
+    );
+    assert_eq!(
+        json!([
+            "v5.0.12",
+            "v5.0.8",
+            "v4.1.2+incompatible",
+            "v4.0.0+incompatible",
+            "v1.5.4"
+        ]),
+        assert_ok_json(
+            "/api/golang/github.com%2Fgo-chi%2Fchi/versions/all?all_majors=true",
+            ALL_VERSIONS
+        )
+    );
+}
+
+#[test]
+fn openvsx() {
+    assert_eq!(
+        json!(["0.38.2", "0.38.1", "0.9.0"]),
+        assert_ok_json(
+            "/api/openvsx/vscjava/vscode-java-test/versions/all?include_prerelease=false",
+            ALL_VERSIONS
+        )
+    );
+    // 0.39.0 is published as a pre-release, although its version doesn't say so.
+    assert_eq!(
+        json!(["0.39.0", "0.38.2", "0.38.1", "0.9.0"]),
+        assert_ok_json(
+            "/api/openvsx/vscjava/vscode-java-test/versions/all",
+            ALL_VERSIONS
+        )
+    );
+    assert_eq!(
+        "0.38.2",
+        assert_ok_json(
+            "/api/openvsx/vscjava/vscode-java-test/versions/latest",
+            VERSION
+        )["version"]
+    );
+    assert_eq!(
+        "0.39.0",
+        assert_ok_json(
+            "/api/openvsx/vscjava/vscode-java-test/versions/latest?include_prerelease=true",
+            VERSION
+        )["version"]
+    );
+    assert_eq!(
+        json!({
+            "name": "vscjava/vscode-java-test",
+            "version": "0.38.1",
+            "target_platform": "universal",
+            "download_url": "https://open-vsx.org/api/vscjava/vscode-java-test/0.38.1/file/vscjava.vscode-java-test-0.38.1.vsix"
+        }),
+        assert_ok_json(
+            "/api/openvsx/vscjava/vscode-java-test/versions/0.38.1",
+            VERSION
+        )
+    );
+    assert_not_found_json("/api/openvsx/vscjava/vscode-java-test/versions/0.1.0");
+}
+
+#[test]
+fn openvsx_target_platform() {
+    let latest = assert_ok_json(
+        "/api/openvsx/rust-lang/rust-analyzer/versions/latest?target=linux-x64",
+        VERSION,
+    );
+    assert_eq!("0.3.1549", latest["version"]);
+    assert_eq!("linux-x64", latest["target_platform"]);
+    assert_eq!(
+        "https://open-vsx.org/api/rust-lang/rust-analyzer/linux-x64/0.3.1549/file/rust-lang.rust-analyzer-0.3.1549@linux-x64.vsix",
+        latest["download_url"]
+    );
+    assert_eq!(
+        "linux-x64",
+        assert_ok_json(
+            "/api/openvsx/rust-lang/rust-analyzer/versions/0.3.1500?target=linux-x64",
+            VERSION
+        )["target_platform"]
+    );
+
+    // Universal extensions are resolved regardless of the target platform.
+    assert_eq!(
+        "universal",
+        assert_ok_json(
+            "/api/openvsx/vscjava/vscode-java-test/versions/latest?target=linux-x64",
+            VERSION
+        )["target_platform"]
+    );
+    // Platform-specific builds of other platforms are not.
+    assert_not_found_json("/api/openvsx/rust-lang/rust-analyzer/versions/latest?target=win32-x64");
+
+    let response = get("/api/openvsx/rust-lang/rust-analyzer/versions/latest?target=linux");
+    assert_eq!(400, response.status().as_u16());
+}
+
+#[test]
+fn maven() {
+    assert_eq!(
+        json!(["3.37.0", "3.36.0", "3.10.0", "3.9.1"]),
+        assert_ok_json(
+            "/api/maven/org.eclipse.jdt/org.eclipse.jdt.core/versions/all",
+            ALL_VERSIONS
+        )
+    );
+    assert_eq!(
+        json!([
+            "3.38.0-SNAPSHOT",
+            "3.37.0",
+            "3.37.0-M1",
+            "3.36.0",
+            "3.10.0",
+            "3.9.1"
+        ]),
+        assert_ok_json(
+            "/api/maven/org.eclipse.jdt/org.eclipse.jdt.core/versions/all?include_prerelease=true",
+            ALL_VERSIONS
+        )
+    );
+    assert_eq!(
+        json!({"name": "org.eclipse.jdt:org.eclipse.jdt.core", "version": "3.37.0"}),
+        assert_ok_json(
+            "/api/maven/org.eclipse.jdt/org.eclipse.jdt.core/versions/latest",
+            VERSION
+        )
+    );
+    assert_eq!(
+        "3.36.0",
+        assert_ok_json(
+            "/api/maven/org.eclipse.jdt/org.eclipse.jdt.core/versions/3.36.0",
+            VERSION
+        )["version"]
+    );
+    assert_not_found_json("/api/maven/org.eclipse.jdt/org.eclipse.jdt.core/versions/3.0.0");
+    assert_not_found_json("/api/maven/org.eclipse.jdt/does-not-exist/versions/latest");
+
+    let response = get("/api/maven/org..eclipse/org.eclipse.jdt.core/versions/latest");
+    assert_eq!(400, response.status().as_u16());
+}
+
+#[test]
+fn nuget() {
+    assert_eq!(
+        json!(["0.26.10", "0.26.7", "0.25.0"]),
+        assert_ok_json("/api/nuget/CSharpier/versions/all", ALL_VERSIONS)
+    );
+    assert_eq!(
+        json!([
+            "0.28.0-beta.1",
+            "0.26.10",
+            "0.26.7",
+            "0.26.0-beta.1",
+            "0.25.0"
+        ]),
+        assert_ok_json(
+            "/api/nuget/csharpier/versions/all?include_prerelease=true",
+            ALL_VERSIONS
+        )
+    );
+    // 0.27.0 is unlisted.
+    assert_eq!(
+        json!(["0.27.0", "0.26.10", "0.26.7", "0.25.0"]),
+        assert_ok_json(
+            "/api/nuget/csharpier/versions/all?include_yanked=true",
+            ALL_VERSIONS
+        )
+    );
+    assert_eq!(
+        json!({"name": "CSharpier", "version": "0.26.10"}),
+        assert_ok_json("/api/nuget/csharpier/versions/latest", VERSION)
+    );
+    assert_eq!(
+        "0.28.0-beta.1",
+        assert_ok_json(
+            "/api/nuget/CSharpier/versions/latest?include_prerelease=true",
+            VERSION
+        )["version"]
+    );
+    // Unlisted versions are still resolved when requested explicitly.
+    assert_eq!(
+        "0.27.0",
+        assert_ok_json("/api/nuget/CSharpier/versions/0.27.0", VERSION)["version"]
+    );
+    assert_eq!(
+        "0.26.7",
+        assert_ok_json("/api/nuget/CSharpier/versions/0.26.7.0", VERSION)["version"]
+    );
+    assert_not_found_json("/api/nuget/CSharpier/versions/0.1.0");
+    assert_not_found_json("/api/nuget/does-not-exist/versions/latest");
+
+    let response = get("/api/nuget/..CSharpier/versions/latest");
+    assert_eq!(400, response.status().as_u16());
+}
+
+#[test]
+fn luarocks() {
+    assert_eq!(
+        json!(["0.26.1-2", "0.26.1-1", "0.25.0-1"]),
+        assert_ok_json("/api/luarocks/luacheck/versions/all", ALL_VERSIONS)
+    );
+    assert_eq!(
+        json!(["scm-1", "1.0.0rc1-1", "0.26.1-2", "0.26.1-1", "0.25.0-1"]),
+        assert_ok_json(
+            "/api/luarocks/luacheck/versions/all?include_prerelease=true",
+            ALL_VERSIONS
+        )
+    );
+    assert_eq!(
+        json!({"name": "luacheck", "version": "0.26.1-2"}),
+        assert_ok_json("/api/luarocks/luacheck/versions/latest", VERSION)
+    );
+    assert_eq!(
+        "0.25.0-1",
+        assert_ok_json("/api/luarocks/luacheck/versions/0.25.0-1", VERSION)["version"]
+    );
+    assert_not_found_json("/api/luarocks/luacheck/versions/0.1.0-1");
+    assert_not_found_json("/api/luarocks/does-not-exist/versions/latest");
+}
+
+#[test]
+fn hackage() {
+    assert_eq!(
+        json!(["3.10", "3.9", "3.8", "3.6.1", "3.6", "3.5"]),
+        assert_ok_json("/api/hackage/hlint/versions/all", ALL_VERSIONS)
+    );
+    assert_eq!(
+        json!(["3.10", "3.9", "3.8", "3.7", "3.6.1", "3.6", "3.5", "3.4.1"]),
+        assert_ok_json(
+            "/api/hackage/hlint/versions/all?include_deprecated=true",
+            ALL_VERSIONS
+        )
+    );
+    assert_eq!(
+        json!({"name": "hlint", "version": "3.10", "deprecated": false}),
+        assert_ok_json("/api/hackage/hlint/versions/latest", VERSION)
+    );
+    assert_eq!(
+        json!({"name": "hlint", "version": "3.9", "deprecated": false}),
+        assert_ok_json("/api/hackage/hlint/versions/3.9", VERSION)
+    );
+    assert_eq!(
+        json!({"name": "hlint", "version": "3.7", "deprecated": true}),
+        assert_ok_json("/api/hackage/hlint/versions/3.7", VERSION)
+    );
+    assert_not_found_json("/api/hackage/hlint/versions/1.0");
+    assert_not_found_json("/api/hackage/does-not-exist/versions/latest");
+
+    let response = get("/api/hackage/hlint_/versions/latest");
+    assert_eq!(400, response.status().as_u16());
+}
+
+#[test]
+fn opam() {
+    assert_eq!(
+        json!(["1.17.0", "1.10.0", "1.9.0", "1.8.3"]),
+        assert_ok_json("/api/opam/ocaml-lsp-server/versions/all", ALL_VERSIONS)
+    );
+    assert_eq!(
+        json!([
+            "1.18.0~5.2preview",
+            "1.17.0",
+            "1.10.0",
+            "1.10.0~beta",
+            "1.9.0",
+            "1.8.3"
+        ]),
+        assert_ok_json(
+            "/api/opam/ocaml-lsp-server/versions/all?include_prerelease=true",
+            ALL_VERSIONS
+        )
+    );
+    assert_eq!(
+        json!({"name": "ocaml-lsp-server", "version": "1.17.0"}),
+        assert_ok_json("/api/opam/ocaml-lsp-server/versions/latest", VERSION)
+    );
+    assert_eq!(
+        json!({"name": "ocaml-lsp-server", "version": "1.18.0~5.2preview"}),
+        assert_ok_json(
+            "/api/opam/ocaml-lsp-server/versions/latest?include_prerelease=true",
+            VERSION
+        )
+    );
+    assert_eq!(
+        json!({"name": "ocaml-lsp-server", "version": "1.10.0~beta"}),
+        assert_ok_json("/api/opam/ocaml-lsp-server/versions/1.10.0~beta", VERSION)
+    );
+    assert_not_found_json("/api/opam/ocaml-lsp-server/versions/1.0.0");
+    assert_not_found_json("/api/opam/ocaml-lsp/versions/latest");
+}
+
+#[test]
+fn purl() {
+    assert_eq!(
+        json!(["0.1.7", "0.1.6", "0.1.1", "0.1.0"]),
+        assert_ok_json(
+            "/api/purl/pkg%3Apypi%2Fcmake_language_server/versions/all?include_prerelease=false&include_yanked=false",
+            ALL_VERSIONS
+        )
+    );
+    assert_eq!(
+        json!(["0.2.0rc1", "0.1.7", "0.1.6", "0.1.1", "0.1.0"]),
+        assert_ok_json(
+            "/api/purl/pkg%3Apypi%2Fcmake_language_server/versions/all?include_yanked=false",
+            ALL_VERSIONS
+        )
+    );
+    assert_eq!(
+        json!(["0.1.10", "0.1.7", "0.1.6", "0.1.1", "0.1.0"]),
+        assert_ok_json(
+            "/api/purl/pkg%3Apypi%2Fcmake_language_server/versions/all?include_prerelease=false",
+            ALL_VERSIONS
+        )
+    );
+    assert_eq!(
+        json!({"name": "@ansible/ansible-language-server", "version": "1.0.4"}),
+        assert_ok_json(
+            "/api/purl/pkg%3Anpm%2F%2540ansible%2Fansible-language-server/versions/latest",
+            VERSION
+        )
+    );
+    assert_eq!(
+        "v3.6.4",
+        assert_ok_json(
+            "/api/purl/pkg%3Agithub%2Fsumneko%2Fvscode-lua%40v3.6.18/versions/v3.6.4",
+            VERSION
+        )["version"]
+    );
+    assert_eq!(
+        "v0.12.0",
+        assert_ok_json(
+            "/api/purl/pkg%3Agolang%2Fgolang.org%2Fx%2Ftools%2Fgopls/versions/latest",
+            VERSION
+        )["version"]
+    );
+    assert_eq!(
+        "3.37.0",
+        assert_ok_json(
+            "/api/purl/pkg%3Amaven%2Forg.eclipse.jdt%2Forg.eclipse.jdt.core/versions/latest",
+            VERSION
+        )["version"]
+    );
+    assert_eq!(
+        "0.26.10",
+        assert_ok_json("/api/purl/pkg%3Anuget%2FCSharpier/versions/latest", VERSION)["version"]
+    );
+    assert_eq!(
+        "2.1.0.10-1",
+        assert_ok_json(
+            "/api/purl/pkg%3Aluarocks%2Flua-cjson/versions/latest",
+            VERSION
+        )["version"]
+    );
+    assert_eq!(
+        "3.10",
+        assert_ok_json("/api/purl/pkg%3Ahackage%2Fhlint/versions/latest", VERSION)["version"]
+    );
+    assert_eq!(
+        "0.27.0",
+        assert_ok_json(
+            "/api/purl/pkg%3Aopam%2Focamlformat/versions/latest",
+            VERSION
+        )["version"]
+    );
+    let latest = assert_ok_json(
+        "/api/purl/pkg%3Aopenvsx%2Frust-lang%2Frust-analyzer%3Ftarget%3Dlinux-x64/versions/latest",
+        VERSION,
+    );
+    assert_eq!("0.3.1549", latest["version"]);
+    assert_eq!("linux-x64", latest["target_platform"]);
+    assert_eq!(
+        "linux-x64",
+        assert_ok_json(
+            "/api/purl/pkg%3Aopenvsx%2Frust-lang%2Frust-analyzer/versions/0.3.1500?target=linux-x64",
+            VERSION
+        )["target_platform"]
+    );
+    assert_eq!(
+        400,
+        get("/api/purl/pkg%3Aopenvsx%2Frust-lang%2Frust-analyzer%3Ftarget%3Dlinux/versions/latest")
+            .status()
+            .as_u16()
+    );
+    assert_not_found_json("/api/purl/pkg%3Acargo%2Fcrates_io_api/versions/17287138");
+
+    let repository_url = std::env::var("NPM_REGISTRY_URL").unwrap();
+    assert_eq!(
+        "3.3.2",
+        assert_ok_json(
+            &format!(
+                "/api/purl/{}/versions/latest",
+                encode(&format!(
+                    "pkg:npm/typescript-language-server?repository_url={}",
+                    encode(&repository_url)
+                ))
+            ),
+            VERSION
+        )["version"]
+    );
+}
+
+#[test]
+fn purl_invalid() {
+    for purl in [
+        "typescript",
+        "pkg%3Acpan%2FMoose",
+        "pkg%3Agithub%2Fvscode-lua",
+        "pkg%3Anpm%2Ftypescript%3Frepository_url%3Dfile%3A%2F%2F%2Fetc",
+        "pkg%3Anpm%2Ftypescript%3Frepository_url%3Dhttp%3A%2F%2F169.254.169.254",
+    ] {
+        assert_eq!(
+            400,
+            get(&format!("/api/purl/{purl}/versions/latest"))
+                .status()
+                .as_u16(),
+            "{purl}"
+        );
+    }
+}
+
+#[test]
+fn github_releases() {
+    for prefix in ["/api/github", "/api/repo"] {
+        assert_eq!(
+            json!([
+                "v3.7.0",
+                "v3.6.18",
+                "v3.6.17",
+                "v3.7.0-beta.1",
+                "v3.6.4",
+                "v3.6.3",
+                "v3.6.2",
+                "v3.6.1"
+            ]),
+            assert_ok_json(
+                &format!("{prefix}/sumneko/vscode-lua/releases/all"),
+                ALL_VERSIONS
+            )
+        );
+        assert_eq!(
+            json!(["v3.6.18", "v3.6.17", "v3.6.4", "v3.6.3", "v3.6.2", "v3.6.1"]),
+            assert_ok_json(
+                &format!(
+                    "{prefix}/sumneko/vscode-lua/releases/all?include_prerelease=false&include_draft=false"
+                ),
+                ALL_VERSIONS
+            )
+        );
+        assert_eq!(
+            json!([
+                "v3.6.18",
+                "v3.6.17",
+                "v3.7.0-beta.1",
+                "v3.6.4",
+                "v3.6.3",
+                "v3.6.2",
+                "v3.6.1"
+            ]),
+            assert_ok_json(
+                &format!("{prefix}/sumneko/vscode-lua/releases/all?include_draft=false"),
+                ALL_VERSIONS
+            )
+        );
+        // v3.7.0 is a draft.
+        assert_eq!(
+            json!(["v3.7.0", "v3.6.18", "v3.6.17", "v3.6.4", "v3.6.3", "v3.6.2", "v3.6.1"]),
+            assert_ok_json(
+                &format!("{prefix}/sumneko/vscode-lua/releases/all?include_prerelease=false"),
+                ALL_VERSIONS
+            )
+        );
+        assert_eq!(
+            "v3.6.18",
+            assert_ok_json(
+                &format!("{prefix}/sumneko/vscode-lua/releases/latest"),
+                RELEASE
+            )["tag_name"]
+        );
+        assert_eq!(
+            "v3.6.4",
+            assert_ok_json(
+                &format!("{prefix}/sumneko/vscode-lua/releases/v3.6.4"),
+                RELEASE
+            )["tag_name"]
+        );
+        assert_not_found_json(&format!("{prefix}/sumneko/vscode-lua/releases/17287138"));
+    }
+}
+
+#[test]
+fn github_secondary_rate_limit() {
+    let response = get("/api/github/sumneko/vscode-lua/releases/v0.0.0-rate-limited");
+    assert_eq!(429, response.status().as_u16());
+    assert_eq!("60", response.headers()[RETRY_AFTER]);
+
+    let response = get("/api/github/sumneko/vscode-lua/releases/v0.0.0-forbidden");
+    assert_eq!(429, response.status().as_u16());
+    assert_eq!("30", response.headers()[RETRY_AFTER]);
+}
+
+#[test]
+fn github_tags() {
+    for prefix in ["/api/github", "/api/repo"] {
+        assert_eq!(
+            json!(["v3.6.18", "v3.6.17", "v3.6.4"]),
+            assert_ok_json(
+                &format!("{prefix}/sumneko/vscode-lua/tags/all"),
+                ALL_VERSIONS
+            )
+        );
+        assert_eq!(
+            json!({"tag": "v3.6.18"}),
+            assert_ok_json(&format!("{prefix}/sumneko/vscode-lua/tags/latest"), TAG)
+        );
+        assert_eq!(
+            json!({"tag": "v3.6.4"}),
+            assert_ok_json(&format!("{prefix}/sumneko/vscode-lua/tags/v3.6.4"), TAG)
+        );
+        assert_not_found_json(&format!("{prefix}/sumneko/vscode-lua/tags/17287138"));
+    }
+}
+
+#[test]
+fn mason_sponsors() {
+    assert_eq!(
+        json!({"current_sponsors": ["octocat", "hubot"]}),
+        assert_ok_json("/api/mason/sponsors", "schemas/mason/sponsors.json")
+    );
+}
+
+#[test]
+fn mason_renovate_badge() {
+    assert_eq!(
+        "red",
+        assert_ok_json("/api/mason/renovate/badge", "schemas/badge.json")["color"]
+    );
+    assert_eq!(
+        405,
+        post_json("/api/mason/renovate/badge", &json!({}))
+            .status()
+            .as_u16()
+    );
+}
+
+#[test]
+fn batch() {
+    let packages = json!([
+        {"ecosystem": "npm", "package": "typescript-language-server"},
+        {"ecosystem": "npm", "package": "@ansible/ansible-language-server"},
+        {"ecosystem": "pypi", "package": "cmake-language-server"},
+        {"ecosystem": "github", "package": "sumneko/vscode-lua"},
+        {"ecosystem": "npm", "package": "does-not-exist"},
+        {"ecosystem": "github", "package": "vscode-lua"},
+        {"purl": "pkg:npm/%40ansible/ansible-language-server"},
+        {"purl": "pkg:github/sumneko/vscode-lua"},
+        {"ecosystem": "npm", "package": "typescript-language-server", "include_prerelease": true},
+        {"purl": "pkg:npm"},
+        {"ecosystem": "maven", "package": "org.eclipse.jdt:org.eclipse.jdt.core"},
+        {"ecosystem": "maven", "package": "org.eclipse.jdt/org.eclipse.jdt.core"},
+        {"ecosystem": "nuget", "package": "CSharpier"},
+        {"ecosystem": "luarocks", "package": "luacheck"},
+        {"ecosystem": "hackage", "package": "hlint"},
+        {"ecosystem": "opam", "package": "ocamlformat"},
+    ]);
+    let response = assert_ok_json_post("/api/batch", &json!({ "packages": packages }), BATCH);
+    let items = response.as_array().unwrap();
+    assert_eq!(packages.as_array().unwrap().len(), items.len());
+    assert_eq!(
+        json!({
+            "ecosystem": "npm",
+            "package": "typescript-language-server",
+            "version": {"name": "typescript-language-server", "version": "3.3.2"}
+        }),
+        items[0]
+    );
+    assert_eq!("1.0.4", items[1]["version"]["version"]);
+    assert_eq!("0.1.7", items[2]["version"]["version"]);
+    assert_eq!("v3.6.18", items[3]["version"]["version"]);
+    assert_eq!(404, items[4]["error"]["status"]);
+    assert_eq!(400, items[5]["error"]["status"]);
+    assert_eq!(
+        json!({
+            "purl": "pkg:npm/%40ansible/ansible-language-server",
+            "version": {"name": "@ansible/ansible-language-server", "version": "1.0.4"}
+        }),
+        items[6]
+    );
+    assert_eq!("v3.6.18", items[7]["version"]["version"]);
+    assert_eq!(true, items[8]["include_prerelease"]);
+    assert_eq!("4.0.0-rc.1", items[8]["version"]["version"]);
+    assert_eq!(400, items[9]["error"]["status"]);
+    assert_eq!("3.37.0", items[10]["version"]["version"]);
+    assert_eq!(400, items[11]["error"]["status"]);
+    assert_eq!("0.26.10", items[12]["version"]["version"]);
+    assert_eq!("0.26.1-2", items[13]["version"]["version"]);
+    assert_eq!("3.10", items[14]["version"]["version"]);
+    assert_eq!("0.27.0", items[15]["version"]["version"]);
+}
+
+#[test]
+fn batch_invalid_request() {
+    assert_eq!(
+        400,
+        post_json("/api/batch", &json!({"packages": "npm"}))
+            .status()
+            .as_u16()
+    );
+    assert_eq!(405, get("/api/batch").status().as_u16());
+}
+
+#[test]
+fn batch_too_large() {
+    let packages: Vec<_> = (0..10_000)
+        .map(|i| json!({"ecosystem": "npm", "package": format!("package-{i}")}))
+        .collect();
+    assert_eq!(
+        413,
+        post_json("/api/batch", &json!({ "packages": packages }))
+            .status()
+            .as_u16()
+    );
+}
@@ -0,0 +1,244 @@
+//! Offline test harness. Serves the API on a local port with every upstream registry pointed at a
+//! stub server which replays the recorded responses in `tests/fixtures/`.
+
+use std::{
+    collections::BTreeMap,
+    convert::Infallible,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    sync::{Arc, OnceLock},
+};
+
+use http::{header::CONTENT_TYPE, HeaderMap, Method, StatusCode};
+use http_body_util::{BodyExt, Full};
+use hyper::{
+    body::{Bytes, Incoming},
+    server::conn::http1,
+    service::service_fn,
+};
+use hyper_util::rt::TokioIo;
+use serde::Deserialize;
+use serde_json::Value;
+use tokio::net::TcpListener;
+
+/// Upstream base URL environment variables, and the path prefix their fixtures are served under.
+const UPSTREAMS: &[(&str, &str)] = &[
+    ("NPM_REGISTRY_URL", "npm"),
+    ("PYPI_URL", "pypi"),
+    ("CRATES_API_URL", "crates"),
+    ("RUBYGEMS_URL", "rubygems"),
+    ("PACKAGIST_URL", "packagist"),
+    ("GOLANG_PROXY_URL", "golang"),
+    ("OPENVSX_URL", "openvsx"),
+    ("MAVEN_REPOSITORY_URL", "maven"),
+    ("NUGET_URL", "nuget"),
+    ("LUAROCKS_URL", "luarocks"),
+    ("HACKAGE_URL", "hackage"),
+    ("OPAM_REPOSITORY_URL", "opam"),
+    ("GITHUB_API_URL", "github"),
+    ("RENOVATE_API_URL", "renovate"),
+];
+
+#[derive(Deserialize)]
+struct Fixture {
+    method: String,
+    path: String,
+    query: Option<String>,
+    /// Only match requests whose body contains this string, e.g. the name of a GraphQL query.
+    body_contains: Option<String>,
+    file: PathBuf,
+    status: Option<u16>,
+    #[serde(default)]
+    headers: BTreeMap<String, String>,
+}
+
+impl Fixture {
+    fn matches(&self, method: &Method, path: &str, query: Option<&str>, body: &str) -> bool {
+        self.method == method.as_str()
+            && self.path == path
+            && self.query.as_deref() == query
+            && self
+                .body_contains
+                .as_ref()
+                .is_none_or(|needle| body.contains(needle))
+    }
+}
+
+fn project_dir() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+}
+
+fn load_fixtures() -> Vec<Fixture> {
+    let manifest = std::fs::read_to_string(project_dir().join("tests/fixtures/manifest.json"))
+        .expect("Failed to read fixture manifest.");
+    serde_json::from_str(&manifest).expect("Failed to parse fixture manifest.")
+}
+
+async fn replay(
+    fixtures: Arc<Vec<Fixture>>,
+    base_url: Arc<String>,
+    request: http::Request<Incoming>,
+) -> Result<http::Response<Full<Bytes>>, Infallible> {
+    let method = request.method().clone();
+    let path = request.uri().path().to_owned();
+    let query = request.uri().query().map(ToOwned::to_owned);
+    let body = request
+        .into_body()
+        .collect()
+        .await
+        .map(|body| String::from_utf8_lossy(&body.to_bytes()).into_owned())
+        .unwrap_or_default();
+
+    let Some(fixture) = fixtures
+        .iter()
+        .find(|fixture| fixture.matches(&method, &path, query.as_deref(), &body))
+    else {
+        return Ok(http::Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .header(CONTENT_TYPE, "application/json")
+            .body(Full::new(Bytes::from_static(br#"{"message":"Not Found"}"#)))
+            .unwrap());
+    };
+
+    let contents = std::fs::read(project_dir().join("tests/fixtures").join(&fixture.file))
+        .expect("Failed to read fixture.");
+    // Binary fixtures, e.g. archives, are served as is.
+    let contents = match String::from_utf8(contents) {
+        Ok(contents) => contents.replace("{{base_url}}", &base_url).into_bytes(),
+        Err(err) => err.into_bytes(),
+    };
+    let mut response = http::Response::builder().status(fixture.status.unwrap_or(200));
+    if !fixture.headers.contains_key(CONTENT_TYPE.as_str()) {
+        response = response.header(CONTENT_TYPE, "application/json");
+    }
+    for (key, value) in &fixture.headers {
+        response = response.header(key, value.replace("{{base_url}}", &base_url));
+    }
+    Ok(response.body(Full::new(Bytes::from(contents))).unwrap())
+}
+
+async fn serve_fixtures(listener: TcpListener) {
+    let fixtures = Arc::new(load_fixtures());
+    let base_url = Arc::new(format!("http://{}", listener.local_addr().unwrap()));
+    loop {
+        let (stream, _) = listener.accept().await.unwrap();
+        let fixtures = fixtures.clone();
+        let base_url = base_url.clone();
+        tokio::spawn(async move {
+            let service =
+                service_fn(move |request| replay(fixtures.clone(), base_url.clone(), request));
+            let _ = http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), service)
+                .await;
+        });
+    }
+}
+
+fn bind() -> std::net::TcpListener {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    listener.set_nonblocking(true).unwrap();
+    listener
+}
+
+/// Returns the address of the API server, starting it (and the upstream stub) on first use.
+fn server() -> SocketAddr {
+    static SERVER: OnceLock<SocketAddr> = OnceLock::new();
+    *SERVER.get_or_init(|| {
+        let upstream = bind();
+        let api = bind();
+        let upstream_url = format!("http://{}", upstream.local_addr().unwrap());
+        for (key, prefix) in UPSTREAMS {
+            std::env::set_var(key, format!("{upstream_url}/{prefix}"));
+        }
+        std::env::set_var("GITHUB_API_KEY", "test-api-key");
+        std::env::set_var("PURL_REPOSITORY_URLS", format!("{upstream_url}/npm"));
+
+        let addr = api.local_addr().unwrap();
+        std::thread::spawn(move || {
+            tokio::runtime::Runtime::new()
+                .unwrap()
+                .block_on(async move {
+                    tokio::spawn(serve_fixtures(TcpListener::from_std(upstream).unwrap()));
+                    mason_registry_api::server::serve(TcpListener::from_std(api).unwrap())
+                        .await
+                        .unwrap();
+                });
+        });
+        addr
+    })
+}
+
+pub fn get(path: &str) -> reqwest::blocking::Response {
+    get_with_headers(path, HeaderMap::new())
+}
+
+pub fn get_with_headers(path: &str, headers: HeaderMap) -> reqwest::blocking::Response {
+    reqwest::blocking::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .unwrap()
+        .get(format!("http://{}{}", server(), path))
+        .headers(headers)
+        .send()
+        .unwrap_or_else(|err| panic!("GET {path} failed: {err}"))
+}
+
+pub fn post_json(path: &str, body: &Value) -> reqwest::blocking::Response {
+    reqwest::blocking::Client::new()
+        .post(format!("http://{}{}", server(), path))
+        .json(body)
+        .send()
+        .unwrap_or_else(|err| panic!("POST {path} failed: {err}"))
+}
+
+fn assert_json(path: &str, status: StatusCode, schema_file: &str) -> Value {
+    assert_json_response(path, get(path), status, schema_file)
+}
+
+fn assert_json_response(
+    path: &str,
+    response: reqwest::blocking::Response,
+    status: StatusCode,
+    schema_file: &str,
+) -> Value {
+    assert_eq!(status, response.status(), "{path}");
+    assert_eq!(
+        Some("application/json"),
+        response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok()),
+        "{path}"
+    );
+    let body: Value = response.json().expect("Failed to parse response body.");
+
+    let schema =
+        std::fs::read_to_string(project_dir().join(schema_file)).expect("Failed to read schema.");
+    let validator = jsonschema::validator_for(&serde_json::from_str(&schema).unwrap())
+        .expect("Invalid schema.");
+    let errors: Vec<String> = validator
+        .iter_errors(&body)
+        .map(|err| err.to_string())
+        .collect();
+    assert!(
+        errors.is_empty(),
+        "{path} does not match {schema_file}: {errors:?}\n{body:#}"
+    );
+    body
+}
+
+/// Asserts that `path` responds with 200 and a JSON body valid against `schema_file`.
+pub fn assert_ok_json(path: &str, schema_file: &str) -> Value {
+    assert_json(path, StatusCode::OK, schema_file)
+}
+
+/// Asserts that posting `body` to `path` responds with 200 and a JSON body valid against
+/// `schema_file`.
+pub fn assert_ok_json_post(path: &str, body: &Value, schema_file: &str) -> Value {
+    assert_json_response(path, post_json(path, body), StatusCode::OK, schema_file)
+}
+
+/// Asserts that `path` responds with 404 and a JSON error body.
+pub fn assert_not_found_json(path: &str) -> Value {
+    assert_json(path, StatusCode::NOT_FOUND, "schemas/errors/not_found.json")
+}